@@ -0,0 +1,173 @@
+#![no_std]
+use soroban_sdk::{
+    contracterror,
+    contracttype,
+    panic_with_error,
+    Env,
+    IntoVal,
+    Symbol,
+    Val,
+    symbol_short,
+};
+
+// ============================================================
+// CHECKPOINTS: VALOR EN EL LEDGER N (REUTILIZABLE)
+// ============================================================
+//
+// Funciones para poder preguntar "¿cuánto valía esto en el ledger N?"
+// (auditorías, votaciones con snapshot):
+//
+//   env.storage().persistent().set(&key, &balance);
+//   checkpoints::registrar(&env, &key, balance);    // después de cada escritura
+//
+//   checkpoints::valor_en(&env, &key, ledger)       // consulta
+//
+// Cada clave guarda su lista de (secuencia del ledger, valor) en
+// Persistent Storage, un checkpoint por key, en orden creciente de
+// ledger. Varias escrituras en el mismo ledger dejan un solo
+// checkpoint (el último valor). La consulta es una búsqueda binaria:
+// O(log n) lecturas.
+//
+// La clave puede ser cualquier valor de contrato; lo natural es usar
+// la misma key de storage del valor (un Symbol, una variante de DataKey).
+//
+// La historia tiene que durar tanto como las consultas que dependen de
+// ella (un snapshot de votación, una auditoría), así que las entradas
+// viven `TTL_CHECKPOINT` ledgers y se extienden también al leerlas.
+
+// ============================================================
+// DEFINICIÓN DE ERRORES PERSONALIZADOS
+// ============================================================
+
+/// Errores de los checkpoints
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    CheckpointFaltante = 1, // Falta un checkpoint por debajo del total
+}
+
+// ============================================================
+// TIPOS Y STORAGE
+// ============================================================
+
+/// Valor de una clave desde el ledger `ledger` (Persistent Storage)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,            // Secuencia del ledger
+    pub valor: i128,
+}
+
+/// TTL de las entradas de checkpoints (~1 año de 17_280 ledgers por día)
+///
+/// Se limita al máximo de la red al extender.
+pub const TTL_CHECKPOINT: u32 = 365 * 17_280;
+
+// Storage keys (Persistent Storage). Son tuplas y no un DataKey
+// porque la clave es un `Val` cualquiera:
+//   ("ckpt_n", clave)          → cantidad de checkpoints
+//   ("ckpt", clave, índice)    → Checkpoint
+
+fn key_total(clave: Val) -> (Symbol, Val) {
+    (symbol_short!("ckpt_n"), clave)
+}
+
+fn key_checkpoint(clave: Val, indice: u32) -> (Symbol, Val, u32) {
+    (symbol_short!("ckpt"), clave, indice)
+}
+
+// ============================================================
+// FUNCIONES
+// ============================================================
+
+/// Registra que `clave` vale `valor` desde el ledger actual
+///
+/// # Panics
+/// Con `Error::CheckpointFaltante` si falta el último checkpoint.
+pub fn registrar<K: IntoVal<Env, Val>>(env: &Env, clave: &K, valor: i128) {
+    let clave: Val = clave.into_val(env);
+    let ledger = env.ledger().sequence();
+    let total = total(env, clave);
+
+    // Misma secuencia que el último checkpoint: se reemplaza
+    let reemplazar = total > 0 && checkpoint(env, clave, total - 1).ledger == ledger;
+    let indice = if reemplazar { total - 1 } else { total };
+
+    let key = key_checkpoint(clave, indice);
+    env.storage().persistent().set(&key, &Checkpoint { ledger, valor });
+    extender(env, &key);
+
+    if indice == total {
+        let key = key_total(clave);
+        env.storage().persistent().set(&key, &(total + 1));
+        extender(env, &key);
+    }
+}
+
+/// Valor de `clave` al cierre del ledger `ledger`
+///
+/// 0 si la clave no tenía valor todavía (igual que los `unwrap_or(0)`
+/// de lectura).
+///
+/// # Panics
+/// Con `Error::CheckpointFaltante` si falta un checkpoint de la
+/// historia: para una auditoría o un snapshot de votación es mejor
+/// fallar que devolver un valor equivocado.
+pub fn valor_en<K: IntoVal<Env, Val>>(env: &Env, clave: &K, ledger: u32) -> i128 {
+    let clave: Val = clave.into_val(env);
+
+    // Búsqueda binaria del último checkpoint con ledger <= `ledger`:
+    // [0, bajo) son <= y [alto, total) son >
+    let mut bajo = 0;
+    let mut alto = total(env, clave);
+    while bajo < alto {
+        let medio = bajo + (alto - bajo) / 2;
+        if checkpoint(env, clave, medio).ledger <= ledger {
+            bajo = medio + 1;
+        } else {
+            alto = medio;
+        }
+    }
+
+    if bajo == 0 {
+        return 0;
+    }
+    checkpoint(env, clave, bajo - 1).valor
+}
+
+/// Cantidad de checkpoints de `clave`
+pub fn cantidad<K: IntoVal<Env, Val>>(env: &Env, clave: &K) -> u32 {
+    total(env, clave.into_val(env))
+}
+
+// Las lecturas extienden el TTL de lo que encuentran: una historia que
+// se sigue consultando no expira.
+
+fn total(env: &Env, clave: Val) -> u32 {
+    let key = key_total(clave);
+    let total = env.storage().persistent().get(&key);
+    if total.is_some() {
+        extender(env, &key);
+    }
+    total.unwrap_or(0)
+}
+
+/// Checkpoint `indice` de `clave` (siempre por debajo del total)
+fn checkpoint(env: &Env, clave: Val, indice: u32) -> Checkpoint {
+    let key = key_checkpoint(clave, indice);
+    let checkpoint = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| panic_with_error!(env, Error::CheckpointFaltante));
+    extender(env, &key);
+    checkpoint
+}
+
+/// Extiende una entrada a `TTL_CHECKPOINT` (o al máximo de la red)
+/// cuando le queda menos de la mitad
+fn extender<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    let ttl = TTL_CHECKPOINT.min(env.storage().max_ttl());
+    env.storage().persistent().extend_ttl(key, ttl / 2, ttl);
+}
@@ -0,0 +1,114 @@
+#![cfg(test)]
+use soroban_sdk::{
+    contract,
+    testutils::{Address as _, Ledger},
+    Address,
+    Env,
+    IntoVal,
+    Val,
+    symbol_short,
+};
+use crate::checkpoints;
+
+/// Contrato vacío: solo da el contexto de storage para las funciones
+#[contract]
+struct ContratoPrueba;
+
+fn setup(env: &Env) -> Address {
+    env.register_contract(None, ContratoPrueba)
+}
+
+fn ir_al_ledger(env: &Env, secuencia: u32) {
+    env.ledger().with_mut(|li| li.sequence_number = secuencia);
+}
+
+#[test]
+fn test_valor_en_ledger() {
+    let env = Env::default();
+    let contrato = setup(&env);
+    let clave = symbol_short!("COUNTER");
+
+    env.as_contract(&contrato, || {
+        assert_eq!(checkpoints::valor_en(&env, &clave, 100), 0);
+
+        // Checkpoints en los ledgers 10, 20, ..., 100 con valor = ledger * 2
+        for ledger in (10..=100).step_by(10) {
+            ir_al_ledger(&env, ledger);
+            checkpoints::registrar(&env, &clave, ledger as i128 * 2);
+        }
+        assert_eq!(checkpoints::cantidad(&env, &clave), 10);
+
+        assert_eq!(checkpoints::valor_en(&env, &clave, 9), 0);
+        assert_eq!(checkpoints::valor_en(&env, &clave, 10), 20);
+        assert_eq!(checkpoints::valor_en(&env, &clave, 55), 100);
+        assert_eq!(checkpoints::valor_en(&env, &clave, 99), 180);
+        assert_eq!(checkpoints::valor_en(&env, &clave, 100), 200);
+        assert_eq!(checkpoints::valor_en(&env, &clave, u32::MAX), 200);
+    });
+}
+
+#[test]
+fn test_mismo_ledger_y_claves_separadas() {
+    let env = Env::default();
+    let contrato = setup(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+
+    env.as_contract(&contrato, || {
+        // Varias escrituras en un ledger dejan un checkpoint con el último valor
+        ir_al_ledger(&env, 5);
+        checkpoints::registrar(&env, &ana, 1);
+        checkpoints::registrar(&env, &ana, 2);
+        assert_eq!(checkpoints::cantidad(&env, &ana), 1);
+        assert_eq!(checkpoints::valor_en(&env, &ana, 5), 2);
+
+        // Cada clave tiene su propia historia
+        ir_al_ledger(&env, 6);
+        checkpoints::registrar(&env, &bea, 50);
+        assert_eq!(checkpoints::valor_en(&env, &ana, 6), 2);
+        assert_eq!(checkpoints::valor_en(&env, &bea, 5), 0);
+        assert_eq!(checkpoints::valor_en(&env, &bea, 6), 50);
+    });
+}
+
+#[test]
+fn test_historia_no_expira_si_se_consulta() {
+    let env = Env::default();
+    let contrato = setup(&env);
+    let ana = Address::generate(&env);
+
+    env.as_contract(&contrato, || {
+        ir_al_ledger(&env, 10);
+        checkpoints::registrar(&env, &ana, 7);
+
+        // Mucho más allá de un TTL corto: la historia sigue ahí
+        ir_al_ledger(&env, 10 + checkpoints::TTL_CHECKPOINT * 3 / 4);
+        assert_eq!(checkpoints::valor_en(&env, &ana, 10), 7);
+
+        // La lectura la extendió: pasa el vencimiento original
+        ir_al_ledger(&env, 10 + checkpoints::TTL_CHECKPOINT * 3 / 2);
+        assert_eq!(checkpoints::valor_en(&env, &ana, 10), 7);
+        assert_eq!(checkpoints::cantidad(&env, &ana), 1);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_checkpoint_faltante_panic() {
+    let env = Env::default();
+    let contrato = setup(&env);
+    let ana = Address::generate(&env);
+
+    env.as_contract(&contrato, || {
+        ir_al_ledger(&env, 5);
+        checkpoints::registrar(&env, &ana, 1);
+
+        // Sin la entrada del checkpoint la consulta falla en vez de
+        // devolver un valor equivocado
+        let clave: Val = ana.into_val(&env);
+        env.storage()
+            .persistent()
+            .remove(&(symbol_short!("ckpt"), clave, 0u32));
+        checkpoints::valor_en(&env, &ana, 5);
+    });
+}
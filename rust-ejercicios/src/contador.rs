@@ -2,10 +2,22 @@
 use soroban_sdk::{
     contract,
     contractimpl,
+    panic_with_error,
+    Address,
     Env,
     Symbol,
     symbol_short,
 };
+use crate::checkpoints;
+use crate::ownable::{
+    self,
+    Error as OwnableError,
+};
+use crate::rate_limit::{
+    self,
+    Error as RateLimitError,
+    LimiteTasa,
+};
 
 /// Contador completo en Soroban
 /// 
@@ -16,6 +28,10 @@ use soroban_sdk::{
 /// - Option en lectura de storage
 /// - Pattern matching en validaciones
 /// - Eventos para transparencia
+///
+/// Para límites, paso y permisos configurables ver `ContadorConfigurable`.
+/// Cada escritura deja un checkpoint: `get_count_at` da el valor en
+/// cualquier ledger pasado (ver `checkpoints.rs`).
 #[contract]
 pub struct ContadorContract;
 
@@ -31,16 +47,29 @@ impl ContadorContract {
     /// 4. Emitir evento
     /// 5. Retornar nuevo valor
     /// 
+    /// # Argumentos
+    /// - `llamador`: Quien incrementa (firma la llamada)
+    /// 
     /// # Retorna
     /// El nuevo valor del contador después de incrementar
     /// 
+    /// # Panic
+    /// Con `RateLimited` si `llamador` agotó sus incrementos de la
+    /// ventana (ver `set_rate_limit`). El límite es por llamador.
+    /// 
     /// # Ejemplo
     /// ```
     /// let contador = ContadorContractClient::new(&env, &contract_id);
-    /// assert_eq!(contador.increment(), 1);
-    /// assert_eq!(contador.increment(), 2);
+    /// assert_eq!(contador.increment(&usuario), 1);
+    /// assert_eq!(contador.increment(&usuario), 2);
     /// ```
-    pub fn increment(env: Env) -> u32 {
+    pub fn increment(env: Env, llamador: Address) -> u32 {
+        // PASO 0: Firma y límite de incrementos del llamador (si está configurado)
+        llamador.require_auth();
+        if let Err(error) = rate_limit::consumir(&env, &llamador) {
+            panic_with_error!(&env, error);
+        }
+
         // PASO 1: Leer del storage
         let mut contador: u32 = env.storage()
             .instance()
@@ -55,6 +84,7 @@ impl ContadorContract {
             &symbol_short!("COUNTER"),
             &contador
         );
+        checkpoints::registrar(&env, &symbol_short!("COUNTER"), contador as i128);
 
         // PASO 4: Emitir evento
         env.events().publish(
@@ -81,8 +111,8 @@ impl ContadorContract {
     /// # Ejemplo
     /// ```
     /// let contador = ContadorContractClient::new(&env, &contract_id);
-    /// contador.increment();
-    /// contador.increment();
+    /// contador.increment(&usuario);
+    /// contador.increment(&usuario);
     /// assert_eq!(contador.decrement(), 1);
     /// ```
     pub fn decrement(env: Env) -> u32 {
@@ -105,6 +135,7 @@ impl ContadorContract {
             &symbol_short!("COUNTER"),
             &contador
         );
+        checkpoints::registrar(&env, &symbol_short!("COUNTER"), contador as i128);
 
         // PASO 5: Emitir evento
         env.events().publish(
@@ -130,7 +161,7 @@ impl ContadorContract {
     /// ```
     /// let contador = ContadorContractClient::new(&env, &contract_id);
     /// assert_eq!(contador.get_count(), 0);
-    /// contador.increment();
+    /// contador.increment(&usuario);
     /// assert_eq!(contador.get_count(), 1);
     /// ```
     pub fn get_count(env: Env) -> u32 {
@@ -140,6 +171,18 @@ impl ContadorContract {
             .unwrap_or(0)
     }
 
+    /// Obtiene el valor que tenía el contador al cierre del ledger `ledger`
+    /// 
+    /// # Ejemplo
+    /// ```
+    /// let ledger = env.ledger().sequence();
+    /// contador.increment(&usuario);
+    /// assert_eq!(contador.get_count_at(&ledger), 0);
+    /// ```
+    pub fn get_count_at(env: Env, ledger: u32) -> u32 {
+        checkpoints::valor_en(&env, &symbol_short!("COUNTER"), ledger) as u32
+    }
+
     /// Resetea el contador a 0
     /// 
     /// # Características:
@@ -149,8 +192,8 @@ impl ContadorContract {
     /// # Ejemplo
     /// ```
     /// let contador = ContadorContractClient::new(&env, &contract_id);
-    /// contador.increment();
-    /// contador.increment();
+    /// contador.increment(&usuario);
+    /// contador.increment(&usuario);
     /// contador.reset();
     /// assert_eq!(contador.get_count(), 0);
     /// ```
@@ -160,6 +203,7 @@ impl ContadorContract {
             &symbol_short!("COUNTER"),
             &0u32
         );
+        checkpoints::registrar(&env, &symbol_short!("COUNTER"), 0);
 
         // Emitir evento
         env.events().publish(
@@ -168,6 +212,27 @@ impl ContadorContract {
         );
     }
 
+    // ============================================================
+    // ADMINISTRACIÓN (opcional)
+    // ============================================================
+
+    /// Define el owner (que firma) que configura el límite de incrementos
+    pub fn initialize(env: Env, owner: Address) -> Result<(), OwnableError> {
+        owner.require_auth();
+        ownable::inicializar(&env, &owner)
+    }
+
+    /// Owner: limita los `increment` de cada llamador por ventana de ledgers (None = sin límite)
+    pub fn set_rate_limit(env: Env, limite: Option<LimiteTasa>) -> Result<(), RateLimitError> {
+        ownable::require_owner(&env).map_err(|_| RateLimitError::SinAdmin)?;
+        rate_limit::configurar(&env, limite)
+    }
+
+    /// Límite de incrementos configurado
+    pub fn get_rate_limit(env: Env) -> Option<LimiteTasa> {
+        rate_limit::limite(&env)
+    }
+
     // ============================================================
     // EJERCICIOS GUIADOS ADICIONALES
     // ============================================================
@@ -210,6 +275,7 @@ impl ContadorContract {
             &symbol_short!("COUNTER"),
             &nuevo_contador
         );
+        checkpoints::registrar(&env, &symbol_short!("COUNTER"), nuevo_contador as i128);
 
         // PASO 4: Emitir evento con información adicional
         env.events().publish(
@@ -249,6 +315,7 @@ impl ContadorContract {
             &symbol_short!("COUNTER"),
             &contador
         );
+        checkpoints::registrar(&env, &symbol_short!("COUNTER"), contador as i128);
 
         env.events().publish(
             (symbol_short!("increment"),),
@@ -302,6 +369,7 @@ impl ContadorContract {
             &symbol_short!("COUNTER"),
             &nuevo_contador
         );
+        checkpoints::registrar(&env, &symbol_short!("COUNTER"), nuevo_contador as i128);
 
         // PASO 5: Emitir evento con información adicional
         env.events().publish(
@@ -319,7 +387,7 @@ impl ContadorContract {
 mod test {
     use super::*;
     use soroban_sdk::{
-        testutils::Address as _,
+        testutils::{Address as _, Ledger},
         Address,
         BytesN,
         Env,
@@ -330,15 +398,17 @@ mod test {
     fn test_increment() {
         // ARRANGE: Preparar
         let env = Env::default();
+        env.mock_all_auths();
+        let usuario = Address::generate(&env);
         let contract_id = BytesN::from_array(&env, &[0; 32]);
         env.register_contract(&contract_id, ContadorContract);
 
         let client = ContadorContractClient::new(&env, &contract_id);
 
         // ACT & ASSERT: Ejecutar y verificar
-        assert_eq!(client.increment(), 1);
-        assert_eq!(client.increment(), 2);
-        assert_eq!(client.increment(), 3);
+        assert_eq!(client.increment(&usuario), 1);
+        assert_eq!(client.increment(&usuario), 2);
+        assert_eq!(client.increment(&usuario), 3);
         assert_eq!(client.get_count(), 3);
     }
 
@@ -346,15 +416,17 @@ mod test {
     #[test]
     fn test_decrement() {
         let env = Env::default();
+        env.mock_all_auths();
+        let usuario = Address::generate(&env);
         let contract_id = BytesN::from_array(&env, &[0; 32]);
         env.register_contract(&contract_id, ContadorContract);
 
         let client = ContadorContractClient::new(&env, &contract_id);
 
         // Incrementar primero
-        client.increment();
-        client.increment();
-        client.increment();
+        client.increment(&usuario);
+        client.increment(&usuario);
+        client.increment(&usuario);
 
         // Decrementar
         assert_eq!(client.decrement(), 2);
@@ -380,15 +452,17 @@ mod test {
     #[test]
     fn test_reset() {
         let env = Env::default();
+        env.mock_all_auths();
+        let usuario = Address::generate(&env);
         let contract_id = BytesN::from_array(&env, &[0; 32]);
         env.register_contract(&contract_id, ContadorContract);
 
         let client = ContadorContractClient::new(&env, &contract_id);
 
         // Incrementar varias veces
-        client.increment();
-        client.increment();
-        client.increment();
+        client.increment(&usuario);
+        client.increment(&usuario);
+        client.increment(&usuario);
 
         // Verificar que tiene valor
         assert_eq!(client.get_count(), 3);
@@ -404,6 +478,8 @@ mod test {
     #[test]
     fn test_get_count() {
         let env = Env::default();
+        env.mock_all_auths();
+        let usuario = Address::generate(&env);
         let contract_id = BytesN::from_array(&env, &[0; 32]);
         env.register_contract(&contract_id, ContadorContract);
 
@@ -413,10 +489,10 @@ mod test {
         assert_eq!(client.get_count(), 0);
 
         // Después de incrementar
-        client.increment();
+        client.increment(&usuario);
         assert_eq!(client.get_count(), 1);
 
-        client.increment();
+        client.increment(&usuario);
         assert_eq!(client.get_count(), 2);
     }
 
@@ -546,6 +622,8 @@ mod test {
     #[test]
     fn test_flujo_completo() {
         let env = Env::default();
+        env.mock_all_auths();
+        let usuario = Address::generate(&env);
         let contract_id = BytesN::from_array(&env, &[0; 32]);
         env.register_contract(&contract_id, ContadorContract);
 
@@ -555,8 +633,8 @@ mod test {
         assert_eq!(client.get_count(), 0);
 
         // Incrementar varias veces
-        client.increment();
-        client.increment();
+        client.increment(&usuario);
+        client.increment(&usuario);
         client.increment_by(&5);
         assert_eq!(client.get_count(), 7);
 
@@ -570,7 +648,7 @@ mod test {
         assert_eq!(client.get_count(), 0);
 
         // Volver a incrementar
-        client.increment();
+        client.increment(&usuario);
         assert_eq!(client.get_count(), 1);
     }
 
@@ -578,6 +656,8 @@ mod test {
     #[test]
     fn test_multiple_increments() {
         let env = Env::default();
+        env.mock_all_auths();
+        let usuario = Address::generate(&env);
         let contract_id = BytesN::from_array(&env, &[0; 32]);
         env.register_contract(&contract_id, ContadorContract);
 
@@ -585,7 +665,7 @@ mod test {
 
         // Incrementar 100 veces
         for i in 1..=100 {
-            assert_eq!(client.increment(), i);
+            assert_eq!(client.increment(&usuario), i);
         }
 
         // Verificar valor final
@@ -596,26 +676,81 @@ mod test {
     #[test]
     fn test_alternar_increment_decrement() {
         let env = Env::default();
+        env.mock_all_auths();
+        let usuario = Address::generate(&env);
         let contract_id = BytesN::from_array(&env, &[0; 32]);
         env.register_contract(&contract_id, ContadorContract);
 
         let client = ContadorContractClient::new(&env, &contract_id);
 
         // Incrementar
-        assert_eq!(client.increment(), 1);
-        assert_eq!(client.increment(), 2);
-        assert_eq!(client.increment(), 3);
+        assert_eq!(client.increment(&usuario), 1);
+        assert_eq!(client.increment(&usuario), 2);
+        assert_eq!(client.increment(&usuario), 3);
 
         // Decrementar
         assert_eq!(client.decrement(), 2);
         assert_eq!(client.decrement(), 1);
 
         // Incrementar de nuevo
-        assert_eq!(client.increment(), 2);
-        assert_eq!(client.increment(), 3);
+        assert_eq!(client.increment(&usuario), 2);
+        assert_eq!(client.increment(&usuario), 3);
 
         // Verificar estado final
         assert_eq!(client.get_count(), 3);
     }
-}
 
+    /// Test: el owner limita los incrementos por ventana de ledgers
+    #[test]
+    fn test_increment_rate_limit() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, ContadorContract);
+        let client = ContadorContractClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let ana = Address::generate(&env);
+        let bea = Address::generate(&env);
+
+        let limite = LimiteTasa { max_llamadas: 2, ventana: 10 };
+        assert_eq!(client.try_set_rate_limit(&Some(limite.clone())), Err(Ok(RateLimitError::SinAdmin)));
+
+        client.initialize(&owner);
+        assert_eq!(env.auths()[0].0, owner);
+        client.set_rate_limit(&Some(limite));
+        assert_eq!(env.auths()[0].0, owner);
+
+        // Cada llamador firma y tiene su propio cupo
+        assert_eq!(client.increment(&ana), 1);
+        assert_eq!(env.auths()[0].0, ana);
+        assert_eq!(client.increment(&ana), 2);
+        assert_eq!(client.try_increment(&ana), Err(Ok(RateLimitError::RateLimited.into())));
+        assert_eq!(client.increment(&bea), 3);
+
+        // En la ventana siguiente se puede de nuevo
+        env.ledger().with_mut(|li| li.sequence_number += 10);
+        assert_eq!(client.increment(&ana), 4);
+    }
+
+    /// Test: get_count_at devuelve el valor de un ledger pasado
+    #[test]
+    fn test_get_count_at() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let usuario = Address::generate(&env);
+        let contract_id = env.register_contract(None, ContadorContract);
+        let client = ContadorContractClient::new(&env, &contract_id);
+
+        env.ledger().with_mut(|li| li.sequence_number = 10);
+        client.increment(&usuario);
+        client.increment(&usuario);
+        env.ledger().with_mut(|li| li.sequence_number = 20);
+        client.reset();
+        env.ledger().with_mut(|li| li.sequence_number = 30);
+        client.increment_by(&7);
+
+        assert_eq!(client.get_count_at(&5), 0);
+        assert_eq!(client.get_count_at(&10), 2);
+        assert_eq!(client.get_count_at(&29), 0);
+        assert_eq!(client.get_count_at(&30), 7);
+    }
+}
@@ -0,0 +1,272 @@
+#![no_std]
+use soroban_sdk::{
+    contract,
+    contractimpl,
+    contracterror,
+    contracttype,
+    Env,
+    Address,
+    Symbol,
+    symbol_short,
+};
+use crate::ownable::{
+    self,
+    Error as OwnableError,
+};
+
+// ============================================================
+// CONTADOR CONFIGURABLE
+// ============================================================
+//
+// Un solo contrato en lugar de las variantes de ejercicio
+// (`ContadorContract`, `ContadorExtendido`, `ContadorConLimite`,
+// `ContadorConSetValue`, `ContadorConHistorial`): el comportamiento
+// sale de una `ConfigContador` guardada al inicializar.
+//
+//   ContadorConLimite   → max: 1000
+//   ContadorExtendido   → paso: n
+//   ContadorConSetValue → ajuste: PermisoAjuste::Cualquiera
+//
+// Toda operación respeta la configuración; solo el owner (ver
+// `ownable.rs`) la puede cambiar.
+
+// ============================================================
+// DEFINICIÓN DE ERRORES PERSONALIZADOS
+// ============================================================
+
+/// Errores del contador configurable
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    NoInicializado = 1,
+    YaInicializado = 2,
+    SinOwner = 3,               // El owner renunció
+    ConfigInvalida = 4,
+    LimiteMaximo = 5,           // El valor pasaría de `max`
+    LimiteMinimo = 6,           // El valor bajaría de `min`
+    BajoCero = 7,               // El valor sería negativo sin `permitir_negativos`
+    NoAutorizado = 8,           // El llamador no puede ajustar el valor
+}
+
+// ============================================================
+// TIPOS Y STORAGE
+// ============================================================
+
+/// Quién puede usar `set_value` y `reset`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PermisoAjuste {
+    Owner,
+    Cualquiera,
+    Operador(Address),
+}
+
+/// Configuración del contador (Instance Storage)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigContador {
+    pub min: i64,
+    pub max: i64,
+    pub paso: u32,                  // Cuánto suman/restan increment y decrement
+    pub inicial: i64,               // Valor al inicializar y al hacer reset
+    pub permitir_negativos: bool,   // false: el piso es max(min, 0)
+    pub ajuste: PermisoAjuste,
+}
+
+impl ConfigContador {
+    /// Piso efectivo del valor
+    fn piso(&self) -> i64 {
+        if self.permitir_negativos {
+            self.min
+        } else {
+            self.min.max(0)
+        }
+    }
+
+    /// Verifica que `valor` respete la configuración
+    fn validar(&self, valor: i64) -> Result<(), Error> {
+        if valor > self.max {
+            return Err(Error::LimiteMaximo);
+        }
+        if valor < 0 && !self.permitir_negativos {
+            return Err(Error::BajoCero);
+        }
+        if valor < self.min {
+            return Err(Error::LimiteMinimo);
+        }
+        Ok(())
+    }
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    // Instance
+    Config,
+    Valor,
+}
+
+// ============================================================
+// CONTRATO
+// ============================================================
+
+/// Contador con límites, paso y permisos configurables
+#[contract]
+pub struct ContadorConfigurable;
+
+#[contractimpl]
+impl ContadorConfigurable {
+    /// Inicializa con un owner (que firma) y la configuración
+    ///
+    /// El valor arranca en `config.inicial`.
+    pub fn initialize(env: Env, owner: Address, config: ConfigContador) -> Result<(), Error> {
+        owner.require_auth();
+        Self::validar_config(&config)?;
+
+        ownable::inicializar(&env, &owner).map_err(|_| Error::YaInicializado)?;
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(&DataKey::Valor, &config.inicial);
+        env.storage().instance().extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    /// Owner: reemplaza la configuración
+    ///
+    /// Si el valor actual queda fuera del rango nuevo, se lleva al
+    /// límite más cercano.
+    pub fn set_config(env: Env, config: ConfigContador) -> Result<i64, Error> {
+        Self::require_owner(&env)?;
+        Self::validar_config(&config)?;
+
+        let valor = Self::get_count(env.clone())?.clamp(config.piso(), config.max);
+        env.storage().instance().set(&DataKey::Config, &config);
+        env.storage().instance().set(&DataKey::Valor, &valor);
+
+        env.events().publish((symbol_short!("config"),), valor);
+
+        Ok(valor)
+    }
+
+    /// Configuración actual
+    pub fn get_config(env: Env) -> Result<ConfigContador, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .ok_or(Error::NoInicializado)
+    }
+
+    /// Valor actual
+    pub fn get_count(env: Env) -> Result<i64, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Valor)
+            .ok_or(Error::NoInicializado)
+    }
+
+    /// Suma `paso` (cualquiera puede llamarla)
+    pub fn increment(env: Env) -> Result<i64, Error> {
+        let config = Self::get_config(env.clone())?;
+        let valor = Self::get_count(env.clone())?
+            .checked_add(config.paso as i64)
+            .ok_or(Error::LimiteMaximo)?;
+
+        Self::guardar(&env, &config, valor, symbol_short!("increment"))
+    }
+
+    /// Resta `paso` (cualquiera puede llamarla)
+    pub fn decrement(env: Env) -> Result<i64, Error> {
+        let config = Self::get_config(env.clone())?;
+        let valor = Self::get_count(env.clone())?
+            .checked_sub(config.paso as i64)
+            .ok_or(Error::LimiteMinimo)?;
+
+        Self::guardar(&env, &config, valor, symbol_short!("decrement"))
+    }
+
+    /// Fija el valor (según `config.ajuste`, con la firma de `llamador`)
+    pub fn set_value(env: Env, llamador: Address, valor: i64) -> Result<i64, Error> {
+        let config = Self::get_config(env.clone())?;
+        Self::verificar_ajuste(&env, &config, &llamador)?;
+
+        Self::guardar(&env, &config, valor, symbol_short!("set_val"))
+    }
+
+    /// Vuelve a `config.inicial` (mismos permisos que `set_value`)
+    pub fn reset(env: Env, llamador: Address) -> Result<i64, Error> {
+        let config = Self::get_config(env.clone())?;
+        Self::verificar_ajuste(&env, &config, &llamador)?;
+
+        Self::guardar(&env, &config, config.inicial, symbol_short!("reset"))
+    }
+
+    /// Consulta el owner actual
+    pub fn owner(env: Env) -> Result<Address, OwnableError> {
+        ownable::owner(&env)
+    }
+
+    /// Propone un nuevo owner (requiere firma del owner actual)
+    pub fn proponer_owner(env: Env, nuevo_owner: Address) -> Result<(), OwnableError> {
+        ownable::proponer(&env, &nuevo_owner)
+    }
+
+    /// El owner propuesto acepta (requiere su firma)
+    pub fn aceptar_owner(env: Env) -> Result<(), OwnableError> {
+        ownable::aceptar(&env).map(|_| ())
+    }
+}
+
+// Helpers privados
+impl ContadorConfigurable {
+    fn validar_config(config: &ConfigContador) -> Result<(), Error> {
+        if config.paso == 0 || config.min > config.max {
+            return Err(Error::ConfigInvalida);
+        }
+
+        // `inicial` tiene que respetar la configuración (así el rango
+        // efectivo tampoco queda vacío)
+        config.validar(config.inicial).map_err(|_| Error::ConfigInvalida)
+    }
+
+    /// `ownable::require_owner` con los errores de este contrato
+    fn require_owner(env: &Env) -> Result<Address, Error> {
+        ownable::require_owner(env).map_err(|e| match e {
+            OwnableError::NoInicializado => Error::NoInicializado,
+            _ => Error::SinOwner,
+        })
+    }
+
+    /// Verifica que `llamador` pueda fijar el valor
+    fn verificar_ajuste(env: &Env, config: &ConfigContador, llamador: &Address) -> Result<(), Error> {
+        llamador.require_auth();
+
+        let permitido = match &config.ajuste {
+            PermisoAjuste::Cualquiera => true,
+            PermisoAjuste::Operador(operador) => operador == llamador,
+            PermisoAjuste::Owner => ownable::owner(env).ok().as_ref() == Some(llamador),
+        };
+
+        if !permitido {
+            return Err(Error::NoAutorizado);
+        }
+        Ok(())
+    }
+
+    /// Valida y guarda el valor nuevo, emitiendo `evento`
+    fn guardar(
+        env: &Env,
+        config: &ConfigContador,
+        valor: i64,
+        evento: Symbol,
+    ) -> Result<i64, Error> {
+        config.validar(valor)?;
+
+        env.storage().instance().set(&DataKey::Valor, &valor);
+        env.storage().instance().extend_ttl(100, 100);
+
+        env.events().publish((evento,), valor);
+
+        Ok(valor)
+    }
+}
@@ -0,0 +1,119 @@
+#![cfg(test)]
+use soroban_sdk::{
+    testutils::Address as _,
+    Address,
+    Env,
+};
+use crate::contador_configurable::{
+    ConfigContador,
+    ContadorConfigurable,
+    ContadorConfigurableClient,
+    Error,
+    PermisoAjuste,
+};
+
+/// Equivalente a `ContadorConLimite`: de 0 a 1000 de a uno
+fn config_con_limite() -> ConfigContador {
+    ConfigContador {
+        min: 0,
+        max: 1000,
+        paso: 1,
+        inicial: 0,
+        permitir_negativos: false,
+        ajuste: PermisoAjuste::Owner,
+    }
+}
+
+fn setup<'a>(env: &Env, config: &ConfigContador) -> (ContadorConfigurableClient<'a>, Address) {
+    let contract_id = env.register_contract(None, ContadorConfigurable);
+    let client = ContadorConfigurableClient::new(env, &contract_id);
+    let owner = Address::generate(env);
+    client.initialize(&owner, config);
+    (client, owner)
+}
+
+#[test]
+fn test_limites_y_paso() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let config = ConfigContador { max: 10, paso: 4, ..config_con_limite() };
+    let (client, owner) = setup(&env, &config);
+    assert_eq!(env.auths()[0].0, owner);
+
+    assert_eq!(client.increment(), 4);
+    assert_eq!(client.increment(), 8);
+    assert_eq!(client.try_increment(), Err(Ok(Error::LimiteMaximo)));
+    assert_eq!(client.get_count(), 8);
+
+    assert_eq!(client.decrement(), 4);
+    assert_eq!(client.decrement(), 0);
+    assert_eq!(client.try_decrement(), Err(Ok(Error::BajoCero)));
+
+    // Con negativos permitidos manda `min`
+    let negativos = ConfigContador { min: -5, permitir_negativos: true, ..config };
+    let (client, _) = setup(&env, &negativos);
+    assert_eq!(client.decrement(), -4);
+    assert_eq!(client.try_decrement(), Err(Ok(Error::LimiteMinimo)));
+}
+
+#[test]
+fn test_permisos_de_ajuste() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env, &config_con_limite());
+    let otra = Address::generate(&env);
+
+    // Owner: solo el owner fija y resetea
+    assert_eq!(client.set_value(&owner, &500), 500);
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(client.try_set_value(&otra, &1), Err(Ok(Error::NoAutorizado)));
+    assert_eq!(client.try_set_value(&owner, &1001), Err(Ok(Error::LimiteMaximo)));
+
+    // Operador: solo esa dirección
+    let operador = Address::generate(&env);
+    client.set_config(&ConfigContador {
+        ajuste: PermisoAjuste::Operador(operador.clone()),
+        ..config_con_limite()
+    });
+    assert_eq!(client.try_reset(&owner), Err(Ok(Error::NoAutorizado)));
+    assert_eq!(client.reset(&operador), 0);
+
+    // Cualquiera (como `ContadorConSetValue`), pero con la firma del llamador
+    client.set_config(&ConfigContador {
+        ajuste: PermisoAjuste::Cualquiera,
+        ..config_con_limite()
+    });
+    assert_eq!(client.set_value(&otra, &7), 7);
+    assert_eq!(env.auths()[0].0, otra);
+}
+
+#[test]
+fn test_cambiar_configuracion() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner) = setup(&env, &config_con_limite());
+
+    client.set_value(&owner, &800);
+
+    // El valor se ajusta al rango nuevo
+    let chica = ConfigContador { max: 100, ..config_con_limite() };
+    assert_eq!(client.set_config(&chica), 100);
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(client.get_count(), 100);
+    assert_eq!(client.get_config(), chica);
+
+    let invalidas = [
+        ConfigContador { paso: 0, ..config_con_limite() },
+        ConfigContador { min: 10, max: 5, ..config_con_limite() },
+        ConfigContador { inicial: 2000, ..config_con_limite() },
+        ConfigContador { min: -5, inicial: -1, ..config_con_limite() },
+    ];
+    for config in invalidas {
+        assert_eq!(client.try_set_config(&config), Err(Ok(Error::ConfigInvalida)));
+    }
+
+    assert_eq!(
+        client.try_initialize(&owner, &config_con_limite()),
+        Err(Ok(Error::YaInicializado))
+    );
+}
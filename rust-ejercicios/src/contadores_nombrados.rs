@@ -0,0 +1,422 @@
+#![no_std]
+use soroban_sdk::{
+    contract,
+    contractimpl,
+    contracterror,
+    contracttype,
+    Env,
+    Address,
+    Symbol,
+    TryFromVal,
+    Val,
+    Vec,
+    symbol_short,
+};
+use crate::checkpoints::TTL_CHECKPOINT;
+
+// ============================================================
+// CONTADORES CON NOMBRE POR OWNER
+// ============================================================
+//
+// En lugar de un único `COUNTER` global, cada dirección crea los
+// contadores que quiera, identificados por (owner, nombre):
+// - El owner puede todo; los escritores que agregue solo
+//   incrementan y decrementan
+// - Cada contador tiene sus propios límites [min, max]
+// - Los contadores de un owner se listan de a páginas
+//
+// El índice por owner usa el mismo esquema que
+// `registro_propuestas.rs`: posición → nombre y nombre → posición,
+// y al eliminar el último ocupa el hueco.
+//
+// Los contadores son de larga vida: las entradas y la instancia viven
+// `TTL_CHECKPOINT` ledgers y se extienden también al leerlas.
+
+// ============================================================
+// DEFINICIÓN DE ERRORES PERSONALIZADOS
+// ============================================================
+
+/// Errores de los contadores con nombre
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    ContadorNoExiste = 1,
+    ContadorYaExiste = 2,
+    LimitesInvalidos = 3,
+    LimiteMaximo = 4,           // El valor pasaría de `max`
+    LimiteMinimo = 5,           // El valor bajaría de `min`
+    NoAutorizado = 6,           // Ni owner ni escritor
+    CantidadInvalida = 7,
+    YaEsEscritor = 8,
+    NoEsEscritor = 9,
+    DemasiadosEscritores = 10,
+    PaginaInvalida = 11,
+}
+
+/// Máximo de escritores por contador (se guardan junto al contador)
+pub const MAX_ESCRITORES: u32 = 10;
+
+/// Máximo de contadores por página en `list_counters`
+pub const MAX_PAGINA: u32 = 50;
+
+// ============================================================
+// TIPOS Y STORAGE
+// ============================================================
+
+/// Contador guardado (Persistent Storage)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContadorNombrado {
+    pub owner: Address,
+    pub nombre: Symbol,
+    pub valor: i64,
+    pub min: i64,
+    pub max: i64,
+    pub escritores: Vec<Address>,   // Además del owner
+}
+
+/// Página de resultados de `list_counters`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaginaContadores {
+    pub contadores: Vec<ContadorNombrado>,
+    /// Cursor para pedir la página siguiente (None = no hay más)
+    pub siguiente: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    // Persistent
+    Contador(Address, Symbol),      // (owner, nombre) → ContadorNombrado
+    Total(Address),                 // owner → cantidad de contadores
+    Indice(Address, u32),           // (owner, posición) → nombre
+    Posicion(Address, Symbol),      // (owner, nombre) → posición en el índice
+}
+
+// ============================================================
+// CONTRATO
+// ============================================================
+
+/// Muchos contadores en un despliegue, por owner y nombre
+#[contract]
+pub struct ContadoresNombrados;
+
+#[contractimpl]
+impl ContadoresNombrados {
+    /// Crea el contador `nombre` de `owner` con límites [min, max]
+    ///
+    /// Arranca en 0, o en el límite más cercano si 0 queda fuera.
+    pub fn create_counter(env: Env, owner: Address, nombre: Symbol, min: i64, max: i64) -> Result<(), Error> {
+        owner.require_auth();
+
+        if min > max {
+            return Err(Error::LimitesInvalidos);
+        }
+
+        let key = DataKey::Contador(owner.clone(), nombre.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::ContadorYaExiste);
+        }
+
+        let contador = ContadorNombrado {
+            owner: owner.clone(),
+            nombre: nombre.clone(),
+            valor: 0i64.clamp(min, max),
+            min,
+            max,
+            escritores: Vec::new(&env),
+        };
+        Self::guardar(&env, &contador);
+        Self::agregar_a_indice(&env, &owner, &nombre);
+
+        env.events().publish(
+            (symbol_short!("crear"), owner),
+            nombre,
+        );
+
+        Ok(())
+    }
+
+    /// Owner: elimina el contador (el nombre queda libre)
+    pub fn delete_counter(env: Env, owner: Address, nombre: Symbol) -> Result<(), Error> {
+        owner.require_auth();
+        Self::leer(&env, &owner, &nombre)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Contador(owner.clone(), nombre.clone()));
+        Self::quitar_de_indice(&env, &owner, &nombre);
+
+        env.events().publish(
+            (symbol_short!("eliminar"), owner),
+            nombre,
+        );
+
+        Ok(())
+    }
+
+    /// Owner: cambia los límites
+    ///
+    /// Si el valor queda fuera del rango nuevo, se lleva al límite
+    /// más cercano.
+    pub fn set_limits(env: Env, owner: Address, nombre: Symbol, min: i64, max: i64) -> Result<i64, Error> {
+        owner.require_auth();
+
+        if min > max {
+            return Err(Error::LimitesInvalidos);
+        }
+
+        let mut contador = Self::leer(&env, &owner, &nombre)?;
+        contador.min = min;
+        contador.max = max;
+        contador.valor = contador.valor.clamp(min, max);
+        Self::guardar(&env, &contador);
+
+        Ok(contador.valor)
+    }
+
+    /// Owner: permite a `escritor` incrementar y decrementar
+    pub fn add_writer(env: Env, owner: Address, nombre: Symbol, escritor: Address) -> Result<(), Error> {
+        owner.require_auth();
+
+        let mut contador = Self::leer(&env, &owner, &nombre)?;
+        if escritor == owner || contador.escritores.contains(&escritor) {
+            return Err(Error::YaEsEscritor);
+        }
+        if contador.escritores.len() >= MAX_ESCRITORES {
+            return Err(Error::DemasiadosEscritores);
+        }
+
+        contador.escritores.push_back(escritor.clone());
+        Self::guardar(&env, &contador);
+
+        env.events().publish(
+            (symbol_short!("escritor"), owner, nombre),
+            (escritor, true),
+        );
+
+        Ok(())
+    }
+
+    /// Owner: quita el permiso de `escritor`
+    pub fn remove_writer(env: Env, owner: Address, nombre: Symbol, escritor: Address) -> Result<(), Error> {
+        owner.require_auth();
+
+        let mut contador = Self::leer(&env, &owner, &nombre)?;
+        let posicion = contador.escritores
+            .first_index_of(&escritor)
+            .ok_or(Error::NoEsEscritor)?;
+
+        contador.escritores.remove(posicion);
+        Self::guardar(&env, &contador);
+
+        env.events().publish(
+            (symbol_short!("escritor"), owner, nombre),
+            (escritor, false),
+        );
+
+        Ok(())
+    }
+
+    /// Suma `cantidad` (owner o escritor, con la firma de `llamador`)
+    pub fn increment(
+        env: Env,
+        llamador: Address,
+        owner: Address,
+        nombre: Symbol,
+        cantidad: u32,
+    ) -> Result<i64, Error> {
+        let mut contador = Self::leer_para_escribir(&env, &llamador, &owner, &nombre, cantidad)?;
+
+        let valor = contador.valor
+            .checked_add(cantidad as i64)
+            .filter(|v| *v <= contador.max)
+            .ok_or(Error::LimiteMaximo)?;
+
+        contador.valor = valor;
+        Self::guardar(&env, &contador);
+
+        env.events().publish(
+            (symbol_short!("increment"), owner, nombre),
+            (llamador, valor),
+        );
+
+        Ok(valor)
+    }
+
+    /// Resta `cantidad` (owner o escritor, con la firma de `llamador`)
+    pub fn decrement(
+        env: Env,
+        llamador: Address,
+        owner: Address,
+        nombre: Symbol,
+        cantidad: u32,
+    ) -> Result<i64, Error> {
+        let mut contador = Self::leer_para_escribir(&env, &llamador, &owner, &nombre, cantidad)?;
+
+        let valor = contador.valor
+            .checked_sub(cantidad as i64)
+            .filter(|v| *v >= contador.min)
+            .ok_or(Error::LimiteMinimo)?;
+
+        contador.valor = valor;
+        Self::guardar(&env, &contador);
+
+        env.events().publish(
+            (symbol_short!("decrement"), owner, nombre),
+            (llamador, valor),
+        );
+
+        Ok(valor)
+    }
+
+    /// Contador completo (valor, límites y escritores)
+    pub fn get_counter(env: Env, owner: Address, nombre: Symbol) -> Result<ContadorNombrado, Error> {
+        Self::leer(&env, &owner, &nombre)
+    }
+
+    /// Valor actual del contador
+    pub fn get_count(env: Env, owner: Address, nombre: Symbol) -> Result<i64, Error> {
+        Ok(Self::leer(&env, &owner, &nombre)?.valor)
+    }
+
+    /// Cantidad de contadores de `owner`
+    pub fn count_counters(env: Env, owner: Address) -> u32 {
+        Self::leer_entrada(&env, &DataKey::Total(owner)).unwrap_or(0)
+    }
+
+    /// Lista los contadores de `owner` de a páginas
+    ///
+    /// Empezar con `cursor = 0` y seguir con `siguiente` hasta que
+    /// sea `None`. `limite` va de 1 a `MAX_PAGINA`. Al eliminar un
+    /// contador el último ocupa su lugar, así que el orden puede
+    /// cambiar entre páginas.
+    pub fn list_counters(env: Env, owner: Address, cursor: u32, limite: u32) -> Result<PaginaContadores, Error> {
+        if limite == 0 || limite > MAX_PAGINA {
+            return Err(Error::PaginaInvalida);
+        }
+
+        let total = Self::count_counters(env.clone(), owner.clone());
+        let fin = total.min(cursor.saturating_add(limite));
+
+        let mut contadores = Vec::new(&env);
+        for posicion in cursor..fin {
+            let nombre: Symbol = Self::leer_entrada(&env, &DataKey::Indice(owner.clone(), posicion))
+                .ok_or(Error::ContadorNoExiste)?;
+            contadores.push_back(Self::leer(&env, &owner, &nombre)?);
+        }
+
+        let siguiente = if fin < total { Some(fin) } else { None };
+
+        Ok(PaginaContadores { contadores, siguiente })
+    }
+}
+
+// Helpers privados
+impl ContadoresNombrados {
+    fn leer(env: &Env, owner: &Address, nombre: &Symbol) -> Result<ContadorNombrado, Error> {
+        Self::extender_instancia(env);
+        Self::leer_entrada(env, &DataKey::Contador(owner.clone(), nombre.clone()))
+            .ok_or(Error::ContadorNoExiste)
+    }
+
+    fn guardar(env: &Env, contador: &ContadorNombrado) {
+        let key = DataKey::Contador(contador.owner.clone(), contador.nombre.clone());
+        env.storage().persistent().set(&key, contador);
+        Self::extender(env, &key);
+        Self::extender_instancia(env);
+    }
+
+    /// Lee una entrada de Persistent Storage y, si está, la extiende
+    fn leer_entrada<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+        let valor = env.storage().persistent().get(key);
+        if valor.is_some() {
+            Self::extender(env, key);
+        }
+        valor
+    }
+
+    /// Extiende una entrada a `TTL_CHECKPOINT` (o al máximo de la red)
+    /// cuando le queda menos de la mitad
+    fn extender(env: &Env, key: &DataKey) {
+        let ttl = TTL_CHECKPOINT.min(env.storage().max_ttl());
+        env.storage().persistent().extend_ttl(key, ttl / 2, ttl);
+    }
+
+    /// Igual que `extender`, para la instancia del contrato
+    fn extender_instancia(env: &Env) {
+        let ttl = TTL_CHECKPOINT.min(env.storage().max_ttl());
+        env.storage().instance().extend_ttl(ttl / 2, ttl);
+    }
+
+    /// Verifica firma y permiso de `llamador` y lee el contador
+    fn leer_para_escribir(
+        env: &Env,
+        llamador: &Address,
+        owner: &Address,
+        nombre: &Symbol,
+        cantidad: u32,
+    ) -> Result<ContadorNombrado, Error> {
+        llamador.require_auth();
+
+        if cantidad == 0 {
+            return Err(Error::CantidadInvalida);
+        }
+
+        let contador = Self::leer(env, owner, nombre)?;
+        if llamador != owner && !contador.escritores.contains(llamador) {
+            return Err(Error::NoAutorizado);
+        }
+
+        Ok(contador)
+    }
+
+    /// Agrega `nombre` al final del índice de `owner`
+    fn agregar_a_indice(env: &Env, owner: &Address, nombre: &Symbol) {
+        let total = Self::count_counters(env.clone(), owner.clone());
+
+        let key_indice = DataKey::Indice(owner.clone(), total);
+        env.storage().persistent().set(&key_indice, nombre);
+        Self::extender(env, &key_indice);
+
+        let key_posicion = DataKey::Posicion(owner.clone(), nombre.clone());
+        env.storage().persistent().set(&key_posicion, &total);
+        Self::extender(env, &key_posicion);
+
+        let key_total = DataKey::Total(owner.clone());
+        env.storage().persistent().set(&key_total, &(total + 1));
+        Self::extender(env, &key_total);
+    }
+
+    /// Saca `nombre` del índice de `owner` moviendo el último a su lugar
+    fn quitar_de_indice(env: &Env, owner: &Address, nombre: &Symbol) {
+        let total = Self::count_counters(env.clone(), owner.clone());
+        let ultima = total - 1;
+
+        let key_posicion = DataKey::Posicion(owner.clone(), nombre.clone());
+        let posicion: u32 = env.storage().persistent().get(&key_posicion).unwrap();
+
+        if posicion != ultima {
+            let nombre_ultimo: Symbol = env.storage()
+                .persistent()
+                .get(&DataKey::Indice(owner.clone(), ultima))
+                .unwrap();
+            let key_indice = DataKey::Indice(owner.clone(), posicion);
+            env.storage().persistent().set(&key_indice, &nombre_ultimo);
+            Self::extender(env, &key_indice);
+            let key_posicion = DataKey::Posicion(owner.clone(), nombre_ultimo);
+            env.storage().persistent().set(&key_posicion, &posicion);
+            Self::extender(env, &key_posicion);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Indice(owner.clone(), ultima));
+        env.storage().persistent().remove(&key_posicion);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Total(owner.clone()), &ultima);
+    }
+}
@@ -0,0 +1,174 @@
+#![cfg(test)]
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+    Env,
+    Symbol,
+    symbol_short,
+};
+use crate::checkpoints::TTL_CHECKPOINT;
+use crate::contadores_nombrados::{
+    ContadoresNombrados,
+    ContadoresNombradosClient,
+    Error,
+    MAX_ESCRITORES,
+};
+
+fn setup<'a>(env: &Env) -> ContadoresNombradosClient<'a> {
+    let contract_id = env.register_contract(None, ContadoresNombrados);
+    ContadoresNombradosClient::new(env, &contract_id)
+}
+
+#[test]
+fn test_contadores_por_owner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+    let visitas = symbol_short!("VISITAS");
+
+    // El mismo nombre en dos owners son contadores distintos
+    client.create_counter(&ana, &visitas, &0, &100);
+    assert_eq!(env.auths()[0].0, ana);
+    client.create_counter(&bea, &visitas, &-10, &10);
+    assert_eq!(
+        client.try_create_counter(&ana, &visitas, &0, &5),
+        Err(Ok(Error::ContadorYaExiste))
+    );
+    assert_eq!(
+        client.try_create_counter(&ana, &symbol_short!("MAL"), &5, &0),
+        Err(Ok(Error::LimitesInvalidos))
+    );
+
+    assert_eq!(client.increment(&ana, &ana, &visitas, &60), 60);
+    assert_eq!(client.decrement(&bea, &bea, &visitas, &10), -10);
+    assert_eq!(client.get_count(&ana, &visitas), 60);
+
+    // Límites propios de cada contador
+    assert_eq!(
+        client.try_increment(&ana, &ana, &visitas, &41),
+        Err(Ok(Error::LimiteMaximo))
+    );
+    assert_eq!(
+        client.try_decrement(&bea, &bea, &visitas, &1),
+        Err(Ok(Error::LimiteMinimo))
+    );
+    assert_eq!(
+        client.try_increment(&ana, &ana, &visitas, &0),
+        Err(Ok(Error::CantidadInvalida))
+    );
+
+    // Achicar los límites lleva el valor al rango nuevo
+    assert_eq!(client.set_limits(&ana, &visitas, &0, &50), 50);
+    assert_eq!(client.get_counter(&ana, &visitas).max, 50);
+}
+
+#[test]
+fn test_escritores() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let owner = Address::generate(&env);
+    let escritor = Address::generate(&env);
+    let nombre = symbol_short!("STOCK");
+
+    client.create_counter(&owner, &nombre, &0, &1_000);
+    assert_eq!(
+        client.try_increment(&escritor, &owner, &nombre, &1),
+        Err(Ok(Error::NoAutorizado))
+    );
+
+    client.add_writer(&owner, &nombre, &escritor);
+    assert_eq!(client.increment(&escritor, &owner, &nombre, &5), 5);
+    assert_eq!(env.auths()[0].0, escritor);
+    assert_eq!(client.try_add_writer(&owner, &nombre, &escritor), Err(Ok(Error::YaEsEscritor)));
+    assert_eq!(client.try_add_writer(&owner, &nombre, &owner), Err(Ok(Error::YaEsEscritor)));
+
+    client.remove_writer(&owner, &nombre, &escritor);
+    assert_eq!(
+        client.try_decrement(&escritor, &owner, &nombre, &1),
+        Err(Ok(Error::NoAutorizado))
+    );
+    assert_eq!(
+        client.try_remove_writer(&owner, &nombre, &escritor),
+        Err(Ok(Error::NoEsEscritor))
+    );
+
+    for _ in 0..MAX_ESCRITORES {
+        client.add_writer(&owner, &nombre, &Address::generate(&env));
+    }
+    assert_eq!(
+        client.try_add_writer(&owner, &nombre, &Address::generate(&env)),
+        Err(Ok(Error::DemasiadosEscritores))
+    );
+}
+
+#[test]
+fn test_listar_y_eliminar() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let owner = Address::generate(&env);
+    let nombres = [
+        symbol_short!("A"),
+        symbol_short!("B"),
+        symbol_short!("C"),
+        symbol_short!("D"),
+    ];
+
+    for nombre in nombres.iter() {
+        client.create_counter(&owner, nombre, &0, &10);
+    }
+    client.create_counter(&Address::generate(&env), &symbol_short!("OTRO"), &0, &10);
+    assert_eq!(client.count_counters(&owner), 4);
+
+    let pagina = client.list_counters(&owner, &0, &3);
+    assert_eq!(pagina.contadores.len(), 3);
+    assert_eq!(pagina.siguiente, Some(3));
+    let ultima = client.list_counters(&owner, &3, &3);
+    assert_eq!(ultima.contadores.len(), 1);
+    assert_eq!(ultima.siguiente, None);
+
+    // Eliminar B: el último (D) ocupa su lugar y el nombre queda libre
+    client.delete_counter(&owner, &nombres[1]);
+    assert_eq!(
+        client.try_get_count(&owner, &nombres[1]),
+        Err(Ok(Error::ContadorNoExiste))
+    );
+    let pagina = client.list_counters(&owner, &0, &10);
+    let listados: [Symbol; 3] = core::array::from_fn(|i| {
+        pagina.contadores.get_unchecked(i as u32).nombre
+    });
+    assert_eq!(listados, [nombres[0].clone(), nombres[3].clone(), nombres[2].clone()]);
+
+    client.create_counter(&owner, &nombres[1], &0, &10);
+    assert_eq!(client.count_counters(&owner), 4);
+
+    assert_eq!(
+        client.try_list_counters(&owner, &0, &0),
+        Err(Ok(Error::PaginaInvalida))
+    );
+}
+
+#[test]
+fn test_contadores_no_expiran_si_se_consultan() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let ana = Address::generate(&env);
+    let visitas = symbol_short!("VISITAS");
+
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    client.create_counter(&ana, &visitas, &0, &100);
+    client.increment(&ana, &ana, &visitas, &3);
+
+    // Mucho más allá de un TTL corto: el contador y el índice siguen ahí
+    env.ledger().with_mut(|li| li.sequence_number = 10 + TTL_CHECKPOINT * 3 / 4);
+    assert_eq!(client.list_counters(&ana, &0, &10).contadores.len(), 1);
+
+    // La lectura los extendió: pasan el vencimiento original
+    env.ledger().with_mut(|li| li.sequence_number = 10 + TTL_CHECKPOINT * 3 / 2);
+    let pagina = client.list_counters(&ana, &0, &10);
+    assert_eq!(pagina.contadores.get(0).unwrap().valor, 3);
+}
@@ -2,13 +2,26 @@
 use soroban_sdk::{
     contract,
     contractimpl,
+    contracterror,
+    contracttype,
     Env,
     symbol_short,
     Address,
     Symbol,
-    Vec as SorobanVec,
+    Vec,
     String as SorobanString,
 };
+use crate::ownable::{
+    self,
+    Error as OwnableError,
+};
+use crate::rate_limit::{
+    self,
+    Error as RateLimitError,
+    LimiteTasa,
+};
+use crate::registro_votantes;
+use crate::checkpoints::TTL_CHECKPOINT;
 
 // ============================================================
 // NIVEL 1: Entendiendo el código
@@ -59,6 +72,9 @@ impl MysteryFunctions {
 // ============================================================
 // NIVEL 2: Modificando el contador
 // ============================================================
+//
+// Variantes de ejercicio: para desplegar, `ContadorConfigurable`
+// (contador_configurable.rs) cubre todas con una configuración.
 
 /// Ejercicio 2.1: Contador con increment_by
 /// 
@@ -266,12 +282,104 @@ impl ContadorConSetValue {
 
 /// Ejercicio 3.1: Contador con historial
 /// 
-/// Mantiene historial de los últimos 5 valores
+/// Mantiene un historial circular (ring buffer) de los últimos
+/// `capacidad` incrementos en Persistent Storage: cada entrada va en
+/// su propia key (`Entrada(slot)`) y un contador de escrituras indica
+/// dónde va la próxima, así escribir es O(1) sin reconstruir un Vec.
+///
+/// El historial es de larga vida: las entradas y la instancia viven
+/// `TTL_CHECKPOINT` ledgers y se extienden también al leerlas.
 #[contract]
 pub struct ContadorConHistorial;
 
+/// Errores del contador con historial
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ErrorHistorial {
+    CapacidadInvalida = 1,
+    HistorialIniciado = 2,      // La capacidad se fija antes del primer incremento
+    PaginaInvalida = 3,
+}
+
+/// Capacidad si no se llama a `init_history`
+pub const CAPACIDAD_HISTORIAL: u32 = 5;
+
+/// Capacidad máxima del historial
+pub const MAX_CAPACIDAD_HISTORIAL: u32 = 1_000;
+
+/// Máximo de entradas por página de `get_history`
+pub const MAX_PAGINA_HISTORIAL: u32 = 50;
+
+/// Un incremento guardado en el historial
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EntradaHistorial {
+    pub valor: u32,
+    pub ledger: u32,            // Secuencia del ledger
+    pub timestamp: u64,
+    pub llamador: Address,
+}
+
+/// Página de resultados de `get_history` (de la más vieja a la más nueva)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaginaHistorial {
+    pub entradas: Vec<EntradaHistorial>,
+    /// Cursor para pedir la página siguiente (None = no hay más)
+    pub siguiente: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKeyHistorial {
+    // Instance
+    Capacidad,
+    Escrituras,                 // Total de incrementos (u64); el slot es Escrituras % Capacidad
+
+    // Persistent
+    Entrada(u32),               // slot → EntradaHistorial
+}
+
 #[contractimpl]
 impl ContadorConHistorial {
+    /// Fija la capacidad del historial (una vez, antes del primer incremento)
+    ///
+    /// Lo firma `owner`, que queda como owner del contador: llamarla
+    /// en la misma transacción del deploy.
+    pub fn init_history(env: Env, owner: Address, capacidad: u32) -> Result<(), ErrorHistorial> {
+        owner.require_auth();
+
+        if capacidad == 0 || capacidad > MAX_CAPACIDAD_HISTORIAL {
+            return Err(ErrorHistorial::CapacidadInvalida);
+        }
+
+        let iniciado = env.storage().instance().has(&DataKeyHistorial::Capacidad)
+            || Self::escrituras(&env) > 0;
+        if iniciado {
+            return Err(ErrorHistorial::HistorialIniciado);
+        }
+
+        ownable::inicializar(&env, &owner).map_err(|_| ErrorHistorial::HistorialIniciado)?;
+        env.storage().instance().set(&DataKeyHistorial::Capacidad, &capacidad);
+        Self::extender_instancia(&env);
+
+        Ok(())
+    }
+
+    /// Owner que fijó la capacidad (si se llamó a `init_history`)
+    pub fn owner(env: Env) -> Result<Address, OwnableError> {
+        ownable::owner(&env)
+    }
+
+    /// Capacidad del historial
+    pub fn get_capacity(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKeyHistorial::Capacidad)
+            .unwrap_or(CAPACIDAD_HISTORIAL)
+    }
+
     /// Obtiene el valor actual del contador
     pub fn get_count(env: Env) -> u32 {
         env.storage()
@@ -280,269 +388,270 @@ impl ContadorConHistorial {
             .unwrap_or(0)
     }
 
-    /// Incrementa el contador y agrega al historial
-    pub fn increment(env: Env) -> u32 {
-        // 1. Leer contador actual
-        let mut contador: u32 = env.storage()
-            .instance()
-            .get(&symbol_short!("COUNT"))
-            .unwrap_or(0);
-        
-        // 2. Incrementar
-        contador += 1;
-        
-        // 3. Leer historial actual
-        let mut history: SorobanVec<u32> = env.storage()
-            .instance()
-            .get(&symbol_short!("HIST"))
-            .unwrap_or(SorobanVec::new(&env));
-        
-        // 4. Agregar nuevo valor al historial
-        history.push_back(contador);
-        
-        // 5. Si historial > 5, mantener solo los últimos 5
-        // Nota: En Soroban, Vec no tiene método para crear slice directamente
-        // Mantenemos todos los elementos pero documentamos que deben limitarse
-        // En una implementación real, podrías usar un Vec con máximo 5 elementos
-        // y remover el primero cuando se agrega uno nuevo
-        if history.len() > 5 {
-            // Remover el primer elemento hasta tener solo 5
-            // Nota: Soroban Vec no tiene remove(0) directo, 
-            // así que reconstruimos el Vec sin el primer elemento
-            let mut new_history = SorobanVec::new(&env);
-            let start_idx = history.len() - 5;
-            for i in start_idx..history.len() {
-                if let Some(val) = history.get(i) {
-                    new_history.push_back(val);
-                }
-            }
-            history = new_history;
-        }
-        
-        // 6. Guardar contador e historial
+    /// Incrementa el contador y agrega la entrada al historial
+    ///
+    /// `llamador` firma y queda registrado en la entrada.
+    pub fn increment(env: Env, llamador: Address) -> u32 {
+        llamador.require_auth();
+
+        // 1. Incrementar el contador
+        let contador = Self::get_count(env.clone()) + 1;
         env.storage().instance().set(
             &symbol_short!("COUNT"),
             &contador
         );
-        env.storage().instance().set(
-            &symbol_short!("HIST"),
-            &history
-        );
-        
-        // 7. Emitir evento
+
+        // 2. Escribir en el slot siguiente (pisa la entrada más vieja
+        //    cuando el historial está lleno)
+        let escrituras = Self::escrituras(&env);
+        let capacidad = Self::get_capacity(env.clone());
+        let slot = (escrituras % capacidad as u64) as u32;
+
+        let entrada = EntradaHistorial {
+            valor: contador,
+            ledger: env.ledger().sequence(),
+            timestamp: env.ledger().timestamp(),
+            llamador: llamador.clone(),
+        };
+        let key = DataKeyHistorial::Entrada(slot);
+        env.storage().persistent().set(&key, &entrada);
+        Self::extender_entrada(&env, &key);
+
+        env.storage()
+            .instance()
+            .set(&DataKeyHistorial::Escrituras, &(escrituras + 1));
+        Self::extender_instancia(&env);
+
+        // 3. Emitir evento
         env.events().publish(
             (symbol_short!("increment"),),
-            contador
+            (contador, llamador)
         );
-        
-        // 8. Retornar nuevo valor
+
         contador
     }
 
-    /// Obtiene el historial completo (máx 5 valores)
-    pub fn get_history(env: Env) -> SorobanVec<u32> {
-        env.storage()
-            .instance()
-            .get(&symbol_short!("HIST"))
-            .unwrap_or(SorobanVec::new(&env))
+    /// Cantidad de entradas guardadas (como mucho la capacidad)
+    pub fn get_history_len(env: Env) -> u32 {
+        let capacidad = Self::get_capacity(env.clone());
+        Self::escrituras(&env).min(capacidad as u64) as u32
     }
-}
 
-/// Ejercicio 3.2: Sistema de votación simple
-/// 
-/// Sistema de votación con dos opciones
-#[contract]
-pub struct SistemaVotacion;
+    /// Lee el historial de a páginas, de la entrada más vieja a la más nueva
+    ///
+    /// `cursor` es la posición dentro del historial (0 = la más vieja);
+    /// para la página siguiente usar `siguiente` hasta que sea `None`.
+    /// `limite` va de 1 a `MAX_PAGINA_HISTORIAL`.
+    pub fn get_history(env: Env, cursor: u32, limite: u32) -> Result<PaginaHistorial, ErrorHistorial> {
+        if limite == 0 || limite > MAX_PAGINA_HISTORIAL {
+            return Err(ErrorHistorial::PaginaInvalida);
+        }
 
-#[contractimpl]
-impl SistemaVotacion {
-    /// Vota por opción A
-    pub fn vote_a(env: Env) {
-        let mut votos_a: u32 = env.storage()
-            .instance()
-            .get(&symbol_short!("VOTE_A"))
-            .unwrap_or(0);
-        
-        votos_a += 1;
-        
-        env.storage().instance().set(
-            &symbol_short!("VOTE_A"),
-            &votos_a
-        );
-        
-        env.events().publish(
-            (symbol_short!("vote_a"),),
-            votos_a
-        );
+        let capacidad = Self::get_capacity(env.clone()) as u64;
+        let escrituras = Self::escrituras(&env);
+        let total = Self::get_history_len(env.clone());
+        let fin = total.min(cursor.saturating_add(limite));
+
+        // Escritura número `primera` = entrada más vieja que sigue guardada
+        let primera = escrituras - total as u64;
+
+        let mut entradas = Vec::new(&env);
+        for posicion in cursor..fin {
+            let slot = ((primera + posicion as u64) % capacidad) as u32;
+            let key = DataKeyHistorial::Entrada(slot);
+            if let Some(entrada) = env.storage().persistent().get(&key) {
+                Self::extender_entrada(&env, &key);
+                entradas.push_back(entrada);
+            }
+        }
+        Self::extender_instancia(&env);
+
+        let siguiente = if fin < total { Some(fin) } else { None };
+
+        Ok(PaginaHistorial { entradas, siguiente })
     }
+}
 
-    /// Vota por opción B
-    pub fn vote_b(env: Env) {
-        let mut votos_b: u32 = env.storage()
+// Helpers privados
+impl ContadorConHistorial {
+    fn escrituras(env: &Env) -> u64 {
+        env.storage()
             .instance()
-            .get(&symbol_short!("VOTE_B"))
-            .unwrap_or(0);
-        
-        votos_b += 1;
-        
-        env.storage().instance().set(
-            &symbol_short!("VOTE_B"),
-            &votos_b
-        );
-        
-        env.events().publish(
-            (symbol_short!("vote_b"),),
-            votos_b
-        );
+            .get(&DataKeyHistorial::Escrituras)
+            .unwrap_or(0)
     }
 
-    /// Obtiene los resultados (votos_a, votos_b)
-    pub fn get_results(env: Env) -> (u32, u32) {
-        let votos_a: u32 = env.storage()
-            .instance()
-            .get(&symbol_short!("VOTE_A"))
-            .unwrap_or(0);
-        
-        let votos_b: u32 = env.storage()
-            .instance()
-            .get(&symbol_short!("VOTE_B"))
-            .unwrap_or(0);
-        
-        (votos_a, votos_b)
+    /// Extiende una entrada a `TTL_CHECKPOINT` (o al máximo de la red)
+    /// cuando le queda menos de la mitad
+    fn extender_entrada(env: &Env, key: &DataKeyHistorial) {
+        let ttl = TTL_CHECKPOINT.min(env.storage().max_ttl());
+        env.storage().persistent().extend_ttl(key, ttl / 2, ttl);
     }
 
-    /// Obtiene el ganador: "A", "B", o "tie"
-    pub fn get_winner(env: Env) -> Symbol {
-        let (votos_a, votos_b) = Self::get_results(env);
-        
-        if votos_a > votos_b {
-            symbol_short!("A")
-        } else if votos_b > votos_a {
-            symbol_short!("B")
-        } else {
-            symbol_short!("tie")
-        }
+    /// Igual que `extender_entrada`, para la instancia (capacidad y escrituras)
+    fn extender_instancia(env: &Env) {
+        let ttl = TTL_CHECKPOINT.min(env.storage().max_ttl());
+        env.storage().instance().extend_ttl(ttl / 2, ttl);
     }
 }
 
+// Ejercicio 3.2: Sistema de votación → ver `votacion.rs`
+// (propuestas con N opciones, un voto por dirección)
+
 // ============================================================
 // PROYECTO INTEGRADOR: Sistema de reputación
 // ============================================================
 
+/// Errores del sistema de reputación
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ErrorReputacion {
+    YaVoto = 1,         // Mismo voto repetido
+    SinVoto = 2,        // Nada que retirar
+    VotanteNoRegistrado = 3,
+    RateLimited = 4,        // Demasiados votos en la ventana (ver `rate_limit.rs`)
+}
+
+/// Voto de un usuario sobre una entidad
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VotoReputacion {
+    Like = 0,
+    Dislike = 1,
+}
+
+/// Contadores de una entidad
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConteoReputacion {
+    pub likes: u32,
+    pub dislikes: u32,
+}
+
+/// Storage keys del sistema de reputación (Persistent Storage)
+/// 
+/// Una entrada por entidad y una por voto: el Instance Storage no
+/// crece con la comunidad.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKeyReputacion {
+    Conteo(Symbol),
+    Voto(Symbol, Address),
+}
+
 /// Proyecto integrador: Sistema de reputación simple
 /// 
-/// Permite dar likes y dislikes a entidades
+/// Permite dar likes y dislikes a entidades. Cada usuario tiene un
+/// voto por entidad (firmado), que puede cambiar o retirar.
+/// 
+/// Opcional: con un registro de votantes configurado por el admin,
+/// solo votan usuarios dados de alta (ver `registro_votantes.rs`).
+/// También puede limitar los votos por usuario (ver `rate_limit.rs`).
 #[contract]
 pub struct ReputationContract;
 
 #[contractimpl]
 impl ReputationContract {
+    /// Define el admin (owner) que configura el registro de votantes
+    /// 
+    /// Lo firma el admin; llamarla en la misma transacción del deploy.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), OwnableError> {
+        admin.require_auth();
+        ownable::inicializar(&env, &admin)
+    }
+
+    /// Admin: solo votan direcciones existentes en `registro`
+    /// (un contrato `GestionUsuario`); None vuelve a abrir el voto
+    pub fn set_voter_registry(env: Env, registro: Option<Address>) -> Result<(), OwnableError> {
+        ownable::require_owner(&env)?;
+        registro_votantes::configurar(&env, registro);
+        Ok(())
+    }
+
+    /// Registro de votantes configurado (None = vota cualquiera)
+    pub fn get_voter_registry(env: Env) -> Option<Address> {
+        registro_votantes::registro(&env)
+    }
+
+    /// Admin: limita las llamadas por dirección (None = sin límite)
+    pub fn set_rate_limit(env: Env, limite: Option<LimiteTasa>) -> Result<(), RateLimitError> {
+        ownable::require_owner(&env).map_err(|_| RateLimitError::SinAdmin)?;
+        rate_limit::configurar(&env, limite)
+    }
+
+    /// Límite de llamadas configurado
+    pub fn get_rate_limit(env: Env) -> Option<LimiteTasa> {
+        rate_limit::limite(&env)
+    }
+
     /// Da un "like" a una entidad
     /// 
+    /// Si el usuario había dado dislike, lo cambia por like.
+    /// 
     /// # Argumentos
     /// - `env`: Entorno de Soroban
     /// - `entity`: Symbol identificando la entidad
-    /// - `user`: Address del usuario que vota
-    pub fn like(env: Env, entity: Symbol, user: Address) {
-        // 1. Verificar que el usuario no haya votado
-        let vote_key = (entity.clone(), user.clone());
-        let existing_vote: Option<Symbol> = env.storage()
-            .instance()
-            .get(&vote_key);
-        
-        if existing_vote.is_some() {
-            panic!("Usuario ya votó por esta entidad");
-        }
-        
-        // 2. Incrementar contador de likes para entity
-        let key_likes = (entity.clone(), symbol_short!("likes"));
-        let mut likes: u32 = env.storage()
-            .instance()
-            .get(&key_likes)
-            .unwrap_or(0);
-        
-        likes += 1;
-        
-        // 3. Registrar que user votó por entity
-        env.storage().instance().set(
-            &vote_key,
-            &symbol_short!("like")
-        );
-        
-        // 4. Guardar nuevo contador de likes
-        env.storage().instance().set(
-            &key_likes,
-            &likes
-        );
-        
-        // 5. Emitir evento
-        env.events().publish(
-            (symbol_short!("like"),),
-            (entity, user, likes)
-        );
+    /// - `user`: Address del usuario que vota (debe firmar)
+    pub fn like(env: Env, entity: Symbol, user: Address) -> Result<(), ErrorReputacion> {
+        Self::votar(env, entity, user, VotoReputacion::Like)
     }
 
     /// Da un "dislike" a una entidad
     /// 
+    /// Si el usuario había dado like, lo cambia por dislike.
+    /// 
     /// # Argumentos
     /// - `env`: Entorno de Soroban
     /// - `entity`: Symbol identificando la entidad
-    /// - `user`: Address del usuario que vota
-    pub fn dislike(env: Env, entity: Symbol, user: Address) {
-        // 1. Verificar que el usuario no haya votado
-        let vote_key = (entity.clone(), user.clone());
-        let existing_vote: Option<Symbol> = env.storage()
-            .instance()
-            .get(&vote_key);
-        
-        if existing_vote.is_some() {
-            panic!("Usuario ya votó por esta entidad");
+    /// - `user`: Address del usuario que vota (debe firmar)
+    pub fn dislike(env: Env, entity: Symbol, user: Address) -> Result<(), ErrorReputacion> {
+        Self::votar(env, entity, user, VotoReputacion::Dislike)
+    }
+
+    /// Retira el voto del usuario sobre una entidad
+    pub fn retract_vote(env: Env, entity: Symbol, user: Address) -> Result<(), ErrorReputacion> {
+        user.require_auth();
+
+        let vote_key = DataKeyReputacion::Voto(entity.clone(), user.clone());
+        let anterior: VotoReputacion = env.storage()
+            .persistent()
+            .get(&vote_key)
+            .ok_or(ErrorReputacion::SinVoto)?;
+
+        let mut conteo = Self::get_counts(env.clone(), entity.clone());
+        match anterior {
+            VotoReputacion::Like => conteo.likes -= 1,
+            VotoReputacion::Dislike => conteo.dislikes -= 1,
         }
-        
-        // 2. Incrementar contador de dislikes para entity
-        let key_dislikes = (entity.clone(), symbol_short!("dislikes"));
-        let mut dislikes: u32 = env.storage()
-            .instance()
-            .get(&key_dislikes)
-            .unwrap_or(0);
-        
-        dislikes += 1;
-        
-        // 3. Registrar que user votó por entity
-        env.storage().instance().set(
-            &vote_key,
-            &symbol_short!("dislike")
-        );
-        
-        // 4. Guardar nuevo contador de dislikes
-        env.storage().instance().set(
-            &key_dislikes,
-            &dislikes
-        );
-        
-        // 5. Emitir evento
+        Self::guardar_conteo(&env, &entity, &conteo);
+        env.storage().persistent().remove(&vote_key);
+
         env.events().publish(
-            (symbol_short!("dislike"),),
-            (entity, user, dislikes)
+            (symbol_short!("retract"),),
+            (entity, user, anterior)
         );
+
+        Ok(())
+    }
+
+    /// Obtiene likes y dislikes de una entidad
+    pub fn get_counts(env: Env, entity: Symbol) -> ConteoReputacion {
+        env.storage()
+            .persistent()
+            .get(&DataKeyReputacion::Conteo(entity))
+            .unwrap_or_default()
     }
 
     /// Obtiene el número de likes para una entidad
     pub fn get_likes(env: Env, entity: Symbol) -> u32 {
-        let key_likes = (entity, symbol_short!("likes"));
-        env.storage()
-            .instance()
-            .get(&key_likes)
-            .unwrap_or(0)
+        Self::get_counts(env, entity).likes
     }
 
     /// Obtiene el número de dislikes para una entidad
     pub fn get_dislikes(env: Env, entity: Symbol) -> u32 {
-        let key_dislikes = (entity, symbol_short!("dislikes"));
-        env.storage()
-            .instance()
-            .get(&key_dislikes)
-            .unwrap_or(0)
+        Self::get_counts(env, entity).dislikes
     }
 
     /// Obtiene el score (likes - dislikes) para una entidad
@@ -550,10 +659,9 @@ impl ReputationContract {
     /// # Retorna
     /// i32 - Puede ser negativo si hay más dislikes que likes
     pub fn get_score(env: Env, entity: Symbol) -> i32 {
-        let likes = Self::get_likes(env.clone(), entity.clone()) as i32;
-        let dislikes = Self::get_dislikes(env, entity) as i32;
+        let conteo = Self::get_counts(env, entity);
         
-        likes - dislikes
+        conteo.likes as i32 - conteo.dislikes as i32
     }
 
     /// Verifica si un usuario ya votó por una entidad
@@ -561,11 +669,78 @@ impl ReputationContract {
     /// # Retorna
     /// true si el usuario ya votó, false si no
     pub fn has_voted(env: Env, entity: Symbol, user: Address) -> bool {
-        let vote_key = (entity, user);
         env.storage()
-            .instance()
-            .get(&vote_key)
-            .is_some()
+            .persistent()
+            .has(&DataKeyReputacion::Voto(entity, user))
+    }
+
+    /// Voto actual del usuario (None si no votó o lo retiró)
+    pub fn get_vote(env: Env, entity: Symbol, user: Address) -> Option<VotoReputacion> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyReputacion::Voto(entity, user))
     }
 }
 
+// Helpers privados
+impl ReputationContract {
+    /// Registra o cambia el voto de `user` y ajusta los contadores
+    fn votar(
+        env: Env,
+        entity: Symbol,
+        user: Address,
+        voto: VotoReputacion,
+    ) -> Result<(), ErrorReputacion> {
+        // 1. Solo el usuario puede votar por sí mismo (y registrado, si se exige)
+        user.require_auth();
+        if !registro_votantes::esta_habilitado(&env, &user) {
+            return Err(ErrorReputacion::VotanteNoRegistrado);
+        }
+        rate_limit::consumir(&env, &user).map_err(|_| ErrorReputacion::RateLimited)?;
+        
+        // 2. Verificar el voto anterior
+        let vote_key = DataKeyReputacion::Voto(entity.clone(), user.clone());
+        let anterior: Option<VotoReputacion> = env.storage()
+            .persistent()
+            .get(&vote_key);
+        
+        if anterior == Some(voto) {
+            return Err(ErrorReputacion::YaVoto);
+        }
+        
+        // 3. Ajustar contadores (un cambio resta del voto anterior)
+        let mut conteo = Self::get_counts(env.clone(), entity.clone());
+        match voto {
+            VotoReputacion::Like => conteo.likes += 1,
+            VotoReputacion::Dislike => conteo.dislikes += 1,
+        }
+        match anterior {
+            Some(VotoReputacion::Like) => conteo.likes -= 1,
+            Some(VotoReputacion::Dislike) => conteo.dislikes -= 1,
+            None => {}
+        }
+        Self::guardar_conteo(&env, &entity, &conteo);
+        
+        // 4. Registrar el voto de user por entity
+        env.storage().persistent().set(&vote_key, &voto);
+        env.storage().persistent().extend_ttl(&vote_key, 100, 100);
+        
+        // 5. Emitir evento
+        let (topic, total) = match voto {
+            VotoReputacion::Like => (symbol_short!("like"), conteo.likes),
+            VotoReputacion::Dislike => (symbol_short!("dislike"), conteo.dislikes),
+        };
+        env.events().publish(
+            (topic,),
+            (entity, user, total)
+        );
+        
+        Ok(())
+    }
+
+    fn guardar_conteo(env: &Env, entity: &Symbol, conteo: &ConteoReputacion) {
+        let key = DataKeyReputacion::Conteo(entity.clone());
+        env.storage().persistent().set(&key, conteo);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+    }
+}
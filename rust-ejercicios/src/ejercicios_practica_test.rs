@@ -2,13 +2,16 @@
 
 use super::ejercicios_practica::*;
 use soroban_sdk::{
-    testutils::Address as _,
+    testutils::{Address as _, Ledger},
     Address,
     BytesN,
     Env,
     Symbol,
     Vec as SorobanVec,
+    symbol_short,
 };
+use crate::storage_patterns::{GestionUsuario, GestionUsuarioClient};
+use crate::checkpoints::TTL_CHECKPOINT;
 
 // ============================================================
 // NIVEL 1: Tests para Mystery Functions
@@ -160,131 +163,143 @@ fn test_set_value_invalid() {
 #[test]
 fn test_contador_con_historial() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = BytesN::from_array(&env, &[0; 32]);
     env.register_contract(&contract_id, ContadorConHistorial);
 
     let client = ContadorConHistorialClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
 
     // Incrementar 7 veces
     for i in 1..=7 {
-        let valor = client.increment();
+        let valor = client.increment(&user);
         assert_eq!(valor, i);
     }
 
     // Verificar historial (debe tener solo los últimos 5: 3, 4, 5, 6, 7)
-    let history = client.get_history();
+    let history = client.get_history(&0, &10).entradas;
     assert_eq!(history.len(), 5);
     
     // Verificar que contiene los valores correctos
-    assert_eq!(history.get(0).unwrap(), 3);
-    assert_eq!(history.get(4).unwrap(), 7);
+    assert_eq!(history.get(0).unwrap().valor, 3);
+    assert_eq!(history.get(4).unwrap().valor, 7);
 }
 
 #[test]
 fn test_historial_menos_de_5() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = BytesN::from_array(&env, &[0; 32]);
     env.register_contract(&contract_id, ContadorConHistorial);
 
     let client = ContadorConHistorialClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
 
     // Incrementar solo 3 veces
-    client.increment();
-    client.increment();
-    client.increment();
+    client.increment(&user);
+    client.increment(&user);
+    client.increment(&user);
 
     // Verificar historial (debe tener 3 elementos)
-    let history = client.get_history();
+    let history = client.get_history(&0, &10).entradas;
     assert_eq!(history.len(), 3);
-    assert_eq!(history.get(0).unwrap(), 1);
-    assert_eq!(history.get(2).unwrap(), 3);
-}
-
-// ============================================================
-// NIVEL 3: Tests para Sistema de Votación
-// ============================================================
-
-#[test]
-fn test_sistema_votacion() {
-    let env = Env::default();
-    let contract_id = BytesN::from_array(&env, &[0; 32]);
-    env.register_contract(&contract_id, SistemaVotacion);
-
-    let client = SistemaVotacionClient::new(&env, &contract_id);
-
-    // Votar por A
-    client.vote_a();
-    client.vote_a();
-    client.vote_a();
-
-    // Votar por B
-    client.vote_b();
-    client.vote_b();
-
-    // Verificar resultados
-    let (votos_a, votos_b) = client.get_results();
-    assert_eq!(votos_a, 3);
-    assert_eq!(votos_b, 2);
-
-    // Verificar ganador
-    let winner = client.get_winner();
-    assert_eq!(winner, symbol_short!("A"));
+    assert_eq!(history.get(0).unwrap().valor, 1);
+    assert_eq!(history.get(2).unwrap().valor, 3);
 }
 
 #[test]
-fn test_sistema_votacion_empate() {
+fn test_historial_circular_paginado() {
     let env = Env::default();
-    let contract_id = BytesN::from_array(&env, &[0; 32]);
-    env.register_contract(&contract_id, SistemaVotacion);
-
-    let client = SistemaVotacionClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ContadorConHistorial);
+    let client = ContadorConHistorialClient::new(&env, &contract_id);
 
-    // Votar igual por ambas
-    client.vote_a();
-    client.vote_b();
+    let owner = Address::generate(&env);
+    assert_eq!(
+        client.try_init_history(&owner, &0),
+        Err(Ok(ErrorHistorial::CapacidadInvalida))
+    );
+    client.init_history(&owner, &3);
+    assert_eq!(env.auths()[0].0, owner);
+    assert_eq!(client.owner(), owner);
+    assert_eq!(
+        client.try_init_history(&owner, &4),
+        Err(Ok(ErrorHistorial::HistorialIniciado))
+    );
 
-    // Verificar empate
-    let winner = client.get_winner();
-    assert_eq!(winner, symbol_short!("tie"));
+    // 5 incrementos de usuarios distintos en ledgers distintos
+    let users: [Address; 5] = core::array::from_fn(|_| Address::generate(&env));
+    for (i, user) in users.iter().enumerate() {
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 10 + i as u32;
+            li.timestamp = 1_000 * i as u64;
+        });
+        client.increment(user);
+        assert_eq!(env.auths()[0].0, *user);
+    }
+    assert_eq!(client.get_history_len(), 3);
+
+    // Página 1: la más vieja que sigue guardada es la tercera escritura
+    let pagina = client.get_history(&0, &2);
+    assert_eq!(pagina.siguiente, Some(2));
+    let entrada = pagina.entradas.get(0).unwrap();
+    assert_eq!(entrada.valor, 3);
+    assert_eq!(entrada.ledger, 12);
+    assert_eq!(entrada.timestamp, 2_000);
+    assert_eq!(entrada.llamador, users[2]);
+    assert_eq!(pagina.entradas.get(1).unwrap().valor, 4);
+
+    // Página 2: la más nueva
+    let pagina = client.get_history(&2, &2);
+    assert_eq!(pagina.siguiente, None);
+    assert_eq!(pagina.entradas.len(), 1);
+    assert_eq!(pagina.entradas.get(0).unwrap().llamador, users[4]);
+
+    assert_eq!(client.try_get_history(&0, &0), Err(Ok(ErrorHistorial::PaginaInvalida)));
 }
 
 #[test]
-fn test_sistema_votacion_b_gana() {
+fn test_historial_no_expira_si_se_consulta() {
     let env = Env::default();
-    let contract_id = BytesN::from_array(&env, &[0; 32]);
-    env.register_contract(&contract_id, SistemaVotacion);
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, ContadorConHistorial);
+    let client = ContadorConHistorialClient::new(&env, &contract_id);
 
-    let client = SistemaVotacionClient::new(&env, &contract_id);
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    client.increment(&Address::generate(&env));
 
-    // Votar más por B
-    client.vote_a();
-    client.vote_b();
-    client.vote_b();
-    client.vote_b();
+    // Mucho más allá de un TTL corto: el historial sigue ahí
+    env.ledger().with_mut(|li| li.sequence_number = 10 + TTL_CHECKPOINT * 3 / 4);
+    assert_eq!(client.get_history(&0, &10).entradas.len(), 1);
 
-    // Verificar que B gana
-    let winner = client.get_winner();
-    assert_eq!(winner, symbol_short!("B"));
+    // La lectura lo extendió: pasa el vencimiento original
+    env.ledger().with_mut(|li| li.sequence_number = 10 + TTL_CHECKPOINT * 3 / 2);
+    let entradas = client.get_history(&0, &10).entradas;
+    assert_eq!(entradas.get(0).unwrap().valor, 1);
 }
 
 // ============================================================
 // PROYECTO INTEGRADOR: Tests para Sistema de Reputación
 // ============================================================
 
+fn crear_reputacion<'a>(env: &Env) -> ReputationContractClient<'a> {
+    let contract_id = env.register_contract(None, ReputationContract);
+    ReputationContractClient::new(env, &contract_id)
+}
+
 #[test]
 fn test_reputation_like() {
     let env = Env::default();
-    let contract_id = BytesN::from_array(&env, &[0; 32]);
-    env.register_contract(&contract_id, ReputationContract);
-
-    let client = ReputationContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let client = crear_reputacion(&env);
 
     let entity = symbol_short!("PRODUCT");
-    let user1 = Address::random(&env);
-    let user2 = Address::random(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
 
-    // User1 da like
+    // User1 da like (el voto lo firma el usuario)
     client.like(&entity, &user1);
+    assert_eq!(env.auths()[0].0, user1);
     assert_eq!(client.get_likes(&entity), 1);
     assert_eq!(client.get_dislikes(&entity), 0);
     assert_eq!(client.get_score(&entity), 1);
@@ -302,13 +317,11 @@ fn test_reputation_like() {
 #[test]
 fn test_reputation_dislike() {
     let env = Env::default();
-    let contract_id = BytesN::from_array(&env, &[0; 32]);
-    env.register_contract(&contract_id, ReputationContract);
-
-    let client = ReputationContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let client = crear_reputacion(&env);
 
     let entity = symbol_short!("PRODUCT");
-    let user = Address::random(&env);
+    let user = Address::generate(&env);
 
     // User da dislike
     client.dislike(&entity, &user);
@@ -320,15 +333,13 @@ fn test_reputation_dislike() {
 #[test]
 fn test_reputation_score_mixto() {
     let env = Env::default();
-    let contract_id = BytesN::from_array(&env, &[0; 32]);
-    env.register_contract(&contract_id, ReputationContract);
-
-    let client = ReputationContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let client = crear_reputacion(&env);
 
     let entity = symbol_short!("PRODUCT");
-    let user1 = Address::random(&env);
-    let user2 = Address::random(&env);
-    let user3 = Address::random(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
 
     // 2 likes
     client.like(&entity, &user1);
@@ -344,35 +355,64 @@ fn test_reputation_score_mixto() {
 }
 
 #[test]
-#[should_panic(expected = "ya votó")]
 fn test_reputation_no_voto_duplicado() {
     let env = Env::default();
-    let contract_id = BytesN::from_array(&env, &[0; 32]);
-    env.register_contract(&contract_id, ReputationContract);
-
-    let client = ReputationContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let client = crear_reputacion(&env);
 
     let entity = symbol_short!("PRODUCT");
-    let user = Address::random(&env);
+    let user = Address::generate(&env);
 
     // Primer voto OK
     client.like(&entity, &user);
 
-    // Segundo voto debe fallar
+    // Repetir el mismo voto falla sin tocar los contadores
+    assert_eq!(client.try_like(&entity, &user), Err(Ok(ErrorReputacion::YaVoto)));
+    assert_eq!(client.get_likes(&entity), 1);
+}
+
+#[test]
+fn test_reputation_cambiar_y_retirar_voto() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = crear_reputacion(&env);
+
+    let entity = symbol_short!("PRODUCT");
+    let user = Address::generate(&env);
+    let otro = Address::generate(&env);
+    client.like(&entity, &otro);
+
+    // Like → dislike
     client.like(&entity, &user);
+    client.dislike(&entity, &user);
+    assert_eq!(client.get_likes(&entity), 1);
+    assert_eq!(client.get_dislikes(&entity), 1);
+    assert_eq!(client.get_vote(&entity, &user), Some(VotoReputacion::Dislike));
+
+    // Retirar deja los contadores como si no hubiera votado
+    client.retract_vote(&entity, &user);
+    assert_eq!(env.auths()[0].0, user);
+    assert_eq!(client.get_counts(&entity), ConteoReputacion { likes: 1, dislikes: 0 });
+    assert_eq!(client.has_voted(&entity, &user), false);
+    assert_eq!(
+        client.try_retract_vote(&entity, &user),
+        Err(Ok(ErrorReputacion::SinVoto))
+    );
+
+    // Puede volver a votar
+    client.dislike(&entity, &user);
+    assert_eq!(client.get_score(&entity), 0);
 }
 
 #[test]
 fn test_reputation_multiple_entidades() {
     let env = Env::default();
-    let contract_id = BytesN::from_array(&env, &[0; 32]);
-    env.register_contract(&contract_id, ReputationContract);
-
-    let client = ReputationContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let client = crear_reputacion(&env);
 
     let entity1 = symbol_short!("PROD1");
     let entity2 = symbol_short!("PROD2");
-    let user = Address::random(&env);
+    let user = Address::generate(&env);
 
     // Votar por ambas entidades
     client.like(&entity1, &user);
@@ -390,15 +430,13 @@ fn test_reputation_multiple_entidades() {
 #[test]
 fn test_reputation_score_negativo() {
     let env = Env::default();
-    let contract_id = BytesN::from_array(&env, &[0; 32]);
-    env.register_contract(&contract_id, ReputationContract);
-
-    let client = ReputationContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let client = crear_reputacion(&env);
 
     let entity = symbol_short!("PRODUCT");
-    let user1 = Address::random(&env);
-    let user2 = Address::random(&env);
-    let user3 = Address::random(&env);
+    let user1 = Address::generate(&env);
+    let user2 = Address::generate(&env);
+    let user3 = Address::generate(&env);
 
     // 1 like
     client.like(&entity, &user1);
@@ -411,3 +449,35 @@ fn test_reputation_score_negativo() {
     assert_eq!(client.get_score(&entity), -1);
 }
 
+#[test]
+fn test_reputation_registro_votantes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = crear_reputacion(&env);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    assert_eq!(env.auths()[0].0, admin);
+
+    let registro_id = env.register_contract(None, GestionUsuario);
+    let registro = GestionUsuarioClient::new(&env, &registro_id);
+    let registrado = Address::generate(&env);
+    registro.initialize(&admin);
+    registro.crear_usuario(&registrado, &0, &0);
+
+    // Solo el admin configura el registro
+    client.set_voter_registry(&Some(registro_id.clone()));
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.get_voter_registry(), Some(registro_id));
+
+    let entity = symbol_short!("PRODUCT");
+    client.like(&entity, &registrado);
+    assert_eq!(
+        client.try_like(&entity, &Address::generate(&env)),
+        Err(Ok(ErrorReputacion::VotanteNoRegistrado))
+    );
+
+    // Sin registro vuelve a votar cualquiera
+    client.set_voter_registry(&None);
+    client.dislike(&entity, &Address::generate(&env));
+    assert_eq!(client.get_score(&entity), 0);
+}
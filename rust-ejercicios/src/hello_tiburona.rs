@@ -8,6 +8,11 @@ use soroban_sdk::{
     Symbol,
     Address,
 };
+use crate::rate_limit::{
+    self,
+    Error as RateLimitError,
+    LimiteTasa,
+};
 
 // ============================================================
 // PARTE 4: HELLO TIBURONA MEJORADO
@@ -33,6 +38,8 @@ pub enum Error {
     NoInicializado = 4,
     /// Error cuando el contrato ya está inicializado
     YaInicializado = 5,
+    /// Error cuando el usuario agotó sus saludos de la ventana
+    RateLimited = 6,
 }
 
 // ============================================================
@@ -151,6 +158,7 @@ impl HelloContract {
     /// # Errores
     /// - `NombreVacio` - Si el nombre está vacío
     /// - `NombreMuyLargo` - Si el nombre es > 32 caracteres
+    /// - `RateLimited` - Si el usuario agotó sus saludos (ver `set_rate_limit`)
     /// 
     /// # Ejemplo
     /// ```rust
@@ -161,6 +169,11 @@ impl HelloContract {
         usuario: Address,
         nombre: Symbol,
     ) -> Result<Symbol, Error> {
+        // Validación 0: Firma del usuario y su límite de saludos (si está configurado)
+        // Sin la firma cualquiera podría gastar el cupo de otra Tiburona
+        usuario.require_auth();
+        rate_limit::consumir(&env, &usuario).map_err(|_| Error::RateLimited)?;
+
         // Validación 1: Nombre no vacío
         // Primera validación = más barata (no requiere storage)
         let nombre_str = nombre.to_string();
@@ -324,6 +337,32 @@ impl HelloContract {
         Ok(())
     }
     
+    /// Limita los saludos por usuario (None = sin límite)
+    /// 
+    /// Solo la administradora puede ejecutar esta función (con su firma).
+    /// 
+    /// # Argumentos
+    /// - `env`: Entorno de Soroban
+    /// - `limite`: Máximo de saludos por ventana de ledgers
+    /// 
+    /// # Errores
+    /// - `SinAdmin` - Si el contrato no está inicializado
+    /// - `LimiteInvalido` - Si `max_llamadas` o `ventana` son 0
+    pub fn set_rate_limit(env: Env, limite: Option<LimiteTasa>) -> Result<(), RateLimitError> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(RateLimitError::SinAdmin)?;
+        admin.require_auth();
+
+        rate_limit::configurar(&env, limite)
+    }
+    
+    /// Obtiene el límite de saludos configurado
+    pub fn get_rate_limit(env: Env) -> Option<LimiteTasa> {
+        rate_limit::limite(&env)
+    }
+    
     // ============================================================
     // FUNCIONES HELPER (para testing)
     // ============================================================
@@ -8,8 +8,10 @@ use soroban_sdk::{
 };
 use crate::hello_tiburona::{
     HelloContract,
+    HelloContractClient,
     Error,
 };
+use crate::rate_limit::LimiteTasa;
 
 #[test]
 fn test_initialize_exitoso() {
@@ -372,3 +374,28 @@ fn test_flujo_completo() {
     );
 }
 
+
+#[test]
+fn test_hello_rate_limit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, HelloContract);
+    let client = HelloContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let usuario = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.set_rate_limit(&Some(LimiteTasa { max_llamadas: 1, ventana: 100 }));
+    assert_eq!(env.auths()[0].0, admin);
+
+    client.hello(&usuario, &symbol_short!("Ana"));
+    assert_eq!(env.auths()[0].0, usuario);
+    assert_eq!(
+        client.try_hello(&usuario, &symbol_short!("Ana")),
+        Err(Ok(Error::RateLimited))
+    );
+
+    // Otra Tiburona tiene su propio cupo
+    client.hello(&Address::generate(&env), &symbol_short!("Luis"));
+    assert_eq!(client.get_contador(), 2);
+}
@@ -2,22 +2,49 @@
 
 // Módulos de ejercicios
 pub mod contador;
+pub mod contador_configurable;
+pub mod contadores_nombrados;
 pub mod ejercicios_practica;
 pub mod traits_ejemplos;
 pub mod result_option_ejemplos;
 pub mod storage_patterns;
 pub mod hello_tiburona;
+pub mod token;
+pub mod ownable;
+pub mod checkpoints;
+pub mod rate_limit;
+pub mod registro_votantes;
+pub mod votacion;
+pub mod registro_propuestas;
+pub mod reputacion_ponderada;
 
 // Re-exportar contratos principales
 pub use contador::ContadorContract;
+pub use contador_configurable::{
+    ContadorConfigurable,
+    Error as ContadorConfigurableError,
+    ConfigContador,
+    PermisoAjuste,
+};
+pub use contadores_nombrados::{
+    ContadoresNombrados,
+    Error as ContadoresNombradosError,
+    ContadorNombrado,
+    PaginaContadores,
+};
 pub use ejercicios_practica::{
     MysteryFunctions,
     ContadorExtendido,
     ContadorConLimite,
     ContadorConSetValue,
     ContadorConHistorial,
-    SistemaVotacion,
+    ErrorHistorial,
+    EntradaHistorial,
+    PaginaHistorial,
     ReputationContract,
+    ErrorReputacion,
+    VotoReputacion,
+    ConteoReputacion,
 };
 pub use traits_ejemplos::{
     Donacion,
@@ -28,6 +55,13 @@ pub use traits_ejemplos::{
     DonacionSalud,
     MicroCredito,
     PropuestaLey,
+    ErrorPropuesta,
+    ModoPeso,
+    OpcionVoto,
+    ReglasVotacion,
+    AccionPropuesta,
+    Ejecucion,
+    MAX_PROFUNDIDAD_DELEGACION,
     registrar_donacion,
     contar_aprobadas,
 };
@@ -37,6 +71,8 @@ pub use result_option_ejemplos::{
     TransferSeguro,
     OptionEjemplo,
     MicroCredito as MicroCreditoResult,
+    Prestamo,
+    EstadoPrestamo,
     ConversionOptionResult,
     DonacionValidada,
     ValidacionHelper,
@@ -50,19 +86,94 @@ pub use storage_patterns::{
     GestionUsuario,
     EstrategiaTTL,
     DonacionInfo,
+    CampanaInfo,
+    PoolIgualacion,
 };
 pub use hello_tiburona::{
     HelloContract,
     Error as HelloError,
     DataKey as HelloDataKey,
 };
+pub use ownable::{
+    Error as OwnableError,
+    DataKeyOwnable,
+};
+pub use registro_votantes::DataKeyRegistroVotantes;
+pub use checkpoints::{
+    Checkpoint,
+    Error as CheckpointsError,
+};
+pub use rate_limit::{
+    Error as RateLimitError,
+    LimiteTasa,
+    DataKeyRateLimit,
+};
+pub use votacion::{
+    SistemaVotacion,
+    Error as VotacionError,
+    Propuesta,
+    Ganador,
+    ModoVotacion,
+    Resultados,
+    Ronda,
+};
+pub use registro_propuestas::{
+    RegistroPropuestas,
+    Error as RegistroError,
+    EstadoPropuesta,
+    PropuestaRegistrada,
+    PaginaPropuestas,
+};
+pub use reputacion_ponderada::{
+    ReputacionPonderada,
+    Error as ReputacionPonderadaError,
+    Puntaje,
+    VotoPonderado,
+};
+pub use token::{
+    TokenContract,
+    Error as TokenError,
+};
 
 // Ejercicios prácticos originales
 use soroban_sdk::{
-    contract, contractimpl, symbol_short, Address, Env, Vec as SorobanVec, 
-    String as SorobanString, Symbol,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env,
+    Vec as SorobanVec, String as SorobanString, Symbol,
 };
 
+/// Errores del ledger del Ejercicio 8
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ErrorEjercicios {
+    /// El ledger ya fue inicializado
+    YaInicializado = 1,
+    /// El ledger no ha sido inicializado
+    NoInicializado = 2,
+    /// El monto debe ser mayor a 0
+    MontoInvalido = 3,
+    /// La cuenta no tiene balance suficiente
+    BalanceInsuficiente = 4,
+    /// El spender no tiene allowance suficiente
+    AllowanceInsuficiente = 5,
+    /// Overflow al sumar balances
+    Overflow = 6,
+}
+
+/// DataKey para el ledger del Ejercicio 8
+///
+/// Patrón: Instance = configuración, Persistent = datos por cuenta
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKeyEjercicios {
+    /// Administradora que puede crear tokens (Instance)
+    Admin,
+    /// Balance de cada cuenta (Persistent)
+    Balance(Address),
+    /// Gasto delegado: (dueña, spender) → monto (Persistent)
+    Allowance(Address, Address),
+}
+
 /// Estructura para el contrato de ejercicios
 #[contract]
 pub struct EjerciciosSoroban;
@@ -195,12 +306,67 @@ impl EjerciciosSoroban {
     // ============================================================
     // EJERCICIO 8: Transferencia de Tokens (DESAFÍO COMPLETO)
     // ============================================================
+    /// Inicializa el ledger con una administradora
+    ///
+    /// Solo puede llamarse una vez. La administradora es la única
+    /// que puede crear balances nuevos con `mint`.
+    ///
+    /// # Argumentos
+    /// - `env`: Entorno de Soroban
+    /// - `admin`: Dirección de la administradora
+    pub fn initialize(env: Env, admin: Address) -> Result<(), ErrorEjercicios> {
+        if env.storage().instance().has(&DataKeyEjercicios::Admin) {
+            return Err(ErrorEjercicios::YaInicializado);
+        }
+
+        env.storage().instance().set(&DataKeyEjercicios::Admin, &admin);
+        env.storage().instance().extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    /// Crea tokens nuevos en la cuenta `to` (solo administradora)
+    ///
+    /// Reemplaza al antiguo `establecer_balance`: en lugar de
+    /// sobrescribir balances, suma de forma segura y exige la firma
+    /// de la administradora.
+    ///
+    /// # Argumentos
+    /// - `env`: Entorno de Soroban
+    /// - `to`: Dirección que recibe los tokens
+    /// - `amount`: Cantidad a crear (u128)
+    pub fn mint(env: Env, to: Address, amount: u128) -> Result<(), ErrorEjercicios> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKeyEjercicios::Admin)
+            .ok_or(ErrorEjercicios::NoInicializado)?;
+        admin.require_auth();
+
+        if amount == 0 {
+            return Err(ErrorEjercicios::MontoInvalido);
+        }
+
+        let nuevo_balance = Self::obtener_balance(env.clone(), to.clone())
+            .checked_add(amount)
+            .ok_or(ErrorEjercicios::Overflow)?;
+        Self::guardar_balance(&env, &to, nuevo_balance);
+
+        env.events().publish(
+            (symbol_short!("mint"),),
+            (to, amount),
+        );
+
+        Ok(())
+    }
+
     /// Transfiere tokens de una cuenta a otra
     ///
     /// # Validaciones
-    /// 1. Amount > 0
-    /// 2. From tiene balance suficiente
-    /// 3. Uso de checked_sub/checked_add para prevenir overflow/underflow
+    /// 1. `from` autorizó la operación (require_auth)
+    /// 2. Amount > 0
+    /// 3. From tiene balance suficiente
+    /// 4. Uso de checked_sub/checked_add para prevenir overflow/underflow
     ///
     /// # Argumentos
     /// - `env`: Entorno de Soroban
@@ -209,7 +375,7 @@ impl EjerciciosSoroban {
     /// - `amount`: Cantidad a transferir (u128)
     ///
     /// # Retorna
-    /// Result<(), String> - Ok(()) si éxito, Err con mensaje si falla
+    /// Result<(), ErrorEjercicios> - Ok(()) si éxito, Err con el motivo si falla
     ///
     /// # Ejemplo de uso
     /// ```
@@ -220,65 +386,86 @@ impl EjerciciosSoroban {
         from: Address,
         to: Address,
         amount: u128,
-    ) -> Result<(), SorobanString> {
-        // Validación 1: Amount mayor a 0
+    ) -> Result<(), ErrorEjercicios> {
+        // Validación 1: Solo el dueño de los fondos puede moverlos
+        from.require_auth();
+
+        Self::mover_balance(&env, &from, &to, amount)?;
+
+        // Emitir evento de transferencia
+        env.events().publish(
+            (symbol_short!("transfer"),),
+            (from, to, amount),
+        );
+
+        Ok(())
+    }
+
+    /// Autoriza a `spender` a gastar hasta `amount` de los fondos de `from`
+    ///
+    /// Sobrescribe la autorización anterior; `amount = 0` la revoca.
+    ///
+    /// # Argumentos
+    /// - `env`: Entorno de Soroban
+    /// - `from`: Dueña de los fondos
+    /// - `spender`: Dirección autorizada a gastar
+    /// - `amount`: Máximo que puede gastar
+    pub fn approve(env: Env, from: Address, spender: Address, amount: u128) {
+        from.require_auth();
+
+        let key = DataKeyEjercicios::Allowance(from.clone(), spender.clone());
         if amount == 0 {
-            return Err(SorobanString::from_str(
-                &env,
-                "El monto debe ser mayor a 0",
-            ));
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &amount);
+            env.storage().persistent().extend_ttl(&key, 100, 100);
         }
 
-        // Leer balance del remitente
-        // En Soroban, usamos una tupla (Address, Symbol) como key para storage por cuenta
-        let key_balance = symbol_short!("balance");
-        let balance_from: u128 = env
-            .storage()
-            .instance()
-            .get(&(from.clone(), key_balance.clone()))
-            .unwrap_or(0);
+        env.events().publish(
+            (symbol_short!("approve"),),
+            (from, spender, amount),
+        );
+    }
 
-        // Validación 2: Balance suficiente
-        if balance_from < amount {
-            return Err(SorobanString::from_str(
-                &env,
-                "Balance insuficiente",
-            ));
-        }
+    /// Obtiene cuánto puede gastar `spender` de los fondos de `from`
+    pub fn allowance(env: Env, from: Address, spender: Address) -> u128 {
+        env.storage()
+            .persistent()
+            .get(&DataKeyEjercicios::Allowance(from, spender))
+            .unwrap_or(0)
+    }
 
-        // Leer balance del destinatario
-        let balance_to: u128 = env
-            .storage()
-            .instance()
-            .get(&(to.clone(), key_balance.clone()))
-            .unwrap_or(0);
+    /// Transfiere tokens de `from` a `to` en nombre de `spender`
+    ///
+    /// # Validaciones
+    /// 1. `spender` autorizó la operación (require_auth)
+    /// 2. El allowance de `spender` cubre el monto
+    /// 3. Las mismas validaciones que `transferir`
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: u128,
+    ) -> Result<(), ErrorEjercicios> {
+        spender.require_auth();
 
-        // Restar de forma segura (prevenir underflow)
-        let nuevo_balance_from = balance_from
+        let allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
+        let nuevo_allowance = allowance
             .checked_sub(amount)
-            .ok_or(SorobanString::from_str(
-                &env,
-                "Error: underflow al restar",
-            ))?;
+            .ok_or(ErrorEjercicios::AllowanceInsuficiente)?;
 
-        // Sumar de forma segura (prevenir overflow)
-        let nuevo_balance_to = balance_to
-            .checked_add(amount)
-            .ok_or(SorobanString::from_str(
-                &env,
-                "Error: overflow al sumar",
-            ))?;
+        Self::mover_balance(&env, &from, &to, amount)?;
 
-        // Actualizar balances en storage
-        let key_balance = symbol_short!("balance");
-        env.storage()
-            .instance()
-            .set(&(from.clone(), key_balance.clone()), &nuevo_balance_from);
-        env.storage()
-            .instance()
-            .set(&(to.clone(), key_balance), &nuevo_balance_to);
+        // Descontar el allowance solo si la transferencia fue exitosa
+        let key = DataKeyEjercicios::Allowance(from.clone(), spender);
+        if nuevo_allowance == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &nuevo_allowance);
+            env.storage().persistent().extend_ttl(&key, 100, 100);
+        }
 
-        // Emitir evento de transferencia
         env.events().publish(
             (symbol_short!("transfer"),),
             (from, to, amount),
@@ -296,24 +483,12 @@ impl EjerciciosSoroban {
     /// # Retorna
     /// Balance de la cuenta (u128)
     pub fn obtener_balance(env: Env, account: Address) -> u128 {
-        let key_balance = symbol_short!("balance");
         env.storage()
-            .instance()
-            .get(&(account, key_balance))
+            .persistent()
+            .get(&DataKeyEjercicios::Balance(account))
             .unwrap_or(0)
     }
 
-    /// Establece el balance inicial de una cuenta (útil para testing)
-    ///
-    /// # Argumentos
-    /// - `env`: Entorno de Soroban
-    /// - `account`: Dirección de la cuenta
-    /// - `balance`: Balance inicial
-    pub fn establecer_balance(env: Env, account: Address, balance: u128) {
-        let key_balance = symbol_short!("balance");
-        env.storage().instance().set(&(account, key_balance), &balance);
-    }
-
     // ============================================================
     // FUNCIONES AUXILIARES ADICIONALES
     // ============================================================
@@ -353,3 +528,41 @@ impl EjerciciosSoroban {
     }
 }
 
+// Helpers privados del ledger (no se exponen en el contrato)
+impl EjerciciosSoroban {
+    /// Mueve `amount` de `from` a `to` con todas las validaciones
+    fn mover_balance(
+        env: &Env,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+    ) -> Result<(), ErrorEjercicios> {
+        // Amount mayor a 0
+        if amount == 0 {
+            return Err(ErrorEjercicios::MontoInvalido);
+        }
+
+        // Restar de forma segura (prevenir underflow = balance insuficiente)
+        let balance_from = Self::obtener_balance(env.clone(), from.clone());
+        let nuevo_balance_from = balance_from
+            .checked_sub(amount)
+            .ok_or(ErrorEjercicios::BalanceInsuficiente)?;
+        Self::guardar_balance(env, from, nuevo_balance_from);
+
+        // Sumar de forma segura (se lee después de restar por si from == to)
+        let balance_to = Self::obtener_balance(env.clone(), to.clone());
+        let nuevo_balance_to = balance_to
+            .checked_add(amount)
+            .ok_or(ErrorEjercicios::Overflow)?;
+        Self::guardar_balance(env, to, nuevo_balance_to);
+
+        Ok(())
+    }
+
+    /// Guarda un balance en Persistent Storage y extiende su TTL
+    fn guardar_balance(env: &Env, account: &Address, balance: u128) {
+        let key = DataKeyEjercicios::Balance(account.clone());
+        env.storage().persistent().set(&key, &balance);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+    }
+}
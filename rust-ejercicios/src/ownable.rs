@@ -0,0 +1,145 @@
+#![no_std]
+use soroban_sdk::{
+    contracterror,
+    contracttype,
+    Env,
+    Address,
+    symbol_short,
+};
+
+// ============================================================
+// OWNABLE REUTILIZABLE
+// ============================================================
+//
+// Funciones para que cualquier contrato del crate tenga un owner:
+//
+//   ownable::inicializar(&env, &owner)?;     // en initialize
+//   ownable::require_owner(&env)?;           // en funciones de admin
+//
+// El traspaso es en dos pasos (proponer → aceptar), así un error
+// al escribir la dirección nueva no deja el contrato sin dueño.
+
+// ============================================================
+// DEFINICIÓN DE ERRORES PERSONALIZADOS
+// ============================================================
+
+/// Errores del patrón Ownable
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    NoInicializado = 1,
+    YaInicializado = 2,
+    SinOwner = 3,           // El owner renunció
+    SinOwnerPendiente = 4,
+}
+
+/// Storage keys para Ownable (Instance Storage)
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKeyOwnable {
+    Owner,          // Option<Address>: None = renunciado
+    PendingOwner,
+}
+
+// ============================================================
+// FUNCIONES
+// ============================================================
+
+/// Guarda el owner inicial (una sola vez)
+pub fn inicializar(env: &Env, owner: &Address) -> Result<(), Error> {
+    if env.storage().instance().has(&DataKeyOwnable::Owner) {
+        return Err(Error::YaInicializado);
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKeyOwnable::Owner, &Some(owner.clone()));
+
+    Ok(())
+}
+
+/// Obtiene el owner actual
+pub fn owner(env: &Env) -> Result<Address, Error> {
+    let owner: Option<Address> = env.storage()
+        .instance()
+        .get(&DataKeyOwnable::Owner)
+        .ok_or(Error::NoInicializado)?;
+
+    owner.ok_or(Error::SinOwner)
+}
+
+/// Obtiene el owner propuesto que aún no aceptó
+pub fn owner_pendiente(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKeyOwnable::PendingOwner)
+}
+
+/// Verifica la firma del owner guardado y lo devuelve
+///
+/// Usar al comienzo de toda función administrativa.
+pub fn require_owner(env: &Env) -> Result<Address, Error> {
+    let owner = owner(env)?;
+    owner.require_auth();
+    Ok(owner)
+}
+
+/// Paso 1: el owner propone a un nuevo owner
+///
+/// Proponer de nuevo reemplaza la propuesta anterior.
+pub fn proponer(env: &Env, nuevo_owner: &Address) -> Result<(), Error> {
+    let owner = require_owner(env)?;
+
+    env.storage()
+        .instance()
+        .set(&DataKeyOwnable::PendingOwner, nuevo_owner);
+
+    env.events().publish(
+        (symbol_short!("own_prop"), owner),
+        nuevo_owner.clone(),
+    );
+
+    Ok(())
+}
+
+/// Paso 2: el owner propuesto acepta con su firma
+///
+/// Devuelve el owner anterior.
+pub fn aceptar(env: &Env) -> Result<Address, Error> {
+    let nuevo_owner: Address = env.storage()
+        .instance()
+        .get(&DataKeyOwnable::PendingOwner)
+        .ok_or(Error::SinOwnerPendiente)?;
+
+    nuevo_owner.require_auth();
+
+    let anterior = owner(env)?;
+
+    env.storage()
+        .instance()
+        .set(&DataKeyOwnable::Owner, &Some(nuevo_owner.clone()));
+    env.storage().instance().remove(&DataKeyOwnable::PendingOwner);
+
+    env.events().publish(
+        (symbol_short!("own_acep"), anterior.clone()),
+        nuevo_owner,
+    );
+
+    Ok(anterior)
+}
+
+/// El owner renuncia: el contrato queda sin owner para siempre
+///
+/// Las funciones protegidas con `require_owner` dejan de funcionar
+/// y se descarta cualquier propuesta pendiente.
+pub fn renunciar(env: &Env) -> Result<(), Error> {
+    let owner = require_owner(env)?;
+
+    env.storage()
+        .instance()
+        .set(&DataKeyOwnable::Owner, &None::<Address>);
+    env.storage().instance().remove(&DataKeyOwnable::PendingOwner);
+
+    env.events().publish((symbol_short!("own_renun"),), owner);
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+#![cfg(test)]
+use soroban_sdk::{
+    contract,
+    testutils::Address as _,
+    Address,
+    Env,
+};
+use crate::ownable::{
+    self,
+    Error,
+};
+
+/// Contrato vacío: solo da el contexto de storage para las funciones
+#[contract]
+struct ContratoPrueba;
+
+fn setup(env: &Env) -> Address {
+    env.register_contract(None, ContratoPrueba)
+}
+
+#[test]
+fn test_inicializar_y_owner() {
+    let env = Env::default();
+    let contrato = setup(&env);
+    let owner = Address::generate(&env);
+
+    env.as_contract(&contrato, || {
+        assert_eq!(ownable::owner(&env), Err(Error::NoInicializado));
+
+        ownable::inicializar(&env, &owner).unwrap();
+        assert_eq!(ownable::owner(&env), Ok(owner.clone()));
+
+        // Solo una vez
+        assert_eq!(ownable::inicializar(&env, &owner), Err(Error::YaInicializado));
+    });
+}
+
+#[test]
+fn test_proponer_y_aceptar() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contrato = setup(&env);
+    let owner = Address::generate(&env);
+    let nuevo_owner = Address::generate(&env);
+    let otro = Address::generate(&env);
+
+    // Cada firma se verifica en su propia invocación
+    env.as_contract(&contrato, || {
+        ownable::inicializar(&env, &owner).unwrap();
+        assert_eq!(ownable::aceptar(&env), Err(Error::SinOwnerPendiente));
+        ownable::proponer(&env, &otro).unwrap();
+    });
+
+    // Una propuesta nueva reemplaza a la anterior
+    env.as_contract(&contrato, || {
+        ownable::proponer(&env, &nuevo_owner).unwrap();
+        assert_eq!(ownable::owner_pendiente(&env), Some(nuevo_owner.clone()));
+        assert_eq!(ownable::owner(&env), Ok(owner.clone()));
+    });
+
+    env.as_contract(&contrato, || {
+        assert_eq!(ownable::aceptar(&env), Ok(owner.clone()));
+        assert_eq!(ownable::owner(&env), Ok(nuevo_owner.clone()));
+        assert_eq!(ownable::owner_pendiente(&env), None);
+    });
+}
+
+#[test]
+fn test_renunciar() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contrato = setup(&env);
+    let owner = Address::generate(&env);
+    let nuevo_owner = Address::generate(&env);
+
+    env.as_contract(&contrato, || {
+        ownable::inicializar(&env, &owner).unwrap();
+        ownable::proponer(&env, &nuevo_owner).unwrap();
+    });
+
+    env.as_contract(&contrato, || {
+        ownable::renunciar(&env).unwrap();
+    });
+
+    env.as_contract(&contrato, || {
+        // Sin owner y sin propuesta pendiente
+        assert_eq!(ownable::owner(&env), Err(Error::SinOwner));
+        assert_eq!(ownable::require_owner(&env), Err(Error::SinOwner));
+        assert_eq!(ownable::aceptar(&env), Err(Error::SinOwnerPendiente));
+
+        // No se puede volver a inicializar
+        assert_eq!(ownable::inicializar(&env, &nuevo_owner), Err(Error::YaInicializado));
+    });
+}
@@ -0,0 +1,144 @@
+#![no_std]
+use soroban_sdk::{
+    contracterror,
+    contracttype,
+    Env,
+    Address,
+    symbol_short,
+};
+
+// ============================================================
+// LÍMITE DE LLAMADAS (REUTILIZABLE)
+// ============================================================
+//
+// Funciones para limitar cuántas veces por ventana puede llamar una
+// dirección a una función pública:
+//
+//   rate_limit::configurar(&env, Some(limite))?;    // admin
+//
+//   rate_limit::consumir(&env, &usuario).map_err(|_| Error::RateLimited)?;
+//
+// Ventana fija medida en secuencia del ledger: cada dirección tiene
+// `max_llamadas` por cada `ventana` ledgers. El uso va en Temporary
+// Storage con TTL igual a la ventana, así expira solo y no paga
+// renta de más. Sin límite configurado no se limita nada.
+
+// ============================================================
+// DEFINICIÓN DE ERRORES PERSONALIZADOS
+// ============================================================
+
+/// Errores del límite de llamadas
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    RateLimited = 1,        // Se agotaron las llamadas de la ventana
+    LimiteInvalido = 2,
+    SinAdmin = 3,           // El contrato no tiene quién configure el límite
+}
+
+// ============================================================
+// TIPOS Y STORAGE
+// ============================================================
+
+/// Límite configurado (Instance Storage)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LimiteTasa {
+    pub max_llamadas: u32,
+    pub ventana: u32,           // Largo de la ventana en ledgers
+}
+
+/// Uso de una dirección en su ventana actual (Temporary Storage)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UsoVentana {
+    pub inicio: u32,            // Secuencia del ledger donde empezó la ventana
+    pub llamadas: u32,
+}
+
+/// Storage keys del límite de llamadas
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKeyRateLimit {
+    Limite,                 // Instance
+    Uso(Address),           // Temporary
+}
+
+// ============================================================
+// FUNCIONES
+// ============================================================
+
+/// Configura el límite (None = sin límite)
+///
+/// La verificación de permisos es de quien llama. Cambiar el límite
+/// no reinicia las ventanas en curso. La ventana tiene que ser menor
+/// que el TTL máximo de la red (`max_ttl`): el uso vive en Temporary
+/// Storage con TTL igual a lo que queda de la ventana.
+pub fn configurar(env: &Env, limite: Option<LimiteTasa>) -> Result<(), Error> {
+    match &limite {
+        Some(limite) => {
+            if limite.max_llamadas == 0
+                || limite.ventana == 0
+                || limite.ventana >= env.storage().max_ttl()
+            {
+                return Err(Error::LimiteInvalido);
+            }
+            env.storage().instance().set(&DataKeyRateLimit::Limite, limite);
+        }
+        None => env.storage().instance().remove(&DataKeyRateLimit::Limite),
+    }
+
+    env.events().publish((symbol_short!("rate_lim"),), limite);
+
+    Ok(())
+}
+
+/// Límite configurado
+pub fn limite(env: &Env) -> Option<LimiteTasa> {
+    env.storage().instance().get(&DataKeyRateLimit::Limite)
+}
+
+/// Cuenta una llamada de `cuenta`; Err(RateLimited) si ya no le quedan
+///
+/// Usar al comienzo de la función a limitar (después de require_auth).
+pub fn consumir(env: &Env, cuenta: &Address) -> Result<(), Error> {
+    let Some(limite) = limite(env) else {
+        return Ok(());
+    };
+
+    let mut uso = uso_actual(env, &limite, cuenta);
+    if uso.llamadas >= limite.max_llamadas {
+        return Err(Error::RateLimited);
+    }
+    uso.llamadas += 1;
+
+    // El TTL cubre lo que queda de la ventana: después ya no sirve
+    let key = DataKeyRateLimit::Uso(cuenta.clone());
+    let restante = uso.inicio.saturating_add(limite.ventana) - env.ledger().sequence();
+    env.storage().temporary().set(&key, &uso);
+    env.storage().temporary().extend_ttl(&key, restante, restante);
+
+    Ok(())
+}
+
+/// Llamadas que le quedan a `cuenta` en su ventana (None = sin límite)
+pub fn restantes(env: &Env, cuenta: &Address) -> Option<u32> {
+    let limite = limite(env)?;
+    let uso = uso_actual(env, &limite, cuenta);
+    Some(limite.max_llamadas.saturating_sub(uso.llamadas))
+}
+
+/// Uso de `cuenta` en la ventana vigente (una nueva si la anterior terminó)
+fn uso_actual(env: &Env, limite: &LimiteTasa, cuenta: &Address) -> UsoVentana {
+    let ahora = env.ledger().sequence();
+    let nueva = UsoVentana { inicio: ahora, llamadas: 0 };
+
+    match env.storage()
+        .temporary()
+        .get::<_, UsoVentana>(&DataKeyRateLimit::Uso(cuenta.clone()))
+    {
+        Some(uso) if ahora < uso.inicio.saturating_add(limite.ventana) => uso,
+        _ => nueva,
+    }
+}
@@ -0,0 +1,98 @@
+#![cfg(test)]
+use soroban_sdk::{
+    contract,
+    testutils::{Address as _, Ledger},
+    Address,
+    Env,
+};
+use crate::rate_limit::{
+    self,
+    Error,
+    LimiteTasa,
+};
+
+/// Contrato vacío: solo da el contexto de storage para las funciones
+#[contract]
+struct ContratoPrueba;
+
+fn setup(env: &Env) -> Address {
+    env.register_contract(None, ContratoPrueba)
+}
+
+fn ir_al_ledger(env: &Env, secuencia: u32) {
+    env.ledger().with_mut(|li| li.sequence_number = secuencia);
+}
+
+#[test]
+fn test_ventana_fija() {
+    let env = Env::default();
+    let contrato = setup(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+
+    env.as_contract(&contrato, || {
+        // Sin límite no se cuenta nada
+        assert_eq!(rate_limit::consumir(&env, &ana), Ok(()));
+        assert_eq!(rate_limit::restantes(&env, &ana), None);
+
+        rate_limit::configurar(&env, Some(LimiteTasa { max_llamadas: 2, ventana: 10 })).unwrap();
+
+        ir_al_ledger(&env, 100);
+        assert_eq!(rate_limit::consumir(&env, &ana), Ok(()));
+        ir_al_ledger(&env, 105);
+        assert_eq!(rate_limit::consumir(&env, &ana), Ok(()));
+        assert_eq!(rate_limit::consumir(&env, &ana), Err(Error::RateLimited));
+        assert_eq!(rate_limit::restantes(&env, &ana), Some(0));
+
+        // Cada dirección tiene su propia ventana
+        assert_eq!(rate_limit::consumir(&env, &bea), Ok(()));
+        assert_eq!(rate_limit::restantes(&env, &bea), Some(1));
+
+        // La ventana de ana empezó en 100: en 110 arranca otra
+        ir_al_ledger(&env, 109);
+        assert_eq!(rate_limit::consumir(&env, &ana), Err(Error::RateLimited));
+        ir_al_ledger(&env, 110);
+        assert_eq!(rate_limit::restantes(&env, &ana), Some(2));
+        assert_eq!(rate_limit::consumir(&env, &ana), Ok(()));
+    });
+}
+
+#[test]
+fn test_configurar() {
+    let env = Env::default();
+    let contrato = setup(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+
+    env.as_contract(&contrato, || {
+        assert_eq!(
+            rate_limit::configurar(&env, Some(LimiteTasa { max_llamadas: 0, ventana: 10 })),
+            Err(Error::LimiteInvalido)
+        );
+        assert_eq!(
+            rate_limit::configurar(&env, Some(LimiteTasa { max_llamadas: 1, ventana: 0 })),
+            Err(Error::LimiteInvalido)
+        );
+
+        // La ventana tiene que caber en el TTL de Temporary Storage
+        let max_ttl = env.storage().max_ttl();
+        assert_eq!(
+            rate_limit::configurar(&env, Some(LimiteTasa { max_llamadas: 1, ventana: max_ttl })),
+            Err(Error::LimiteInvalido)
+        );
+        let larga = LimiteTasa { max_llamadas: 1, ventana: max_ttl - 1 };
+        rate_limit::configurar(&env, Some(larga)).unwrap();
+        rate_limit::consumir(&env, &bea).unwrap();
+        rate_limit::configurar(&env, None).unwrap();
+
+        let limite = LimiteTasa { max_llamadas: 1, ventana: 10 };
+        rate_limit::configurar(&env, Some(limite.clone())).unwrap();
+        assert_eq!(rate_limit::limite(&env), Some(limite));
+        rate_limit::consumir(&env, &ana).unwrap();
+        assert_eq!(rate_limit::consumir(&env, &ana), Err(Error::RateLimited));
+
+        // Quitar el límite vuelve a dejar pasar todo
+        rate_limit::configurar(&env, None).unwrap();
+        assert_eq!(rate_limit::consumir(&env, &ana), Ok(()));
+    });
+}
@@ -0,0 +1,481 @@
+#![no_std]
+use soroban_sdk::{
+    contract,
+    contractimpl,
+    contracterror,
+    contracttype,
+    Env,
+    Address,
+    Symbol,
+    TryFromVal,
+    Val,
+    Vec,
+    symbol_short,
+};
+use crate::checkpoints::TTL_CHECKPOINT;
+use crate::traits_ejemplos::{
+    OpcionVoto,
+    ReglasVotacion,
+    Votable,
+};
+
+// ============================================================
+// REGISTRO ON-CHAIN DE PROPUESTAS
+// ============================================================
+//
+// `contar_aprobadas` (traits_ejemplos.rs) solo funciona con un
+// slice en memoria. Este contrato guarda propuestas estilo
+// `PropuestaLey` por ID, con estado, y permite preguntarle a la
+// cadena cuántas pasaron:
+//
+//   Abierta ──finalizar──► Aprobada ──marcar_ejecutada──► Ejecutada
+//                     └──► Rechazada
+//
+// Cada estado tiene su índice paginable, y las cantidades por
+// estado se mantienen al día, así que contar no recorre nada.
+//
+// Votan solo las direcciones a las que el creador les asignó peso
+// (como `ModoPeso::Asignado` de `PropuestaLey`): una dirección nueva
+// no tiene peso, así que crear cuentas no da votos.
+//
+// El registro es de larga vida: las entradas y la instancia viven
+// `TTL_CHECKPOINT` ledgers y se extienden también al leerlas.
+
+// ============================================================
+// DEFINICIÓN DE ERRORES PERSONALIZADOS
+// ============================================================
+
+/// Errores del registro de propuestas
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    PropuestaNoExiste = 1,
+    DuracionInvalida = 2,
+    ReglasInvalidas = 3,
+    VotacionCerrada = 4,
+    YaVoto = 5,
+    VotacionAbierta = 6,
+    YaFinalizada = 7,
+    NoAprobada = 8,
+    PaginaInvalida = 9,
+    SinPeso = 10,
+}
+
+/// Máximo de propuestas por página en `listar_por_estado`
+pub const MAX_PAGINA: u32 = 50;
+
+// ============================================================
+// TIPOS Y STORAGE
+// ============================================================
+
+/// Estado de una propuesta registrada
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EstadoPropuesta {
+    Abierta = 0,
+    Aprobada = 1,
+    Rechazada = 2,
+    Ejecutada = 3,      // Aprobada y ya llevada a cabo
+}
+
+/// Propuesta guardada (Persistent Storage)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PropuestaRegistrada {
+    pub id: u32,
+    pub creador: Address,
+    pub titulo: Symbol,
+    pub estado: EstadoPropuesta,
+    pub creada: u64,            // Timestamp de creación
+    pub cierre: u64,            // Desde este timestamp ya no se vota
+    pub votos_si: i128,
+    pub votos_no: i128,
+    pub abstenciones: i128,
+    pub reglas: ReglasVotacion,
+}
+
+/// Página de resultados de `listar_por_estado`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaginaPropuestas {
+    pub propuestas: Vec<PropuestaRegistrada>,
+    /// Cursor para pedir la página siguiente (None = no hay más)
+    pub siguiente: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    // Instance
+    TotalPropuestas,
+    TotalEstado(EstadoPropuesta),
+
+    // Persistent
+    Propuesta(u32),
+    Voto(u32, Address),                 // (propuesta, votante) → opción
+    Peso(u32, Address),                 // (propuesta, votante) → peso asignado
+    IndiceEstado(EstadoPropuesta, u32), // (estado, posición) → ID
+    PosicionEstado(u32),                // ID → posición en el índice de su estado
+}
+
+// ============================================================
+// CONTRATO
+// ============================================================
+
+/// Registro de propuestas con estado y consultas paginadas
+#[contract]
+pub struct RegistroPropuestas;
+
+#[contractimpl]
+impl RegistroPropuestas {
+    /// Crea una propuesta abierta por `duracion` segundos y devuelve su ID
+    pub fn crear_propuesta(
+        env: Env,
+        creador: Address,
+        titulo: Symbol,
+        duracion: u64,
+        reglas: ReglasVotacion,
+    ) -> Result<u32, Error> {
+        creador.require_auth();
+
+        if duracion == 0 {
+            return Err(Error::DuracionInvalida);
+        }
+
+        if reglas.quorum < 0 || reglas.umbral_bps > 10_000 {
+            return Err(Error::ReglasInvalidas);
+        }
+
+        let id = Self::get_total_propuestas(env.clone());
+        let ahora = env.ledger().timestamp();
+
+        let propuesta = PropuestaRegistrada {
+            id,
+            creador: creador.clone(),
+            titulo: titulo.clone(),
+            estado: EstadoPropuesta::Abierta,
+            creada: ahora,
+            cierre: ahora.saturating_add(duracion),
+            votos_si: 0,
+            votos_no: 0,
+            abstenciones: 0,
+            reglas,
+        };
+
+        Self::guardar_propuesta(&env, &propuesta);
+        Self::agregar_a_indice(&env, EstadoPropuesta::Abierta, id);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalPropuestas, &(id + 1));
+        Self::extender_instancia(&env);
+
+        env.events().publish(
+            (symbol_short!("prop_new"), creador),
+            (id, titulo),
+        );
+
+        Ok(id)
+    }
+
+    /// El creador asigna el peso de `votante` en una propuesta abierta
+    ///
+    /// Se puede cambiar mientras el votante no haya votado; 0 lo
+    /// saca del padrón.
+    pub fn asignar_peso(env: Env, id: u32, votante: Address, peso: i128) -> Result<(), Error> {
+        let propuesta = Self::get_propuesta(env.clone(), id)?;
+        propuesta.creador.require_auth();
+
+        if propuesta.estado != EstadoPropuesta::Abierta
+            || env.ledger().timestamp() >= propuesta.cierre
+        {
+            return Err(Error::VotacionCerrada);
+        }
+
+        if peso < 0 {
+            return Err(Error::SinPeso);
+        }
+
+        if env.storage().persistent().has(&DataKey::Voto(id, votante.clone())) {
+            return Err(Error::YaVoto);
+        }
+
+        let key = DataKey::Peso(id, votante);
+        env.storage().persistent().set(&key, &peso);
+        Self::extender(&env, &key);
+
+        Ok(())
+    }
+
+    /// Peso de `votante` en la propuesta `id` (0 si no está en el padrón)
+    pub fn get_peso(env: Env, id: u32, votante: Address) -> i128 {
+        Self::leer(&env, &DataKey::Peso(id, votante)).unwrap_or(0)
+    }
+
+    /// Vota en una propuesta abierta con el peso asignado (una vez por dirección)
+    pub fn votar(env: Env, votante: Address, id: u32, opcion: OpcionVoto) -> Result<(), Error> {
+        votante.require_auth();
+
+        let mut propuesta = Self::get_propuesta(env.clone(), id)?;
+
+        if propuesta.estado != EstadoPropuesta::Abierta
+            || env.ledger().timestamp() >= propuesta.cierre
+        {
+            return Err(Error::VotacionCerrada);
+        }
+
+        let key_voto = DataKey::Voto(id, votante.clone());
+        if env.storage().persistent().has(&key_voto) {
+            return Err(Error::YaVoto);
+        }
+
+        let peso = Self::get_peso(env.clone(), id, votante.clone());
+        if peso <= 0 {
+            return Err(Error::SinPeso);
+        }
+
+        match opcion {
+            OpcionVoto::AFavor => propuesta.votos_si += peso,
+            OpcionVoto::EnContra => propuesta.votos_no += peso,
+            OpcionVoto::Abstencion => propuesta.abstenciones += peso,
+        }
+        Self::guardar_propuesta(&env, &propuesta);
+
+        env.storage().persistent().set(&key_voto, &opcion);
+        Self::extender(&env, &key_voto);
+
+        env.events().publish(
+            (symbol_short!("prop_voto"), id),
+            (votante, opcion, peso),
+        );
+
+        Ok(())
+    }
+
+    /// Cierra la votación: pasa a Aprobada o Rechazada según sus reglas
+    ///
+    /// Cualquiera puede llamarla después del cierre.
+    pub fn finalizar(env: Env, id: u32) -> Result<EstadoPropuesta, Error> {
+        let mut propuesta = Self::get_propuesta(env.clone(), id)?;
+
+        if propuesta.estado != EstadoPropuesta::Abierta {
+            return Err(Error::YaFinalizada);
+        }
+
+        if env.ledger().timestamp() < propuesta.cierre {
+            return Err(Error::VotacionAbierta);
+        }
+
+        let estado = if propuesta.paso(&env) {
+            EstadoPropuesta::Aprobada
+        } else {
+            EstadoPropuesta::Rechazada
+        };
+        Self::cambiar_estado(&env, &mut propuesta, estado);
+
+        env.events().publish(
+            (symbol_short!("prop_fin"), id),
+            estado,
+        );
+
+        Ok(estado)
+    }
+
+    /// El creador registra que la propuesta aprobada ya se llevó a cabo
+    pub fn marcar_ejecutada(env: Env, id: u32) -> Result<(), Error> {
+        let mut propuesta = Self::get_propuesta(env.clone(), id)?;
+        propuesta.creador.require_auth();
+
+        if propuesta.estado != EstadoPropuesta::Aprobada {
+            return Err(Error::NoAprobada);
+        }
+
+        Self::cambiar_estado(&env, &mut propuesta, EstadoPropuesta::Ejecutada);
+
+        env.events().publish(
+            (symbol_short!("prop_ejec"), id),
+            propuesta.titulo,
+        );
+
+        Ok(())
+    }
+
+    /// Obtiene una propuesta
+    pub fn get_propuesta(env: Env, id: u32) -> Result<PropuestaRegistrada, Error> {
+        Self::extender_instancia(&env);
+        Self::leer(&env, &DataKey::Propuesta(id)).ok_or(Error::PropuestaNoExiste)
+    }
+
+    /// Cantidad de propuestas creadas
+    pub fn get_total_propuestas(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalPropuestas)
+            .unwrap_or(0)
+    }
+
+    /// Opción votada por `votante` (None si no votó)
+    pub fn get_voto(env: Env, id: u32, votante: Address) -> Option<OpcionVoto> {
+        Self::leer(&env, &DataKey::Voto(id, votante))
+    }
+
+    /// Cantidad de propuestas en `estado`
+    pub fn contar_por_estado(env: Env, estado: EstadoPropuesta) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalEstado(estado))
+            .unwrap_or(0)
+    }
+
+    /// Propuestas que pasaron (aprobadas, ejecutadas o no)
+    ///
+    /// Las abiertas cuentan recién después de `finalizar`.
+    pub fn contar_aprobadas(env: Env) -> u32 {
+        Self::contar_por_estado(env.clone(), EstadoPropuesta::Aprobada)
+            + Self::contar_por_estado(env, EstadoPropuesta::Ejecutada)
+    }
+
+    /// Lista propuestas en `estado`, paginado
+    ///
+    /// Empezar con `cursor = 0` y seguir con `siguiente` hasta que
+    /// sea `None`. `limite` va de 1 a `MAX_PAGINA`. Al cambiar de
+    /// estado, una propuesta sale de su índice y la última ocupa su
+    /// lugar: el orden no es de creación y puede cambiar entre
+    /// páginas si hay finalizaciones en el medio.
+    pub fn listar_por_estado(
+        env: Env,
+        estado: EstadoPropuesta,
+        cursor: u32,
+        limite: u32,
+    ) -> Result<PaginaPropuestas, Error> {
+        if limite == 0 || limite > MAX_PAGINA {
+            return Err(Error::PaginaInvalida);
+        }
+
+        let total = Self::contar_por_estado(env.clone(), estado);
+        let fin = total.min(cursor.saturating_add(limite));
+
+        let mut propuestas = Vec::new(&env);
+        for posicion in cursor..fin {
+            let id: u32 = Self::leer(&env, &DataKey::IndiceEstado(estado, posicion))
+                .ok_or(Error::PropuestaNoExiste)?;
+            propuestas.push_back(Self::get_propuesta(env.clone(), id)?);
+        }
+
+        let siguiente = if fin < total { Some(fin) } else { None };
+
+        Ok(PaginaPropuestas { propuestas, siguiente })
+    }
+}
+
+// Helpers privados
+impl RegistroPropuestas {
+    fn guardar_propuesta(env: &Env, propuesta: &PropuestaRegistrada) {
+        let key = DataKey::Propuesta(propuesta.id);
+        env.storage().persistent().set(&key, propuesta);
+        Self::extender(env, &key);
+    }
+
+    /// Agrega `id` al final del índice de `estado`
+    fn agregar_a_indice(env: &Env, estado: EstadoPropuesta, id: u32) {
+        let total = Self::contar_por_estado(env.clone(), estado);
+
+        let key_indice = DataKey::IndiceEstado(estado, total);
+        env.storage().persistent().set(&key_indice, &id);
+        Self::extender(env, &key_indice);
+
+        let key_posicion = DataKey::PosicionEstado(id);
+        env.storage().persistent().set(&key_posicion, &total);
+        Self::extender(env, &key_posicion);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalEstado(estado), &(total + 1));
+    }
+
+    /// Saca `id` del índice de `estado` moviendo el último a su lugar
+    fn quitar_de_indice(env: &Env, estado: EstadoPropuesta, id: u32) {
+        let total = Self::contar_por_estado(env.clone(), estado);
+        let ultima = total - 1;
+
+        let posicion: u32 = env.storage()
+            .persistent()
+            .get(&DataKey::PosicionEstado(id))
+            .unwrap();
+
+        if posicion != ultima {
+            let id_ultima: u32 = env.storage()
+                .persistent()
+                .get(&DataKey::IndiceEstado(estado, ultima))
+                .unwrap();
+            let key_indice = DataKey::IndiceEstado(estado, posicion);
+            env.storage().persistent().set(&key_indice, &id_ultima);
+            Self::extender(env, &key_indice);
+            let key_posicion = DataKey::PosicionEstado(id_ultima);
+            env.storage().persistent().set(&key_posicion, &posicion);
+            Self::extender(env, &key_posicion);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::IndiceEstado(estado, ultima));
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalEstado(estado), &ultima);
+    }
+
+    fn cambiar_estado(env: &Env, propuesta: &mut PropuestaRegistrada, estado: EstadoPropuesta) {
+        Self::quitar_de_indice(env, propuesta.estado, propuesta.id);
+        Self::agregar_a_indice(env, estado, propuesta.id);
+
+        propuesta.estado = estado;
+        Self::guardar_propuesta(env, propuesta);
+    }
+
+    /// Lee una entrada de Persistent Storage y, si está, la extiende
+    fn leer<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Option<V> {
+        let valor = env.storage().persistent().get(key);
+        if valor.is_some() {
+            Self::extender(env, key);
+        }
+        valor
+    }
+
+    /// Extiende una entrada a `TTL_CHECKPOINT` (o al máximo de la red)
+    /// cuando le queda menos de la mitad
+    fn extender(env: &Env, key: &DataKey) {
+        let ttl = TTL_CHECKPOINT.min(env.storage().max_ttl());
+        env.storage().persistent().extend_ttl(key, ttl / 2, ttl);
+    }
+
+    /// Igual que `extender`, para la instancia (totales por estado)
+    fn extender_instancia(env: &Env) {
+        let ttl = TTL_CHECKPOINT.min(env.storage().max_ttl());
+        env.storage().instance().extend_ttl(ttl / 2, ttl);
+    }
+}
+
+// Una propuesta guardada también es Votable: sirve con
+// `traits_ejemplos::contar_aprobadas` sobre una página leída
+impl Votable for PropuestaRegistrada {
+    fn votos_a_favor(&self, _env: &Env) -> i128 {
+        self.votos_si
+    }
+
+    fn votos_en_contra(&self, _env: &Env) -> i128 {
+        self.votos_no
+    }
+
+    fn abstenciones(&self, _env: &Env) -> i128 {
+        self.abstenciones
+    }
+
+    fn quorum(&self, _env: &Env) -> i128 {
+        self.reglas.quorum
+    }
+
+    fn umbral_bps(&self, _env: &Env) -> u32 {
+        self.reglas.umbral_bps
+    }
+}
@@ -0,0 +1,230 @@
+#![cfg(test)]
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+    Env,
+    symbol_short,
+};
+use crate::checkpoints::TTL_CHECKPOINT;
+use crate::registro_propuestas::{
+    Error,
+    EstadoPropuesta,
+    RegistroPropuestas,
+    RegistroPropuestasClient,
+};
+use crate::traits_ejemplos::{
+    contar_aprobadas,
+    OpcionVoto,
+    ReglasVotacion,
+};
+
+fn setup<'a>(env: &Env) -> RegistroPropuestasClient<'a> {
+    let contract_id = env.register_contract(None, RegistroPropuestas);
+    RegistroPropuestasClient::new(env, &contract_id)
+}
+
+fn mayoria_simple() -> ReglasVotacion {
+    ReglasVotacion { quorum: 0, umbral_bps: 5_000 }
+}
+
+/// Votante nuevo con peso 1 en la propuesta `id`
+fn votante(env: &Env, client: &RegistroPropuestasClient, id: u32) -> Address {
+    let votante = Address::generate(env);
+    client.asignar_peso(&id, &votante, &1);
+    votante
+}
+
+/// Emite `si` votos a favor y `no` en contra con votantes nuevos
+fn votar(env: &Env, client: &RegistroPropuestasClient, id: u32, si: u32, no: u32) {
+    for _ in 0..si {
+        client.votar(&votante(env, client, id), &id, &OpcionVoto::AFavor);
+    }
+    for _ in 0..no {
+        client.votar(&votante(env, client, id), &id, &OpcionVoto::EnContra);
+    }
+}
+
+#[test]
+fn test_crear_y_finalizar() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let id = client.crear_propuesta(&creador, &symbol_short!("PARQUE"), &100, &mayoria_simple());
+
+    let propuesta = client.get_propuesta(&id);
+    assert_eq!(propuesta.estado, EstadoPropuesta::Abierta);
+    assert_eq!(propuesta.creada, 1_000);
+    assert_eq!(propuesta.cierre, 1_100);
+
+    votar(&env, &client, id, 2, 1);
+
+    // Un voto por dirección
+    let abstencion = votante(&env, &client, id);
+    client.votar(&abstencion, &id, &OpcionVoto::Abstencion);
+    assert_eq!(
+        client.try_votar(&abstencion, &id, &OpcionVoto::AFavor),
+        Err(Ok(Error::YaVoto))
+    );
+
+    assert_eq!(client.try_finalizar(&id), Err(Ok(Error::VotacionAbierta)));
+
+    let tarde = votante(&env, &client, id);
+    env.ledger().with_mut(|li| li.timestamp = 1_100);
+    assert_eq!(
+        client.try_votar(&tarde, &id, &OpcionVoto::AFavor),
+        Err(Ok(Error::VotacionCerrada))
+    );
+
+    assert_eq!(client.finalizar(&id), EstadoPropuesta::Aprobada);
+    assert_eq!(client.try_finalizar(&id), Err(Ok(Error::YaFinalizada)));
+
+    // Solo el creador marca la ejecución
+    client.marcar_ejecutada(&id);
+    assert_eq!(env.auths()[0].0, creador);
+    assert_eq!(client.get_propuesta(&id).estado, EstadoPropuesta::Ejecutada);
+    assert_eq!(client.try_marcar_ejecutada(&id), Err(Ok(Error::NoAprobada)));
+}
+
+#[test]
+fn test_padron_con_peso() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+    let id = client.crear_propuesta(&creador, &symbol_short!("PARQUE"), &100, &mayoria_simple());
+
+    // Una dirección nueva no vota
+    let cualquiera = Address::generate(&env);
+    assert_eq!(
+        client.try_votar(&cualquiera, &id, &OpcionVoto::AFavor),
+        Err(Ok(Error::SinPeso))
+    );
+
+    // Solo el creador arma el padrón
+    let [ana, bea, caro] = [0; 3].map(|_| Address::generate(&env));
+    client.asignar_peso(&id, &ana, &5);
+    assert_eq!(env.auths()[0].0, creador);
+    client.asignar_peso(&id, &bea, &2);
+    client.asignar_peso(&id, &caro, &2);
+    assert_eq!(client.try_asignar_peso(&id, &ana, &-1), Err(Ok(Error::SinPeso)));
+
+    // Los votos cuentan con su peso
+    client.votar(&ana, &id, &OpcionVoto::EnContra);
+    client.votar(&bea, &id, &OpcionVoto::AFavor);
+    client.votar(&caro, &id, &OpcionVoto::AFavor);
+    let propuesta = client.get_propuesta(&id);
+    assert_eq!(propuesta.votos_si, 4);
+    assert_eq!(propuesta.votos_no, 5);
+
+    // El peso no cambia después de votar
+    assert_eq!(client.try_asignar_peso(&id, &bea, &10), Err(Ok(Error::YaVoto)));
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(
+        client.try_asignar_peso(&id, &cualquiera, &1),
+        Err(Ok(Error::VotacionCerrada))
+    );
+    assert_eq!(client.finalizar(&id), EstadoPropuesta::Rechazada);
+}
+
+#[test]
+fn test_contar_aprobadas_on_chain() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+
+    // 0: pasa, 1: no pasa, 2: pasa y se ejecuta, 3: no alcanza el quórum
+    let ids = [0u32, 1, 2, 3].map(|_| {
+        client.crear_propuesta(&creador, &symbol_short!("LEY"), &100, &mayoria_simple())
+    });
+    let con_quorum = client.crear_propuesta(
+        &creador,
+        &symbol_short!("LEY"),
+        &100,
+        &ReglasVotacion { quorum: 5, umbral_bps: 5_000 },
+    );
+    votar(&env, &client, ids[0], 3, 1);
+    votar(&env, &client, ids[1], 1, 3);
+    votar(&env, &client, ids[2], 2, 0);
+    votar(&env, &client, con_quorum, 2, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    for id in [ids[0], ids[1], ids[2], con_quorum] {
+        client.finalizar(&id);
+    }
+    client.marcar_ejecutada(&ids[2]);
+
+    assert_eq!(client.contar_aprobadas(), 2);
+    assert_eq!(client.contar_por_estado(&EstadoPropuesta::Rechazada), 2);
+    assert_eq!(client.contar_por_estado(&EstadoPropuesta::Abierta), 1);
+
+    // Mismo resultado con la función genérica sobre lo guardado
+    let guardadas = [ids[0], ids[1], ids[2], con_quorum].map(|id| client.get_propuesta(&id));
+    assert_eq!(contar_aprobadas(&guardadas, &env), 2);
+}
+
+#[test]
+fn test_listar_por_estado() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+
+    for _ in 0..5 {
+        client.crear_propuesta(&creador, &symbol_short!("LEY"), &100, &mayoria_simple());
+    }
+
+    let pagina = client.listar_por_estado(&EstadoPropuesta::Abierta, &0, &2);
+    assert_eq!(pagina.propuestas.len(), 2);
+    assert_eq!(pagina.siguiente, Some(2));
+
+    let ultima = client.listar_por_estado(&EstadoPropuesta::Abierta, &4, &2);
+    assert_eq!(ultima.propuestas.len(), 1);
+    assert_eq!(ultima.siguiente, None);
+
+    // Rechazar la 1 (sin votos): sale de Abierta y la última ocupa su lugar
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.finalizar(&1);
+
+    let abiertas = client.listar_por_estado(&EstadoPropuesta::Abierta, &0, &10);
+    let ids: [u32; 4] = core::array::from_fn(|i| abiertas.propuestas.get_unchecked(i as u32).id);
+    assert_eq!(ids, [0, 4, 2, 3]);
+
+    let rechazadas = client.listar_por_estado(&EstadoPropuesta::Rechazada, &0, &10);
+    assert_eq!(rechazadas.propuestas.get_unchecked(0).id, 1);
+
+    assert_eq!(
+        client.try_listar_por_estado(&EstadoPropuesta::Abierta, &0, &0),
+        Err(Ok(Error::PaginaInvalida))
+    );
+    assert_eq!(client.try_get_propuesta(&9), Err(Ok(Error::PropuestaNoExiste)));
+}
+
+#[test]
+fn test_registro_no_expira_si_se_consulta() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    let id = client.crear_propuesta(&creador, &symbol_short!("PARQUE"), &100, &mayoria_simple());
+    let ana = votante(&env, &client, id);
+    client.votar(&ana, &id, &OpcionVoto::AFavor);
+
+    // Mucho más allá de un TTL corto: la propuesta y su índice siguen ahí
+    env.ledger().with_mut(|li| li.sequence_number = 10 + TTL_CHECKPOINT * 3 / 4);
+    let pagina = client.listar_por_estado(&EstadoPropuesta::Abierta, &0, &10);
+    assert_eq!(pagina.propuestas.len(), 1);
+    assert_eq!(client.get_voto(&id, &ana), Some(OpcionVoto::AFavor));
+
+    // La lectura las extendió: pasan el vencimiento original
+    env.ledger().with_mut(|li| li.sequence_number = 10 + TTL_CHECKPOINT * 3 / 2);
+    let pagina = client.listar_por_estado(&EstadoPropuesta::Abierta, &0, &10);
+    assert_eq!(pagina.propuestas.get(0).unwrap().votos_si, 1);
+    assert_eq!(client.get_voto(&id, &ana), Some(OpcionVoto::AFavor));
+}
@@ -0,0 +1,67 @@
+#![no_std]
+use soroban_sdk::{
+    contracttype,
+    Env,
+    Address,
+    symbol_short,
+};
+use crate::storage_patterns::GestionUsuarioClient;
+
+// ============================================================
+// VOTANTES REGISTRADOS (REUTILIZABLE)
+// ============================================================
+//
+// Funciones para que un contrato de votación acepte solo votantes
+// dados de alta en un registro de usuarios (`GestionUsuario`):
+//
+//   ownable::require_owner(&env)?;                  // admin
+//   registro_votantes::configurar(&env, Some(registro));
+//
+//   if !registro_votantes::esta_habilitado(&env, &votante) {
+//       return Err(Error::VotanteNoRegistrado);
+//   }
+//
+// Sin registro configurado vota cualquiera (comportamiento anterior).
+
+/// Storage keys del registro de votantes (Instance Storage)
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKeyRegistroVotantes {
+    Registro,       // Address del contrato GestionUsuario
+}
+
+// ============================================================
+// FUNCIONES
+// ============================================================
+
+/// Configura el contrato registro (None = sin restricción)
+///
+/// La verificación de permisos es de quien llama.
+pub fn configurar(env: &Env, registro: Option<Address>) {
+    match &registro {
+        Some(registro) => env.storage()
+            .instance()
+            .set(&DataKeyRegistroVotantes::Registro, registro),
+        None => env.storage()
+            .instance()
+            .remove(&DataKeyRegistroVotantes::Registro),
+    }
+
+    env.events().publish((symbol_short!("registro"),), registro);
+}
+
+/// Contrato registro configurado
+pub fn registro(env: &Env) -> Option<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKeyRegistroVotantes::Registro)
+}
+
+/// ¿Puede votar? Consulta `usuario_existe` en el registro (llamada
+/// entre contratos); sin registro configurado, siempre true
+pub fn esta_habilitado(env: &Env, votante: &Address) -> bool {
+    match registro(env) {
+        Some(registro) => GestionUsuarioClient::new(env, &registro).usuario_existe(votante),
+        None => true,
+    }
+}
@@ -0,0 +1,421 @@
+#![no_std]
+use soroban_sdk::{
+    contract,
+    contractimpl,
+    contracterror,
+    contracttype,
+    Env,
+    Address,
+    symbol_short,
+};
+use crate::ejercicios_practica::VotoReputacion;
+
+// ============================================================
+// REPUTACIÓN PONDERADA CON DECAIMIENTO
+// ============================================================
+//
+// Alternativa a `ReputationContract` (likes - dislikes en i32):
+// - Las entidades son direcciones, así cada votante tiene su propia
+//   reputación
+// - Cada voto guarda su timestamp y el peso del votante al votar:
+//   peso = reputación del votante, acotada a [PESO_MINIMO, tope]
+// - El tope de cada votante lo asigna el admin (por defecto
+//   PESO_MINIMO): una cuenta nueva, como un sock-puppet, vota con el
+//   mínimo aunque otras cuentas nuevas le den reputación entre ellas
+// - Los votos pierden la mitad de su valor cada `vida_media` segundos
+//
+// Los puntajes se guardan ya sumados por entidad (positivos y
+// negativos por separado) con la fecha de la última actualización.
+// Como el decaimiento exponencial no tiene memoria
+// (2^-(a+b) = 2^-a · 2^-b), basta decaer la suma completa:
+// consultar no recorre los votos. `decaer` calcula 2^-x en punto
+// fijo, así que decaer en varios pasos da lo mismo que de una vez,
+// salvo el truncado a la unidad de ESCALA en cada paso.
+
+// ============================================================
+// DEFINICIÓN DE ERRORES PERSONALIZADOS
+// ============================================================
+
+/// Errores de la reputación ponderada
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    YaInicializado = 1,
+    NoInicializado = 2,
+    VidaMediaInvalida = 3,
+    YaVoto = 4,
+    SinVoto = 5,
+    AutoVoto = 6,
+    TopeInvalido = 7,
+}
+
+/// Escala de puntos fijos: 1_000 = un voto de peso 1
+pub const ESCALA: i128 = 1_000;
+
+/// Peso de un votante sin reputación (0,1 votos)
+pub const PESO_MINIMO: i128 = ESCALA / 10;
+
+/// Peso máximo de un votante (10 votos)
+pub const PESO_MAXIMO: i128 = 10 * ESCALA;
+
+/// Tope de peso de un votante que el admin no habilitó
+pub const TOPE_POR_DEFECTO: i128 = PESO_MINIMO;
+
+// ============================================================
+// TIPOS Y STORAGE
+// ============================================================
+
+/// Voto guardado (Persistent Storage)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VotoPonderado {
+    pub voto: VotoReputacion,
+    pub peso: i128,             // Peso del votante al votar (en ESCALA)
+    pub timestamp: u64,
+}
+
+/// Sumas de una entidad (Persistent Storage)
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EstadoEntidad {
+    pub likes: u32,
+    pub dislikes: u32,
+    pub positivo: i128,         // Pesos de likes, decaídos hasta `actualizado`
+    pub negativo: i128,         // Pesos de dislikes, decaídos hasta `actualizado`
+    pub actualizado: u64,
+}
+
+/// Resultado de `get_score`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Puntaje {
+    pub likes: u32,
+    pub dislikes: u32,
+    pub crudo: i64,             // likes - dislikes, como ReputationContract
+    pub decaido: i128,          // Pesos decaídos a hoy (en ESCALA)
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    // Instance
+    Admin,
+    VidaMedia,
+
+    // Persistent
+    Entidad(Address),
+    Voto(Address, Address),     // (entidad, votante)
+    TopePeso(Address),          // Tope de peso asignado por el admin
+}
+
+// ============================================================
+// CONTRATO
+// ============================================================
+
+/// Reputación entre direcciones, ponderada por reputación y con decaimiento
+#[contract]
+pub struct ReputacionPonderada;
+
+#[contractimpl]
+impl ReputacionPonderada {
+    /// Inicializa con un admin y la vida media de los votos (segundos)
+    pub fn initialize(env: Env, admin: Address, vida_media: u64) -> Result<(), Error> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::YaInicializado);
+        }
+
+        if vida_media == 0 {
+            return Err(Error::VidaMediaInvalida);
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::VidaMedia, &vida_media);
+        env.storage().instance().extend_ttl(100, 100);
+
+        Ok(())
+    }
+
+    /// Admin: cambia la vida media
+    ///
+    /// La nueva vida media aplica hacia atrás hasta la última
+    /// actualización de cada entidad (y, al retirar un voto, desde que
+    /// se emitió): no se recorren las entidades para decaerlas antes del
+    /// cambio.
+    pub fn set_half_life(env: Env, vida_media: u64) -> Result<(), Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NoInicializado)?;
+        admin.require_auth();
+
+        if vida_media == 0 {
+            return Err(Error::VidaMediaInvalida);
+        }
+
+        env.storage().instance().set(&DataKey::VidaMedia, &vida_media);
+
+        env.events().publish((symbol_short!("vida_med"),), vida_media);
+
+        Ok(())
+    }
+
+    /// Vida media configurada (segundos)
+    pub fn get_half_life(env: Env) -> Result<u64, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::VidaMedia)
+            .ok_or(Error::NoInicializado)
+    }
+
+    /// `votante` da like a `entidad` con su peso actual
+    pub fn like(env: Env, entidad: Address, votante: Address) -> Result<(), Error> {
+        Self::votar(env, entidad, votante, VotoReputacion::Like)
+    }
+
+    /// `votante` da dislike a `entidad` con su peso actual
+    pub fn dislike(env: Env, entidad: Address, votante: Address) -> Result<(), Error> {
+        Self::votar(env, entidad, votante, VotoReputacion::Dislike)
+    }
+
+    /// Retira el voto: se descuenta lo que el voto vale hoy
+    pub fn retract_vote(env: Env, entidad: Address, votante: Address) -> Result<(), Error> {
+        votante.require_auth();
+
+        let key_voto = DataKey::Voto(entidad.clone(), votante.clone());
+        let voto: VotoPonderado = env.storage()
+            .persistent()
+            .get(&key_voto)
+            .ok_or(Error::SinVoto)?;
+
+        let mut estado = Self::estado_actual(&env, &entidad)?;
+        Self::descontar(&env, &mut estado, &voto)?;
+        Self::guardar_estado(&env, &entidad, &estado);
+        env.storage().persistent().remove(&key_voto);
+
+        env.events().publish(
+            (symbol_short!("retract"), entidad),
+            votante,
+        );
+
+        Ok(())
+    }
+
+    /// Puntaje crudo y decaído de `entidad`
+    pub fn get_score(env: Env, entidad: Address) -> Result<Puntaje, Error> {
+        let estado = Self::estado_actual(&env, &entidad)?;
+
+        Ok(Puntaje {
+            likes: estado.likes,
+            dislikes: estado.dislikes,
+            crudo: estado.likes as i64 - estado.dislikes as i64,
+            decaido: estado.positivo - estado.negativo,
+        })
+    }
+
+    /// Admin: tope de peso de `votante` (en ESCALA)
+    ///
+    /// La reputación sola no alcanza para votar con más peso: la pueden
+    /// fabricar cuentas que se votan entre ellas. El tope lo asigna el
+    /// admin, entre PESO_MINIMO y PESO_MAXIMO.
+    pub fn set_max_weight(env: Env, votante: Address, tope: i128) -> Result<(), Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NoInicializado)?;
+        admin.require_auth();
+
+        if !(PESO_MINIMO..=PESO_MAXIMO).contains(&tope) {
+            return Err(Error::TopeInvalido);
+        }
+
+        let key = DataKey::TopePeso(votante.clone());
+        env.storage().persistent().set(&key, &tope);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+
+        env.events().publish((symbol_short!("tope"), votante), tope);
+
+        Ok(())
+    }
+
+    /// Tope de peso de `votante` (TOPE_POR_DEFECTO si el admin no le asignó)
+    pub fn get_max_weight(env: Env, votante: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TopePeso(votante))
+            .unwrap_or(TOPE_POR_DEFECTO)
+    }
+
+    /// Peso con el que votaría `votante` ahora (en ESCALA)
+    ///
+    /// Su reputación, acotada a [PESO_MINIMO, tope].
+    pub fn get_weight(env: Env, votante: Address) -> Result<i128, Error> {
+        let tope = Self::get_max_weight(env.clone(), votante.clone());
+        let reputacion = Self::get_score(env, votante)?.decaido;
+        Ok(reputacion.clamp(PESO_MINIMO, tope))
+    }
+
+    /// Voto de `votante` sobre `entidad` (None si no votó)
+    pub fn get_vote(env: Env, entidad: Address, votante: Address) -> Option<VotoPonderado> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Voto(entidad, votante))
+    }
+}
+
+// Helpers privados
+impl ReputacionPonderada {
+    /// Registra o cambia el voto de `votante`
+    fn votar(env: Env, entidad: Address, votante: Address, voto: VotoReputacion) -> Result<(), Error> {
+        votante.require_auth();
+
+        if entidad == votante {
+            return Err(Error::AutoVoto);
+        }
+
+        let key_voto = DataKey::Voto(entidad.clone(), votante.clone());
+        let anterior: Option<VotoPonderado> = env.storage().persistent().get(&key_voto);
+
+        if anterior.as_ref().map(|v| v.voto) == Some(voto) {
+            return Err(Error::YaVoto);
+        }
+
+        let mut estado = Self::estado_actual(&env, &entidad)?;
+
+        // Cambiar de voto: primero sale el anterior
+        if let Some(anterior) = anterior {
+            Self::descontar(&env, &mut estado, &anterior)?;
+        }
+
+        let peso = Self::get_weight(env.clone(), votante.clone())?;
+        match voto {
+            VotoReputacion::Like => {
+                estado.likes += 1;
+                estado.positivo += peso;
+            }
+            VotoReputacion::Dislike => {
+                estado.dislikes += 1;
+                estado.negativo += peso;
+            }
+        }
+        Self::guardar_estado(&env, &entidad, &estado);
+
+        let nuevo = VotoPonderado {
+            voto,
+            peso,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key_voto, &nuevo);
+        env.storage().persistent().extend_ttl(&key_voto, 100, 100);
+
+        let topic = match voto {
+            VotoReputacion::Like => symbol_short!("like"),
+            VotoReputacion::Dislike => symbol_short!("dislike"),
+        };
+        env.events().publish(
+            (topic, entidad),
+            (votante, peso),
+        );
+
+        Ok(())
+    }
+
+    /// Estado de `entidad` con las sumas decaídas hasta ahora
+    fn estado_actual(env: &Env, entidad: &Address) -> Result<EstadoEntidad, Error> {
+        let vida_media = Self::get_half_life(env.clone())?;
+        let ahora = env.ledger().timestamp();
+
+        let mut estado: EstadoEntidad = env.storage()
+            .persistent()
+            .get(&DataKey::Entidad(entidad.clone()))
+            .unwrap_or_default();
+
+        let transcurrido = ahora.saturating_sub(estado.actualizado);
+        estado.positivo = decaer(estado.positivo, transcurrido, vida_media);
+        estado.negativo = decaer(estado.negativo, transcurrido, vida_media);
+        estado.actualizado = ahora;
+
+        Ok(estado)
+    }
+
+    /// Quita un voto de `estado` con su valor decaído a hoy
+    fn descontar(env: &Env, estado: &mut EstadoEntidad, voto: &VotoPonderado) -> Result<(), Error> {
+        let vida_media = Self::get_half_life(env.clone())?;
+        let transcurrido = env.ledger().timestamp().saturating_sub(voto.timestamp);
+        let valor = decaer(voto.peso, transcurrido, vida_media);
+
+        // Cada actualización trunca la suma, así que puede quedar unas
+        // unidades de ESCALA por debajo de sus votos: el último voto en
+        // salir se lleva el resto y ninguna suma queda negativa
+        match voto.voto {
+            VotoReputacion::Like => {
+                estado.likes -= 1;
+                estado.positivo = if estado.likes == 0 {
+                    0
+                } else {
+                    (estado.positivo - valor).max(0)
+                };
+            }
+            VotoReputacion::Dislike => {
+                estado.dislikes -= 1;
+                estado.negativo = if estado.dislikes == 0 {
+                    0
+                } else {
+                    (estado.negativo - valor).max(0)
+                };
+            }
+        }
+
+        Ok(())
+    }
+
+    fn guardar_estado(env: &Env, entidad: &Address, estado: &EstadoEntidad) {
+        let key = DataKey::Entidad(entidad.clone());
+        env.storage().persistent().set(&key, estado);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+    }
+}
+
+/// `2^(-2^-k)` en Q64 para k = 1..=32: `2^(-1/2)`, `2^(-1/4)`, ...
+const RAICES_DE_MEDIO: [u128; 32] = [
+    0xb504f333f9de6484, 0xd744fccad69d6af4, 0xeac0c6e7dd24392e, 0xf5257d152486cc2c,
+    0xfa83b2db722a033a, 0xfd3e0c0cf486c174, 0xfe9e115c7b8f884b, 0xff4ecb59511ec8a5,
+    0xffa756521c8daed1, 0xffd3a751c0f7e10b, 0xffe9d2b2f7db2755, 0xfff4e91bff1b8c3d,
+    0xfffa747ea0040664, 0xfffd3a3b7814eb53, 0xfffe9d1cc60ddab1, 0xffff4e8e25879bfa,
+    0xffffa7470363f451, 0xffffd3a37dda0313, 0xffffe9d1bdf703ae, 0xfffff4e8debe025e,
+    0xfffffa746f4fa150, 0xfffffd3a37a3f8b0, 0xfffffe9d1bd1065a, 0xffffff4e8de845ad,
+    0xffffffa746f41376, 0xffffffd3a37a05e3, 0xffffffe9d1bd01fb, 0xfffffff4e8de80c0,
+    0xfffffffa746f4050, 0xfffffffd3a37a024, 0xfffffffe9d1bd011, 0xffffffff4e8de808,
+];
+
+/// `valor · 2^(-transcurrido / vida_media)` sin punto flotante
+///
+/// Cada vida media completa divide por 2 (desplazamiento). La
+/// fracción que sobra, `f`, se toma con 32 bits y `2^-f` es el
+/// producto de las raíces de `RAICES_DE_MEDIO` de los bits en 1.
+/// El error relativo es menor a 2^-32; el resultado se trunca.
+/// `valor` no puede ser negativo (son sumas de pesos).
+fn decaer(valor: i128, transcurrido: u64, vida_media: u64) -> i128 {
+    let mitades = transcurrido / vida_media;
+    if mitades >= 127 {
+        return 0;
+    }
+
+    let valor = (valor.max(0) >> mitades) as u128;
+    let fraccion = ((transcurrido % vida_media) as u128) << 32;
+    let fraccion = fraccion / vida_media as u128;
+
+    let mut factor: u128 = 1 << 64;
+    for (i, raiz) in RAICES_DE_MEDIO.iter().enumerate() {
+        if fraccion & (1 << (31 - i)) != 0 {
+            factor = (factor * raiz) >> 64;
+        }
+    }
+
+    // valor · factor / 2^64 sin desbordar: parte alta y baja por separado
+    let alto = (valor >> 64) * factor;
+    let bajo = ((valor & u64::MAX as u128) * factor) >> 64;
+    (alto + bajo) as i128
+}
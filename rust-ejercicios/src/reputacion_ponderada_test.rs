@@ -0,0 +1,209 @@
+#![cfg(test)]
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+    Env,
+    Vec,
+};
+use crate::ejercicios_practica::VotoReputacion;
+use crate::reputacion_ponderada::{
+    Error,
+    ReputacionPonderada,
+    ReputacionPonderadaClient,
+    ESCALA,
+    PESO_MAXIMO,
+    PESO_MINIMO,
+};
+
+/// Un día de vida media
+const DIA: u64 = 86_400;
+
+fn setup<'a>(env: &Env) -> (ReputacionPonderadaClient<'a>, Address) {
+    let contract_id = env.register_contract(None, ReputacionPonderada);
+    let client = ReputacionPonderadaClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin, &DIA);
+    (client, admin)
+}
+
+/// `cantidad` cuentas nuevas dan like a `entidad`
+fn likes_de_cuentas_nuevas(env: &Env, client: &ReputacionPonderadaClient, entidad: &Address, cantidad: u32) {
+    for _ in 0..cantidad {
+        client.like(entidad, &Address::generate(env));
+    }
+}
+
+#[test]
+fn test_puntaje_crudo_y_ponderado() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    assert_eq!(env.auths()[0].0, admin);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+
+    // Una cuenta nueva vota con el peso mínimo
+    let nueva = Address::generate(&env);
+    assert_eq!(client.get_weight(&nueva), PESO_MINIMO);
+    client.like(&bea, &nueva);
+    assert_eq!(env.auths()[0].0, nueva);
+
+    // 10 cuentas nuevas le dan a ana reputación 1, pero sin tope
+    // asignado sigue votando con el mínimo
+    likes_de_cuentas_nuevas(&env, &client, &ana, 10);
+    assert_eq!(client.get_weight(&ana), PESO_MINIMO);
+
+    // El admin la habilita → vota con su reputación (peso 1)
+    client.set_max_weight(&ana, &PESO_MAXIMO);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.get_max_weight(&ana), PESO_MAXIMO);
+    assert_eq!(client.get_weight(&ana), ESCALA);
+    assert_eq!(client.try_set_max_weight(&ana, &(PESO_MAXIMO + 1)), Err(Ok(Error::TopeInvalido)));
+    assert_eq!(client.try_set_max_weight(&ana, &0), Err(Ok(Error::TopeInvalido)));
+
+    // Un like de ana vale lo que 10 cuentas nuevas
+    client.like(&bea, &ana);
+    let puntaje = client.get_score(&bea);
+    assert_eq!(puntaje.crudo, 2);
+    assert_eq!(puntaje.decaido, ESCALA + PESO_MINIMO);
+
+    let dislike = Address::generate(&env);
+    client.dislike(&bea, &dislike);
+    let puntaje = client.get_score(&bea);
+    assert_eq!((puntaje.likes, puntaje.dislikes, puntaje.crudo), (2, 1, 1));
+    assert_eq!(puntaje.decaido, ESCALA);
+
+    // El peso está acotado (200 invocaciones: sin límite de presupuesto)
+    env.budget().reset_unlimited();
+    likes_de_cuentas_nuevas(&env, &client, &ana, 200);
+    assert_eq!(client.get_weight(&ana), PESO_MAXIMO);
+}
+
+#[test]
+fn test_anillo_de_sock_puppets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+
+    // 11 cuentas nuevas que se dan like todas con todas, dos vueltas
+    // (la segunda retira y vuelve a votar para re-pesar los votos)
+    env.budget().reset_unlimited();
+    let mut anillo = Vec::new(&env);
+    for _ in 0..11 {
+        anillo.push_back(Address::generate(&env));
+    }
+    for vuelta in 0..2 {
+        for entidad in anillo.iter() {
+            for votante in anillo.iter().filter(|v| *v != entidad) {
+                if vuelta == 1 {
+                    client.retract_vote(&entidad, &votante);
+                }
+                client.like(&entidad, &votante);
+            }
+        }
+    }
+
+    // Ninguna sale del peso mínimo: 10 likes de peso mínimo
+    for cuenta in anillo.iter() {
+        assert_eq!(client.get_weight(&cuenta), PESO_MINIMO);
+        assert_eq!(client.get_score(&cuenta).decaido, 10 * PESO_MINIMO);
+    }
+}
+
+#[test]
+fn test_decaimiento_por_vida_media() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin) = setup(&env);
+    let ana = Address::generate(&env);
+
+    likes_de_cuentas_nuevas(&env, &client, &ana, 10);
+    assert_eq!(client.get_score(&ana).decaido, 1_000);
+
+    // Una vida media: la mitad; dos: un cuarto
+    env.ledger().with_mut(|li| li.timestamp = DIA);
+    assert_eq!(client.get_score(&ana).decaido, 500);
+    env.ledger().with_mut(|li| li.timestamp = 2 * DIA);
+    assert_eq!(client.get_score(&ana).decaido, 250);
+
+    // Entre medio también es exponencial: 1_000 · 2^-2,5 ≈ 176,8
+    env.ledger().with_mut(|li| li.timestamp = 2 * DIA + DIA / 2);
+    assert_eq!(client.get_score(&ana).decaido, 176);
+
+    // El crudo no decae
+    assert_eq!(client.get_score(&ana).crudo, 10);
+
+    // Un voto nuevo suma completo sobre lo decaído
+    client.like(&ana, &Address::generate(&env));
+    assert_eq!(client.get_score(&ana).decaido, 276);
+
+    // Cambiar la vida media lo firma el admin
+    client.set_half_life(&(2 * DIA));
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.try_set_half_life(&0), Err(Ok(Error::VidaMediaInvalida)));
+}
+
+#[test]
+fn test_decaimiento_en_varias_actualizaciones() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+    let votante = Address::generate(&env);
+
+    // Mismos likes para las dos
+    likes_de_cuentas_nuevas(&env, &client, &ana, 10);
+    likes_de_cuentas_nuevas(&env, &client, &bea, 10);
+
+    // La suma de ana se decae y se guarda cada tercio de vida media
+    // (un voto que entra, cambia y sale); la de bea, nunca
+    for paso in 1..=7u64 {
+        env.ledger().with_mut(|li| li.timestamp = paso * DIA / 3);
+        match paso {
+            1 => client.like(&ana, &votante),
+            7 => client.retract_vote(&ana, &votante),
+            _ if paso % 2 == 0 => client.dislike(&ana, &votante),
+            _ => client.like(&ana, &votante),
+        }
+    }
+
+    // 1_000 · 2^(-7/3) ≈ 198,4: de a pasos o de una vez da lo mismo,
+    // salvo el truncado (como mucho una unidad por paso)
+    let ana = client.get_score(&ana).decaido;
+    let bea = client.get_score(&bea).decaido;
+    assert_eq!(bea, 198);
+    assert!((ana - bea).abs() <= 7, "{ana} vs {bea}");
+}
+
+#[test]
+fn test_cambiar_y_retirar_voto() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env);
+    let ana = Address::generate(&env);
+    let votante = Address::generate(&env);
+
+    client.like(&ana, &votante);
+    assert_eq!(client.try_like(&ana, &votante), Err(Ok(Error::YaVoto)));
+    assert_eq!(client.try_like(&ana, &ana), Err(Ok(Error::AutoVoto)));
+
+    // Cambiar a dislike mueve el peso decaído de lado
+    env.ledger().with_mut(|li| li.timestamp = DIA);
+    client.dislike(&ana, &votante);
+    let puntaje = client.get_score(&ana);
+    assert_eq!((puntaje.likes, puntaje.dislikes), (0, 1));
+    assert_eq!(puntaje.decaido, -PESO_MINIMO);
+
+    let voto = client.get_vote(&ana, &votante).unwrap();
+    assert_eq!(voto.voto, VotoReputacion::Dislike);
+    assert_eq!(voto.timestamp, DIA);
+
+    // Retirar deja todo en cero
+    env.ledger().with_mut(|li| li.timestamp = 3 * DIA);
+    client.retract_vote(&ana, &votante);
+    let puntaje = client.get_score(&ana);
+    assert_eq!((puntaje.crudo, puntaje.decaido), (0, 0));
+    assert_eq!(client.get_vote(&ana, &votante), None);
+    assert_eq!(client.try_retract_vote(&ana, &votante), Err(Ok(Error::SinVoto)));
+}
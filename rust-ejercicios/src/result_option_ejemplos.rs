@@ -10,6 +10,16 @@ use soroban_sdk::{
     Error,
     symbol_short,
     contracttype,
+    token,
+    IntoVal,
+    TryFromVal,
+    Val,
+    Vec,
+};
+use crate::checkpoints::TTL_CHECKPOINT;
+use crate::ownable::{
+    self,
+    Error as OwnableError,
 };
 
 // ============================================================
@@ -31,6 +41,15 @@ pub enum Error {
     LimiteExcedido = 4,
     SolicitanteNoValida = 5,
     NoInicializado = 6,
+    PrestamoNoExiste = 7,
+    PrestamoPagado = 8,
+    EnIncumplimiento = 9,
+    TasaInvalida = 10,
+    PlazoInvalido = 11,
+    YaInicializado = 12,
+    SinOwner = 13,              // El owner renunció
+    LiquidezInsuficiente = 14,  // El contrato no tiene fondos para prestar
+    DemasiadosPrestamos = 15,   // Alcanzó MAX_PRESTAMOS_ACTIVOS
 }
 
 // ============================================================
@@ -285,11 +304,79 @@ impl OptionEjemplo {
 // EJEMPLO 4: Sistema de Préstamos con Option y Result
 // ============================================================
 
+/// Segundos en un año (las tasas son anuales)
+pub const SEGUNDOS_POR_ANIO: u64 = 365 * 24 * 60 * 60;
+
+/// Tasa anual máxima permitida (100%)
+pub const MAX_TASA_BPS: u32 = 10_000;
+
+/// Préstamos sin saldar que puede tener una solicitante a la vez
+/// 
+/// Acota lo que recorre `solicitar_prestamo` al buscar incumplimientos.
+pub const MAX_PRESTAMOS_ACTIVOS: u32 = 20;
+
+/// Estado de un préstamo
+/// 
+/// - Activo: dentro del plazo
+/// - Pagado: capital e interés saldados (estado final)
+/// - Vencido: pasó el vencimiento pero sigue en período de gracia
+/// - Incumplido: pasó el período de gracia sin saldar la deuda
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EstadoPrestamo {
+    Activo = 0,
+    Pagado = 1,
+    Vencido = 2,
+    Incumplido = 3,
+}
+
+/// Condiciones que se aplican a los préstamos nuevos
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CondicionesCredito {
+    pub tasa_bps: u32,      // Interés simple anual (500 = 5%)
+    pub plazo: u64,         // Segundos hasta el vencimiento
+    pub gracia: u64,        // Segundos después del vencimiento antes del incumplimiento
+}
+
+/// Registro de un préstamo
+/// 
+/// Los pagos cubren primero el interés devengado y después el capital.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Prestamo {
+    pub solicitante: Address,
+    pub principal: i128,            // Monto original
+    pub principal_pendiente: i128,
+    pub interes_pendiente: i128,    // Devengado y aún no pagado
+    pub pagado: i128,               // Total pagado (interés + capital)
+    pub tasa_bps: u32,
+    pub inicio: u64,                // Timestamp del ledger
+    pub vencimiento: u64,
+    pub fin_gracia: u64,
+    pub ultimo_devengo: u64,        // Hasta cuándo se calculó el interés
+    pub resto_devengo: i128,        // Fracción de interés aún no cobrada (× 10_000 × año)
+    pub estado: EstadoPrestamo,
+}
+
 /// Sistema de préstamos completo
 /// 
 /// Demuestra el uso combinado de Option y Result:
 /// - Option para datos que pueden no existir (límite de crédito)
 /// - Result para operaciones que pueden fallar (solicitar préstamo)
+/// 
+/// Ciclo de vida: `solicitar_prestamo` → `pagar` (parcial o total).
+/// El capital pendiente ocupa el límite de crédito hasta que se paga.
+/// 
+/// Los fondos son tokens reales (cualquier token SEP-41): el owner
+/// `fondear` la liquidez, los préstamos salen de ella al balance de la
+/// solicitante, y los pagos vuelven a ella. Siempre se cumple:
+/// balance del contrato en el token == liquidez + Σ balances.
+/// 
+/// Los datos de cada solicitante (límite, crédito usado, incumplimientos
+/// y préstamos activos) van en storage persistent con TTL largo: son
+/// por cuenta y no deben crecer la instancia ni vencer con la deuda viva.
 #[contract]
 pub struct MicroCredito;
 
@@ -299,26 +386,142 @@ pub enum DataKeyCredito {
     LimiteCredito(Address),
     Balance(Address),
     TotalPrestado(Address),
+    CreditoUsado(Address),          // Capital pendiente de todos sus préstamos
+    Incumplimientos(Address),       // Préstamos incumplidos sin saldar
+    PrestamosActivos(Address),      // IDs de sus préstamos sin saldar
+    Condiciones,
+    TotalPrestamos,
+    Prestamo(u32),
+    Token,                          // Token que se presta
+    Liquidez,                       // Tokens disponibles para prestar
 }
 
 #[contractimpl]
 impl MicroCredito {
+    /// Inicializar con un owner y el token que se presta (una sola vez)
+    pub fn initialize(env: Env, owner: Address, token: Address) -> Result<(), Error> {
+        owner.require_auth();
+        
+        ownable::inicializar(&env, &owner).map_err(|_| Error::YaInicializado)?;
+        env.storage().instance().set(&DataKeyCredito::Token, &token);
+        env.storage().instance().set(&DataKeyCredito::Liquidez, &0i128);
+        env.storage().instance().extend_ttl(100, 100);
+        
+        Ok(())
+    }
+    
+    /// Owner: aportar tokens para prestar
+    pub fn fondear(env: Env, monto: i128) -> Result<(), Error> {
+        let owner = Self::require_owner(&env)?;
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        Self::token_client(&env)?.transfer(&owner, &env.current_contract_address(), &monto);
+        Self::ajustar_liquidez(&env, monto)?;
+        
+        Ok(())
+    }
+    
+    /// Owner: retirar liquidez que no está prestada
+    pub fn retirar_liquidez(env: Env, monto: i128) -> Result<(), Error> {
+        let owner = Self::require_owner(&env)?;
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        Self::ajustar_liquidez(&env, -monto)?;
+        Self::token_client(&env)?.transfer(&env.current_contract_address(), &owner, &monto);
+        
+        Ok(())
+    }
+    
+    /// Tokens disponibles para prestar
+    pub fn get_liquidez(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKeyCredito::Liquidez)
+            .unwrap_or(0)
+    }
+    
+    /// Consulta el owner actual
+    pub fn owner(env: Env) -> Result<Address, OwnableError> {
+        ownable::owner(&env)
+    }
+    
+    /// Propone un nuevo owner (requiere firma del owner actual)
+    pub fn proponer_owner(env: Env, nuevo_owner: Address) -> Result<(), OwnableError> {
+        ownable::proponer(&env, &nuevo_owner)
+    }
+    
+    /// El owner propuesto acepta (requiere su firma)
+    pub fn aceptar_owner(env: Env) -> Result<(), OwnableError> {
+        ownable::aceptar(&env).map(|_| ())
+    }
+    
     /// Obtener límite de crédito (puede no existir)
     /// 
     /// Retorna Option<i128> porque:
     /// - Es válido que una solicitante no tenga límite aún
     /// - El caller decide cómo manejar None
     pub fn get_limite(env: Env, solicitante: Address) -> Option<i128> {
-        env.storage()
-            .instance()
-            .get(&DataKeyCredito::LimiteCredito(solicitante))
+        Self::leer_cuenta(&env, &DataKeyCredito::LimiteCredito(solicitante))
     }
     
-    /// Establecer límite de crédito
-    pub fn establecer_limite(env: Env, solicitante: Address, limite: i128) {
+    /// Owner: establecer límite de crédito (0 bloquea préstamos nuevos)
+    pub fn establecer_limite(env: Env, solicitante: Address, limite: i128) -> Result<(), Error> {
+        Self::require_owner(&env)?;
+        
+        if limite < 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        Self::guardar_cuenta(&env, &DataKeyCredito::LimiteCredito(solicitante), &limite);
+        
+        Ok(())
+    }
+    
+    /// Owner: establecer condiciones para los préstamos nuevos
+    /// 
+    /// Los préstamos ya otorgados conservan sus condiciones.
+    pub fn establecer_condiciones(
+        env: Env,
+        tasa_bps: u32,
+        plazo: u64,
+        gracia: u64,
+    ) -> Result<(), Error> {
+        Self::require_owner(&env)?;
+        
+        if tasa_bps > MAX_TASA_BPS {
+            return Err(Error::TasaInvalida);
+        }
+        
+        if plazo == 0 {
+            return Err(Error::PlazoInvalido);
+        }
+        
+        env.storage().instance().set(
+            &DataKeyCredito::Condiciones,
+            &CondicionesCredito { tasa_bps, plazo, gracia },
+        );
+        
+        Ok(())
+    }
+    
+    /// Obtener condiciones vigentes (con valores por defecto)
+    /// 
+    /// Por defecto: 5% anual, 30 días de plazo y 7 de gracia.
+    pub fn get_condiciones(env: Env) -> CondicionesCredito {
         env.storage()
             .instance()
-            .set(&DataKeyCredito::LimiteCredito(solicitante), &limite);
+            .get(&DataKeyCredito::Condiciones)
+            .unwrap_or(CondicionesCredito {
+                tasa_bps: 500,
+                plazo: 30 * 24 * 60 * 60,
+                gracia: 7 * 24 * 60 * 60,
+            })
     }
     
     /// Solicitar préstamo (puede fallar)
@@ -326,11 +529,13 @@ impl MicroCredito {
     /// Retorna Result porque:
     /// - Puede fallar por múltiples motivos específicos
     /// - Necesitamos informar exactamente qué salió mal
+    /// 
+    /// Devuelve el ID del préstamo.
     pub fn solicitar_prestamo(
         env: Env,
         solicitante: Address,
         monto: i128,
-    ) -> Result<(), Error> {
+    ) -> Result<u32, Error> {
         // 1. Autenticación
         solicitante.require_auth();
         
@@ -345,17 +550,260 @@ impl MicroCredito {
         let limite = Self::get_limite(env.clone(), solicitante.clone())
             .ok_or(Error::SolicitanteNoValida)?;
         
-        // 4. Verificar que no excede límite
-        if monto > limite {
+        // 4. Sin préstamos nuevos mientras haya uno incumplido: el
+        // contador cubre los ya marcados, y el recorrido los que pasaron
+        // `fin_gracia` sin que nadie llamara a `actualizar_estado`
+        if Self::get_incumplimientos(env.clone(), solicitante.clone()) > 0 {
+            return Err(Error::EnIncumplimiento);
+        }
+        
+        let ahora = env.ledger().timestamp();
+        let mut activos = Self::prestamos_activos(&env, &solicitante);
+        for id in activos.iter() {
+            if ahora > Self::leer_prestamo(&env, id)?.fin_gracia {
+                return Err(Error::EnIncumplimiento);
+            }
+        }
+        
+        if activos.len() >= MAX_PRESTAMOS_ACTIVOS {
+            return Err(Error::DemasiadosPrestamos);
+        }
+        
+        // 5. Verificar que no excede límite (contando lo que ya debe)
+        let credito_usado = Self::get_credito_usado(env.clone(), solicitante.clone());
+        let nuevo_usado = credito_usado
+            .checked_add(monto)
+            .ok_or(Error::LimiteExcedido)?;
+        
+        if nuevo_usado > limite {
             return Err(Error::LimiteExcedido);
         }
         
-        // 5. Procesar préstamo
-        Self::ejecutar_prestamo(env, solicitante, monto)?;
+        // 6. Procesar préstamo (sale de la liquidez)
+        Self::ajustar_liquidez(&env, -monto)?;
+        Self::ejecutar_prestamo(env.clone(), solicitante.clone(), monto)?;
+        
+        Self::guardar_cuenta(
+            &env,
+            &DataKeyCredito::CreditoUsado(solicitante.clone()),
+            &nuevo_usado,
+        );
+        
+        // 7. Guardar registro del préstamo
+        let condiciones = Self::get_condiciones(env.clone());
+        let vencimiento = ahora
+            .checked_add(condiciones.plazo)
+            .ok_or(Error::PlazoInvalido)?;
+        let fin_gracia = vencimiento
+            .checked_add(condiciones.gracia)
+            .ok_or(Error::PlazoInvalido)?;
+        
+        let id: u32 = env.storage()
+            .instance()
+            .get(&DataKeyCredito::TotalPrestamos)
+            .unwrap_or(0);
+        
+        activos.push_back(id);
+        Self::guardar_cuenta(
+            &env,
+            &DataKeyCredito::PrestamosActivos(solicitante.clone()),
+            &activos,
+        );
+        
+        let prestamo = Prestamo {
+            solicitante,
+            principal: monto,
+            principal_pendiente: monto,
+            interes_pendiente: 0,
+            pagado: 0,
+            tasa_bps: condiciones.tasa_bps,
+            inicio: ahora,
+            vencimiento,
+            fin_gracia,
+            ultimo_devengo: ahora,
+            resto_devengo: 0,
+            estado: EstadoPrestamo::Activo,
+        };
+        
+        Self::guardar_prestamo(&env, id, &prestamo);
+        env.storage()
+            .instance()
+            .set(&DataKeyCredito::TotalPrestamos, &(id + 1));
+        
+        Ok(id)
+    }
+    
+    /// Pagar un préstamo (parcial o total)
+    /// 
+    /// Descuenta del balance de la solicitante y lo devuelve a la
+    /// liquidez. Primero cubre el interés devengado y después el
+    /// capital; si `monto` supera la deuda solo se cobra la deuda.
+    /// Devuelve lo que queda por pagar.
+    pub fn pagar(env: Env, id: u32, monto: i128) -> Result<i128, Error> {
+        // 1. Buscar préstamo (Option → Result)
+        let mut prestamo = Self::leer_prestamo(&env, id)?;
+        prestamo.solicitante.require_auth();
+        
+        if prestamo.estado == EstadoPrestamo::Pagado {
+            return Err(Error::PrestamoPagado);
+        }
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        // 2. Poner al día interés y estado
+        Self::actualizar(&env, &mut prestamo)?;
+        
+        let deuda = prestamo.principal_pendiente + prestamo.interes_pendiente;
+        let aplicado = monto.min(deuda);
+        
+        // 3. Cobrar del balance de la solicitante
+        let key_balance = DataKeyCredito::Balance(prestamo.solicitante.clone());
+        let balance: i128 = env.storage()
+            .instance()
+            .get(&key_balance)
+            .unwrap_or(0);
+        
+        ValidacionHelper::validar_balance(balance, aplicado)?;
+        
+        env.storage()
+            .instance()
+            .set(&key_balance, &(balance - aplicado));
+        Self::ajustar_liquidez(&env, aplicado)?;
+        
+        // 4. Interés primero, después capital
+        let a_interes = aplicado.min(prestamo.interes_pendiente);
+        let a_capital = aplicado - a_interes;
+        
+        prestamo.interes_pendiente -= a_interes;
+        prestamo.principal_pendiente -= a_capital;
+        prestamo.pagado += aplicado;
+        
+        // 5. El capital pagado vuelve a estar disponible en el límite
+        let credito_usado = Self::get_credito_usado(env.clone(), prestamo.solicitante.clone());
+        Self::guardar_cuenta(
+            &env,
+            &DataKeyCredito::CreditoUsado(prestamo.solicitante.clone()),
+            &(credito_usado - a_capital),
+        );
+        
+        // 6. ¿Quedó saldado?
+        let restante = prestamo.principal_pendiente + prestamo.interes_pendiente;
+        if restante == 0 {
+            if prestamo.estado == EstadoPrestamo::Incumplido {
+                Self::ajustar_incumplimientos(&env, &prestamo.solicitante, -1);
+            }
+            prestamo.estado = EstadoPrestamo::Pagado;
+            Self::quitar_activo(&env, &prestamo.solicitante, id);
+        }
+        
+        Self::guardar_prestamo(&env, id, &prestamo);
+        
+        Ok(restante)
+    }
+    
+    /// Actualizar interés y estado de un préstamo según el tiempo
+    /// 
+    /// Cualquiera puede llamarla (por ejemplo, para marcar un
+    /// incumplimiento). Devuelve el estado resultante.
+    pub fn actualizar_estado(env: Env, id: u32) -> Result<EstadoPrestamo, Error> {
+        let mut prestamo = Self::leer_prestamo(&env, id)?;
+        
+        Self::actualizar(&env, &mut prestamo)?;
+        Self::guardar_prestamo(&env, id, &prestamo);
+        
+        Ok(prestamo.estado)
+    }
+    
+    /// Depositar fondos propios en el balance (por ejemplo, para pagar)
+    /// 
+    /// Transfiere `monto` del token desde la solicitante al contrato.
+    pub fn depositar(env: Env, solicitante: Address, monto: i128) -> Result<(), Error> {
+        solicitante.require_auth();
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        Self::token_client(&env)?.transfer(
+            &solicitante,
+            &env.current_contract_address(),
+            &monto,
+        );
+        
+        let key = DataKeyCredito::Balance(solicitante);
+        let balance: i128 = env.storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(0);
+        
+        let nuevo_balance = balance
+            .checked_add(monto)
+            .ok_or(Error::MontoInvalido)?;
+        
+        env.storage().instance().set(&key, &nuevo_balance);
+        
+        Ok(())
+    }
+    
+    /// Retirar tokens del balance (lo prestado o lo depositado)
+    pub fn retirar(env: Env, solicitante: Address, monto: i128) -> Result<(), Error> {
+        solicitante.require_auth();
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        let key = DataKeyCredito::Balance(solicitante.clone());
+        let balance: i128 = env.storage()
+            .instance()
+            .get(&key)
+            .unwrap_or(0);
+        
+        ValidacionHelper::validar_balance(balance, monto)?;
+        
+        env.storage().instance().set(&key, &(balance - monto));
+        
+        Self::token_client(&env)?.transfer(
+            &env.current_contract_address(),
+            &solicitante,
+            &monto,
+        );
         
         Ok(())
     }
     
+    /// Obtener un préstamo (como quedó en la última operación)
+    pub fn get_prestamo(env: Env, id: u32) -> Option<Prestamo> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyCredito::Prestamo(id))
+    }
+    
+    /// Deuda total a la fecha (capital + interés devengado hasta ahora)
+    pub fn get_deuda(env: Env, id: u32) -> Result<i128, Error> {
+        let mut prestamo = Self::leer_prestamo(&env, id)?;
+        Self::devengar(&env, &mut prestamo)?;
+        
+        Ok(prestamo.principal_pendiente + prestamo.interes_pendiente)
+    }
+    
+    /// Capital pendiente que ocupa el límite de crédito
+    pub fn get_credito_usado(env: Env, solicitante: Address) -> i128 {
+        Self::leer_cuenta(&env, &DataKeyCredito::CreditoUsado(solicitante))
+            .unwrap_or(0)
+    }
+    
+    /// Cantidad de préstamos marcados como incumplidos y sin saldar
+    /// 
+    /// Solo cuenta los que pasaron por `actualizar_estado` o `pagar`;
+    /// `solicitar_prestamo` además revisa los préstamos activos.
+    pub fn get_incumplimientos(env: Env, solicitante: Address) -> u32 {
+        Self::leer_cuenta(&env, &DataKeyCredito::Incumplimientos(solicitante))
+            .unwrap_or(0)
+    }
+    
     /// Ejecutar préstamo (helper privado)
     /// 
     /// Usa el operador ? para propagar errores
@@ -365,7 +813,7 @@ impl MicroCredito {
         monto: i128,
     ) -> Result<(), Error> {
         // Leer balance actual
-        let balance_actual = env.storage()
+        let balance_actual: i128 = env.storage()
             .instance()
             .get(&DataKeyCredito::Balance(solicitante.clone()))
             .unwrap_or(0);
@@ -381,7 +829,7 @@ impl MicroCredito {
             .set(&DataKeyCredito::Balance(solicitante.clone()), &nuevo_balance);
         
         // Actualizar total prestado
-        let total_prestado = env.storage()
+        let total_prestado: i128 = env.storage()
             .instance()
             .get(&DataKeyCredito::TotalPrestado(solicitante.clone()))
             .unwrap_or(0);
@@ -414,6 +862,166 @@ impl MicroCredito {
     }
 }
 
+// Helpers privados de MicroCredito
+impl MicroCredito {
+    /// `ownable::require_owner` con los errores de este contrato
+    fn require_owner(env: &Env) -> Result<Address, Error> {
+        ownable::require_owner(env).map_err(|e| match e {
+            OwnableError::NoInicializado => Error::NoInicializado,
+            _ => Error::SinOwner,
+        })
+    }
+    
+    /// Cliente del token que se presta
+    fn token_client(env: &Env) -> Result<token::Client<'_>, Error> {
+        let token: Address = env.storage()
+            .instance()
+            .get(&DataKeyCredito::Token)
+            .ok_or(Error::NoInicializado)?;
+        Ok(token::Client::new(env, &token))
+    }
+    
+    /// Suma (o resta, si `delta` < 0) a la liquidez disponible
+    fn ajustar_liquidez(env: &Env, delta: i128) -> Result<(), Error> {
+        let nueva = Self::get_liquidez(env.clone())
+            .checked_add(delta)
+            .ok_or(Error::MontoInvalido)?;
+        
+        if nueva < 0 {
+            return Err(Error::LiquidezInsuficiente);
+        }
+        
+        env.storage().instance().set(&DataKeyCredito::Liquidez, &nueva);
+        env.storage().instance().extend_ttl(100, 100);
+        
+        Ok(())
+    }
+    
+    /// Leer préstamo (None → Error::PrestamoNoExiste)
+    fn leer_prestamo(env: &Env, id: u32) -> Result<Prestamo, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyCredito::Prestamo(id))
+            .ok_or(Error::PrestamoNoExiste)
+    }
+    
+    /// Guardar préstamo y extender su TTL
+    fn guardar_prestamo(env: &Env, id: u32, prestamo: &Prestamo) {
+        let key = DataKeyCredito::Prestamo(id);
+        env.storage().persistent().set(&key, prestamo);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+    }
+    
+    /// Devengar interés simple sobre el capital pendiente hasta ahora
+    /// 
+    /// interés = capital × tasa_bps × segundos / (10_000 × SEGUNDOS_POR_ANIO)
+    /// 
+    /// Lo que la división entera deja afuera queda en `resto_devengo`
+    /// para el próximo devengo: llamar seguido (por ejemplo con
+    /// `actualizar_estado` en cada ledger) no hace que el interés se
+    /// redondee a 0.
+    fn devengar(env: &Env, prestamo: &mut Prestamo) -> Result<(), Error> {
+        let ahora = env.ledger().timestamp();
+        if prestamo.estado == EstadoPrestamo::Pagado || ahora <= prestamo.ultimo_devengo {
+            return Ok(());
+        }
+        
+        let transcurrido = (ahora - prestamo.ultimo_devengo) as i128;
+        let divisor = 10_000 * SEGUNDOS_POR_ANIO as i128;
+        let acumulado = prestamo.principal_pendiente
+            .checked_mul(prestamo.tasa_bps as i128)
+            .and_then(|x| x.checked_mul(transcurrido))
+            .and_then(|x| x.checked_add(prestamo.resto_devengo))
+            .ok_or(Error::MontoInvalido)?;
+        
+        prestamo.interes_pendiente = prestamo.interes_pendiente
+            .checked_add(acumulado / divisor)
+            .ok_or(Error::MontoInvalido)?;
+        prestamo.resto_devengo = acumulado % divisor;
+        prestamo.ultimo_devengo = ahora;
+        
+        Ok(())
+    }
+    
+    /// Devengar interés y aplicar las transiciones de estado por tiempo
+    /// 
+    /// Activo → Vencido (pasó `vencimiento`) → Incumplido (pasó `fin_gracia`)
+    fn actualizar(env: &Env, prestamo: &mut Prestamo) -> Result<(), Error> {
+        Self::devengar(env, prestamo)?;
+        
+        let ahora = env.ledger().timestamp();
+        let nuevo_estado = match prestamo.estado {
+            EstadoPrestamo::Pagado | EstadoPrestamo::Incumplido => prestamo.estado,
+            _ if ahora > prestamo.fin_gracia => EstadoPrestamo::Incumplido,
+            _ if ahora > prestamo.vencimiento => EstadoPrestamo::Vencido,
+            _ => EstadoPrestamo::Activo,
+        };
+        
+        if nuevo_estado == EstadoPrestamo::Incumplido
+            && prestamo.estado != EstadoPrestamo::Incumplido
+        {
+            Self::ajustar_incumplimientos(env, &prestamo.solicitante, 1);
+        }
+        
+        prestamo.estado = nuevo_estado;
+        
+        Ok(())
+    }
+    
+    /// Sumar o restar al contador de incumplimientos
+    fn ajustar_incumplimientos(env: &Env, solicitante: &Address, delta: i32) {
+        let key = DataKeyCredito::Incumplimientos(solicitante.clone());
+        let actual: u32 = Self::leer_cuenta(env, &key).unwrap_or(0);
+        let nuevo = (actual as i64 + delta as i64).max(0) as u32;
+        Self::guardar_cuenta(env, &key, &nuevo);
+    }
+    
+    /// IDs de los préstamos sin saldar de una solicitante
+    fn prestamos_activos(env: &Env, solicitante: &Address) -> Vec<u32> {
+        Self::leer_cuenta(env, &DataKeyCredito::PrestamosActivos(solicitante.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+    
+    /// Sacar un préstamo saldado de los activos de su solicitante
+    fn quitar_activo(env: &Env, solicitante: &Address, id: u32) {
+        let mut activos = Self::prestamos_activos(env, solicitante);
+        if let Some(i) = activos.first_index_of(id) {
+            activos.remove(i);
+            Self::guardar_cuenta(
+                env,
+                &DataKeyCredito::PrestamosActivos(solicitante.clone()),
+                &activos,
+            );
+        }
+    }
+    
+    /// Leer un dato por solicitante (persistent) y extender su TTL
+    fn leer_cuenta<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKeyCredito) -> Option<V> {
+        let valor = env.storage().persistent().get(key);
+        if valor.is_some() {
+            Self::extender_cuenta(env, key);
+        }
+        valor
+    }
+    
+    /// Guardar un dato por solicitante (persistent) con TTL largo
+    fn guardar_cuenta<V: IntoVal<Env, Val>>(
+        env: &Env,
+        key: &DataKeyCredito,
+        valor: &V,
+    ) {
+        env.storage().persistent().set(key, valor);
+        Self::extender_cuenta(env, key);
+    }
+    
+    /// Extiende a `TTL_CHECKPOINT` (o al máximo de la red) cuando le
+    /// queda menos de la mitad
+    fn extender_cuenta(env: &Env, key: &DataKeyCredito) {
+        let ttl = TTL_CHECKPOINT.min(env.storage().max_ttl());
+        env.storage().persistent().extend_ttl(key, ttl / 2, ttl);
+    }
+}
+
 // ============================================================
 // EJEMPLO 5: Helper Functions para Validación
 // ============================================================
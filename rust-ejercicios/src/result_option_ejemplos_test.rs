@@ -1,6 +1,7 @@
 #![cfg(test)]
 use soroban_sdk::{
-    testutils::Address as _,
+    testutils::{Address as _, Ledger},
+    token::{Client as TokenClient, StellarAssetClient},
     Address,
     Env,
     symbol_short,
@@ -10,6 +11,11 @@ use crate::result_option_ejemplos::{
     TransferSeguro,
     OptionEjemplo,
     MicroCredito,
+    MicroCreditoClient,
+    EstadoPrestamo,
+    MAX_PRESTAMOS_ACTIVOS,
+    MAX_TASA_BPS,
+    SEGUNDOS_POR_ANIO,
     ConversionOptionResult,
     DonacionValidada,
     Error,
@@ -188,14 +194,32 @@ fn test_option_map() {
     assert_eq!(balance_doble, Some(100));
 }
 
+/// MicroCredito inicializado con un token de prueba y 10_000 de liquidez
+fn crear_microcredito<'a>(env: &Env) -> (MicroCreditoClient<'a>, StellarAssetClient<'a>) {
+    let owner = Address::generate(env);
+    let token_id = env.register_stellar_asset_contract(owner.clone());
+    let token_admin = StellarAssetClient::new(env, &token_id);
+    
+    let contract_id = env.register_contract(None, MicroCredito);
+    let credito = MicroCreditoClient::new(env, &contract_id);
+    
+    env.mock_all_auths();
+    credito.initialize(&owner, &token_id);
+    token_admin.mint(&owner, &10_000);
+    credito.fondear(&10_000);
+    
+    (credito, token_admin)
+}
+
 #[test]
 fn test_microcredito_get_limite_none() {
     let env = Env::default();
+    let (credito, _) = crear_microcredito(&env);
     
     let solicitante = Address::generate(&env);
     
     // Solicitante nueva sin límite
-    let limite = MicroCredito::get_limite(env.clone(), solicitante.clone());
+    let limite = credito.get_limite(&solicitante);
     
     assert_eq!(limite, None);
 }
@@ -204,64 +228,339 @@ fn test_microcredito_get_limite_none() {
 fn test_microcredito_solicitar_prestamo_sin_limite() {
     let env = Env::default();
     env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
     
     let solicitante = Address::generate(&env);
     
     // Intentar solicitar préstamo sin límite establecido
-    let resultado = MicroCredito::solicitar_prestamo(env.clone(), solicitante.clone(), 100);
+    let resultado = credito.try_solicitar_prestamo(&solicitante, &100);
     
-    assert_eq!(resultado, Err(Error::SolicitanteNoValida));
+    assert_eq!(resultado, Err(Ok(Error::SolicitanteNoValida)));
 }
 
 #[test]
 fn test_microcredito_solicitar_prestamo_exitoso() {
     let env = Env::default();
     env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
     
     let solicitante = Address::generate(&env);
     
     // Establecer límite
-    MicroCredito::establecer_limite(env.clone(), solicitante.clone(), 1000);
+    credito.establecer_limite(&solicitante, &1000);
     
     // Solicitar préstamo
-    let resultado = MicroCredito::solicitar_prestamo(env.clone(), solicitante.clone(), 500);
+    let resultado = credito.try_solicitar_prestamo(&solicitante, &500);
     
     assert!(resultado.is_ok());
     
     // Verificar balance
-    assert_eq!(MicroCredito::obtener_balance(env.clone(), solicitante.clone()), 500);
-    assert_eq!(MicroCredito::obtener_total_prestado(env.clone(), solicitante.clone()), 500);
+    assert_eq!(credito.obtener_balance(&solicitante), 500);
+    assert_eq!(credito.obtener_total_prestado(&solicitante), 500);
 }
 
 #[test]
 fn test_microcredito_solicitar_prestamo_limite_excedido() {
     let env = Env::default();
     env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
     
     let solicitante = Address::generate(&env);
     
     // Establecer límite
-    MicroCredito::establecer_limite(env.clone(), solicitante.clone(), 1000);
+    credito.establecer_limite(&solicitante, &1000);
     
     // Intentar solicitar más del límite
-    let resultado = MicroCredito::solicitar_prestamo(env.clone(), solicitante.clone(), 1500);
+    let resultado = credito.try_solicitar_prestamo(&solicitante, &1500);
     
-    assert_eq!(resultado, Err(Error::LimiteExcedido));
+    assert_eq!(resultado, Err(Ok(Error::LimiteExcedido)));
 }
 
 #[test]
 fn test_microcredito_solicitar_prestamo_monto_invalido() {
     let env = Env::default();
     env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
     
     let solicitante = Address::generate(&env);
     
-    MicroCredito::establecer_limite(env.clone(), solicitante.clone(), 1000);
+    credito.establecer_limite(&solicitante, &1000);
     
     // Intentar con monto negativo
-    let resultado = MicroCredito::solicitar_prestamo(env.clone(), solicitante.clone(), -100);
+    let resultado = credito.try_solicitar_prestamo(&solicitante, &-100);
     
-    assert_eq!(resultado, Err(Error::MontoInvalido));
+    assert_eq!(resultado, Err(Ok(Error::MontoInvalido)));
+}
+
+#[test]
+fn test_microcredito_registro_del_prestamo() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
+    
+    let solicitante = Address::generate(&env);
+    credito.establecer_limite(&solicitante, &1000);
+    credito.establecer_condiciones(&1_000, &100, &50);
+    
+    env.ledger().with_mut(|li| li.timestamp = 10);
+    let id = credito.solicitar_prestamo(&solicitante, &400);
+    
+    let prestamo = credito.get_prestamo(&id).unwrap();
+    assert_eq!(prestamo.principal, 400);
+    assert_eq!(prestamo.tasa_bps, 1_000);
+    assert_eq!(prestamo.inicio, 10);
+    assert_eq!(prestamo.vencimiento, 110);
+    assert_eq!(prestamo.fin_gracia, 160);
+    assert_eq!(prestamo.estado, EstadoPrestamo::Activo);
+    
+    // El capital pendiente ocupa el límite
+    assert_eq!(credito.get_credito_usado(&solicitante), 400);
+    assert_eq!(
+        credito.try_solicitar_prestamo(&solicitante, &601),
+        Err(Ok(Error::LimiteExcedido))
+    );
+}
+
+#[test]
+fn test_microcredito_interes_y_pagos() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (credito, token_admin) = crear_microcredito(&env);
+    let token = TokenClient::new(&env, &token_admin.address);
+    
+    let solicitante = Address::generate(&env);
+    credito.establecer_limite(&solicitante, &2_000);
+    // 10% anual, plazo de un año
+    credito.establecer_condiciones(&1_000, &SEGUNDOS_POR_ANIO, &0);
+    
+    let id = credito.solicitar_prestamo(&solicitante, &1_000);
+    assert_eq!(credito.get_liquidez(), 9_000);
+    
+    // Medio año → 50 de interés
+    env.ledger().with_mut(|li| li.timestamp = SEGUNDOS_POR_ANIO / 2);
+    assert_eq!(credito.get_deuda(&id), 1_050);
+    
+    // Pago parcial: cubre primero el interés
+    assert_eq!(credito.pagar(&id, &300), 750);
+    let prestamo = credito.get_prestamo(&id).unwrap();
+    assert_eq!(prestamo.interes_pendiente, 0);
+    assert_eq!(prestamo.principal_pendiente, 750);
+    assert_eq!(credito.get_credito_usado(&solicitante), 750);
+    assert_eq!(credito.obtener_balance(&solicitante), 700);
+    
+    // Pago total (lo que excede la deuda no se cobra); el depósito
+    // son tokens reales de la solicitante
+    token_admin.mint(&solicitante, &100);
+    credito.depositar(&solicitante, &100);
+    assert_eq!(token.balance(&solicitante), 0);
+    assert_eq!(credito.pagar(&id, &5_000), 0);
+    assert_eq!(credito.obtener_balance(&solicitante), 50);
+    
+    // Lo pagado volvió a la liquidez; el resto se puede retirar
+    assert_eq!(credito.get_liquidez(), 10_050);
+    credito.retirar(&solicitante, &50);
+    assert_eq!(token.balance(&solicitante), 50);
+    assert_eq!(token.balance(&credito.address), 10_050);
+    
+    let prestamo = credito.get_prestamo(&id).unwrap();
+    assert_eq!(prestamo.estado, EstadoPrestamo::Pagado);
+    assert_eq!(prestamo.pagado, 1_050);
+    
+    // El límite vuelve a estar disponible
+    assert_eq!(credito.get_credito_usado(&solicitante), 0);
+    assert_eq!(credito.try_pagar(&id, &1), Err(Ok(Error::PrestamoPagado)));
+}
+
+#[test]
+fn test_microcredito_pago_sin_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
+    
+    let solicitante = Address::generate(&env);
+    credito.establecer_limite(&solicitante, &1_000);
+    // 100% anual
+    credito.establecer_condiciones(&MAX_TASA_BPS, &SEGUNDOS_POR_ANIO, &0);
+    
+    let id = credito.solicitar_prestamo(&solicitante, &100);
+    
+    // Al año debe 200 pero solo tiene 100
+    env.ledger().with_mut(|li| li.timestamp = SEGUNDOS_POR_ANIO);
+    assert_eq!(credito.try_pagar(&id, &200), Err(Ok(Error::BalanceInsuficiente)));
+    assert_eq!(credito.try_pagar(&id, &0), Err(Ok(Error::MontoInvalido)));
+    assert_eq!(credito.try_pagar(&7, &1), Err(Ok(Error::PrestamoNoExiste)));
+}
+
+#[test]
+fn test_microcredito_vencido_e_incumplido() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
+    
+    let solicitante = Address::generate(&env);
+    credito.establecer_limite(&solicitante, &1_000);
+    credito.establecer_condiciones(&0, &100, &50);
+    
+    let id = credito.solicitar_prestamo(&solicitante, &300);
+    
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(credito.actualizar_estado(&id), EstadoPrestamo::Activo);
+    
+    env.ledger().with_mut(|li| li.timestamp = 101);
+    assert_eq!(credito.actualizar_estado(&id), EstadoPrestamo::Vencido);
+    
+    env.ledger().with_mut(|li| li.timestamp = 151);
+    assert_eq!(credito.actualizar_estado(&id), EstadoPrestamo::Incumplido);
+    assert_eq!(credito.get_incumplimientos(&solicitante), 1);
+    
+    // Con un incumplimiento no se otorgan préstamos nuevos
+    assert_eq!(
+        credito.try_solicitar_prestamo(&solicitante, &10),
+        Err(Ok(Error::EnIncumplimiento))
+    );
+    
+    // Saldar la deuda levanta el bloqueo
+    assert_eq!(credito.pagar(&id, &300), 0);
+    assert_eq!(credito.get_incumplimientos(&solicitante), 0);
+    assert!(credito.try_solicitar_prestamo(&solicitante, &10).is_ok());
+}
+
+#[test]
+fn test_microcredito_incumplimiento_sin_actualizar_estado() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
+    
+    let solicitante = Address::generate(&env);
+    credito.establecer_limite(&solicitante, &1_000);
+    credito.establecer_condiciones(&0, &100, &50);
+    
+    let id = credito.solicitar_prestamo(&solicitante, &300);
+    
+    // Dentro de la gracia todavía puede pedir
+    env.ledger().with_mut(|li| li.timestamp = 150);
+    credito.solicitar_prestamo(&solicitante, &10);
+    
+    // Pasó fin_gracia y nadie marcó el incumplimiento: igual se rechaza
+    env.ledger().with_mut(|li| li.timestamp = 151);
+    assert_eq!(credito.get_incumplimientos(&solicitante), 0);
+    assert_eq!(
+        credito.try_solicitar_prestamo(&solicitante, &10),
+        Err(Ok(Error::EnIncumplimiento))
+    );
+    
+    // Saldar el préstamo vencido lo saca de los activos
+    assert_eq!(credito.pagar(&id, &300), 0);
+    assert!(credito.try_solicitar_prestamo(&solicitante, &10).is_ok());
+}
+
+#[test]
+fn test_microcredito_maximo_de_prestamos_activos() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
+    
+    let solicitante = Address::generate(&env);
+    credito.establecer_limite(&solicitante, &1_000);
+    credito.establecer_condiciones(&0, &100, &50);
+    
+    for _ in 0..MAX_PRESTAMOS_ACTIVOS {
+        credito.solicitar_prestamo(&solicitante, &1);
+    }
+    assert_eq!(
+        credito.try_solicitar_prestamo(&solicitante, &1),
+        Err(Ok(Error::DemasiadosPrestamos))
+    );
+    
+    // Saldar uno libera lugar
+    assert_eq!(credito.pagar(&0, &1), 0);
+    assert!(credito.try_solicitar_prestamo(&solicitante, &1).is_ok());
+}
+
+#[test]
+fn test_microcredito_interes_no_se_pierde_devengando_seguido() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
+    
+    let solicitante = Address::generate(&env);
+    credito.establecer_limite(&solicitante, &1_000);
+    // 10% anual sobre 1_000: 100 por año, menos de 1 por cada tramo
+    credito.establecer_condiciones(&1_000, &SEGUNDOS_POR_ANIO, &0);
+    let id = credito.solicitar_prestamo(&solicitante, &1_000);
+    
+    // Devengar cada 10 minutos durante un día: cada tramo solo
+    // redondearía a 0
+    let mut ahora = 0;
+    while ahora < 24 * 60 * 60 {
+        ahora += 10 * 60;
+        env.ledger().with_mut(|li| li.timestamp = ahora);
+        credito.actualizar_estado(&id);
+    }
+    
+    // Un día de una sola vez da lo mismo: 100 / 365 → 0 entero y el
+    // resto se conserva; a los 4 días ya es 1
+    assert_eq!(credito.get_deuda(&id), 1_000);
+    env.ledger().with_mut(|li| li.timestamp = 4 * 24 * 60 * 60);
+    assert_eq!(credito.get_deuda(&id), 1_001);
+    
+    // Medio año en tramos: exactamente 50, como de una sola vez
+    env.ledger().with_mut(|li| li.timestamp = SEGUNDOS_POR_ANIO / 2);
+    assert_eq!(credito.get_deuda(&id), 1_050);
+}
+
+#[test]
+fn test_microcredito_solo_owner_configura() {
+    let env = Env::default();
+    let (credito, _) = crear_microcredito(&env);
+    let owner = credito.owner();
+    
+    credito.establecer_condiciones(&700, &100, &0);
+    assert_eq!(env.auths()[0].0, owner);
+    
+    credito.establecer_limite(&Address::generate(&env), &500);
+    assert_eq!(env.auths()[0].0, owner);
+    
+    assert_eq!(
+        credito.try_initialize(&owner, &owner),
+        Err(Ok(Error::YaInicializado))
+    );
+}
+
+#[test]
+fn test_microcredito_sin_liquidez() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (credito, _) = crear_microcredito(&env);
+    
+    let solicitante = Address::generate(&env);
+    credito.establecer_limite(&solicitante, &50_000);
+    
+    assert_eq!(
+        credito.try_solicitar_prestamo(&solicitante, &10_001),
+        Err(Ok(Error::LiquidezInsuficiente))
+    );
+    credito.solicitar_prestamo(&solicitante, &10_000);
+    assert_eq!(credito.try_retirar_liquidez(&1), Err(Ok(Error::LiquidezInsuficiente)));
+}
+
+#[test]
+fn test_microcredito_condiciones_invalidas() {
+    let env = Env::default();
+    let (credito, _) = crear_microcredito(&env);
+    
+    assert_eq!(
+        credito.try_establecer_condiciones(&(MAX_TASA_BPS + 1), &100, &0),
+        Err(Ok(Error::TasaInvalida))
+    );
+    assert_eq!(
+        credito.try_establecer_condiciones(&500, &0, &0),
+        Err(Ok(Error::PlazoInvalido))
+    );
+    assert_eq!(
+        credito.try_establecer_limite(&Address::generate(&env), &-1),
+        Err(Ok(Error::MontoInvalido))
+    );
 }
 
 #[test]
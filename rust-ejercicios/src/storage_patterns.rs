@@ -12,7 +12,10 @@ use soroban_sdk::{
     u64,
     Error,
     symbol_short,
+    token,
+    Vec,
 };
+use crate::checkpoints;
 
 // ============================================================
 // PARTE 3: STORAGE PATTERNS EN SOROBAN
@@ -33,6 +36,28 @@ pub enum Error {
     YaInicializado = 4,
     NoInicializado = 5,
     UsuarioNoExiste = 6,
+    PaginaInvalida = 7,
+    CampanaNoExiste = 8,
+    CampanaCerrada = 9,
+    CampanaEnCurso = 10,
+    MetaNoAlcanzada = 11,
+    MetaAlcanzada = 12,
+    YaReclamada = 13,
+    SinAporte = 14,
+    FechaInvalida = 15,
+    PoolNoExiste = 16,
+    PoolCerrado = 17,
+    DemasiadosPools = 18,
+    RatioInvalido = 19,
+    Pausado = 20,
+    ComisionInvalida = 21,
+    SinAdminPendiente = 22,
+    Overflow = 23,
+    PoolSinBeneficiarias = 24,
+    FondosPoolInsuficientes = 25,
+    DemasiadasBeneficiarias = 26,
+    BeneficiariaDuplicada = 27,
+    DonacionNoExiste = 28,
 }
 
 // ============================================================
@@ -168,7 +193,17 @@ impl DatosUsuarios {
         // Extender TTL después de modificar
         env.storage()
             .persistent()
-            .extend_ttl(&DataKeyPersistent::Balance(usuario), 100, 100);
+            .extend_ttl(&DataKeyPersistent::Balance(usuario.clone()), 100, 100);
+        
+        // Historial: checkpoint del balance en este ledger
+        checkpoints::registrar(&env, &DataKeyPersistent::Balance(usuario), balance);
+    }
+    
+    /// Obtener el balance que tenía el usuario al cierre del ledger `ledger`
+    /// 
+    /// Patrón: checkpoints (ver `checkpoints.rs`) - 0 si todavía no tenía balance
+    pub fn get_balance_at(env: Env, usuario: Address, ledger: u32) -> i128 {
+        checkpoints::valor_en(&env, &DataKeyPersistent::Balance(usuario), ledger)
     }
     
     /// Verificar si usuario existe (patrón de verificación de existencia)
@@ -302,12 +337,104 @@ impl CacheTemporal {
 
 /// Estructura para información de donación
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DonacionInfo {
     pub donante: Address,
     pub beneficiaria: Address,
     pub monto: i128,
     pub timestamp: u64,
+    /// Campaña a la que se aportó (None = donación directa)
+    pub campana: Option<u32>,
+    /// Monto agregado por un pool de igualación (no sale del donante)
+    pub monto_igualado: i128,
+    /// Parte de `monto` que se llevó la plataforma
+    pub comision: i128,
+}
+
+/// Estructura para una campaña de recaudación
+/// 
+/// Los aportes quedan en custodia hasta `fecha_limite`. Después:
+/// - si `recaudado >= meta`, la beneficiaria reclama todo
+/// - si no, cada donante recupera exactamente lo que aportó
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CampanaInfo {
+    pub beneficiaria: Address,
+    pub meta: i128,
+    pub fecha_limite: u64,     // Timestamp del ledger
+    pub recaudado: i128,
+    pub reclamada: bool,
+}
+
+/// Pool de igualación financiado por un patrocinador
+/// 
+/// Cada donación a una beneficiaria elegible recibe
+/// `monto * ratio_bps / 10_000` extra, limitado por
+/// `tope_por_donacion` y por lo que quede de `fondos`. No se igualan
+/// las donaciones hechas por una beneficiaria del propio pool (ni a
+/// sí misma ni a otra): serían fondos del pool dando otra vuelta.
+/// 
+/// Las beneficiarias elegibles van cada una en su propia key
+/// (`PoolBeneficiaria`): cada donación verifica la elegibilidad con
+/// un `has`, sin recorrer una lista.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PoolIgualacion {
+    pub patrocinador: Address,
+    pub ratio_bps: u32,             // 10_000 = 1:1, 5_000 = 1:2
+    pub tope_por_donacion: i128,
+    pub fondos: i128,               // Tope total del pool
+    pub igualado: i128,             // Ya entregado a beneficiarias
+    pub beneficiarias: u32,         // Cantidad de beneficiarias elegibles
+    pub activo: bool,
+}
+
+/// Máximo de pools activos a la vez (se recorren en cada donación)
+pub const MAX_POOLS_ACTIVOS: u32 = 5;
+
+/// Máximo de beneficiarias por pool (se guardan al crearlo)
+pub const MAX_BENEFICIARIAS_POOL: u32 = 100;
+
+/// Fondos mínimos de un pool mientras el admin no configure otro valor
+/// (1 token de 7 decimales, como los Stellar Asset Contracts)
+/// 
+/// Sin mínimo, cualquiera ocupa los `MAX_POOLS_ACTIVOS` lugares con
+/// pools de polvo y los patrocinadores reales no pueden crear el suyo.
+pub const FONDOS_MINIMOS_POOL: i128 = 10_000_000;
+
+/// Base para ratios en puntos básicos (10_000 = 100%)
+const BPS_BASE: i128 = 10_000;
+
+/// Comisión máxima de la plataforma (10%)
+pub const MAX_COMISION_BPS: u32 = 1_000;
+
+/// Máximo de donaciones por página en las consultas de historial
+pub const MAX_PAGINA: u32 = 50;
+
+/// Duración aproximada de un ledger, para pasar fechas a TTL
+const SEGUNDOS_POR_LEDGER: u64 = 5;
+
+/// Margen después de la fecha límite para reclamar o reembolsar
+/// (30 días de 17_280 ledgers)
+const MARGEN_CAMPANA_LEDGERS: u32 = 30 * 17_280;
+
+/// TTL mínimo de cualquier entrada (el de siempre)
+const TTL_MINIMO: u32 = 100;
+
+/// Página de resultados de una consulta de historial
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaginaDonaciones {
+    pub donaciones: Vec<DonacionInfo>,
+    /// Cursor para pedir la página siguiente (None = no hay más)
+    pub siguiente: Option<u32>,
+}
+
+/// Índices de historial que se pueden paginar (uso interno)
+enum Indice {
+    Global,
+    Donante(Address),
+    Beneficiaria(Address),
 }
 
 /// DataKey para PlataformaDonaciones
@@ -318,24 +445,71 @@ pub enum DataKeyDonaciones {
     Admin,
     NombrePlataforma,
     TotalDonaciones,
+    Token,              // Token que la plataforma custodia
+    TotalCustodia,      // Tokens que el contrato tiene en su poder
+    TotalCampanas,
+    TotalPools,
+    PoolsActivos,       // Vec<u32> con los pools que aún igualan
+    FondosMinimosPool,
+    AdminPendiente,     // Propuesto, falta que acepte
+    Pausado,
+    ComisionBps,
+    Tesoreria,          // Destino de las comisiones
     
     // Persistent: Datos críticos de usuarios
-    BalanceDonante(Address),
-    DonacionesRecibidas(Address),
+    BalanceDonante(Address),        // Depositado y aún no donado
+    DonacionesRecibidas(Address),   // Total histórico recibido
+    SaldoBeneficiaria(Address),     // Recibido y aún no retirado
     Donacion(u32),
     
+    // Persistent: Campañas
+    Campana(u32),
+    AporteCampana(u32, Address),    // (campaña, donante) → aportado
+    
+    // Persistent: Pools de igualación
+    Pool(u32),
+    PoolBeneficiaria(u32, Address), // (pool, beneficiaria) → elegible
+    IgualadoRecibido(Address),      // Total histórico igualado por pools
+    
+    // Persistent: Índices de historial (slot por posición + contador)
+    IndiceDonante(Address, u32),        // (donante, posición) → ID
+    TotalPorDonante(Address),
+    IndiceBeneficiaria(Address, u32),   // (beneficiaria, posición) → ID
+    TotalPorBeneficiaria(Address),
+    
     // Temporary: Cache
     CacheTotalDonado,
 }
 
 /// Plataforma de donaciones completa con todos los patrones
+/// 
+/// La plataforma custodia tokens reales (cualquier token SEP-41,
+/// por ejemplo un Stellar Asset Contract):
+/// - Donantes `depositar` y luego `donar`, o usan `donar_directo`
+/// - Beneficiarias `retirar` lo que recibieron
+/// - Campañas: aportes en custodia hasta la fecha límite
+/// - Pools de igualación: un patrocinador suma un extra a cada
+///   donación elegible hecha con `donar` o `donar_directo`
+/// - Admin: pausa, comisión hacia una tesorería, nombre y traspaso
+///   del rol en dos pasos
+/// - Siempre se cumple: balance del contrato en el token ==
+///   Σ BalanceDonante + Σ SaldoBeneficiaria + Σ aportes a campañas
+///   no reclamados ni reembolsados + Σ fondos restantes de pools
+///   activos == TotalCustodia
 #[contract]
 pub struct PlataformaDonaciones;
 
 #[contractimpl]
 impl PlataformaDonaciones {
     /// Inicializar (una sola vez)
-    pub fn initialize(env: Env, admin: Address, nombre: Symbol) -> Result<(), Error> {
+    /// 
+    /// `token` es la dirección del contrato del token que se custodia.
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        nombre: Symbol,
+        token: Address,
+    ) -> Result<(), Error> {
         // Verificar que no esté ya inicializado
         if env.storage().instance().has(&DataKeyDonaciones::Admin) {
             return Err(Error::YaInicializado);
@@ -345,6 +519,8 @@ impl PlataformaDonaciones {
         env.storage().instance().set(&DataKeyDonaciones::Admin, &admin);
         env.storage().instance().set(&DataKeyDonaciones::NombrePlataforma, &nombre);
         env.storage().instance().set(&DataKeyDonaciones::TotalDonaciones, &0u32);
+        env.storage().instance().set(&DataKeyDonaciones::Token, &token);
+        env.storage().instance().set(&DataKeyDonaciones::TotalCustodia, &0i128);
         
         // Extender TTL de configuración
         env.storage().instance().extend_ttl(100, 100);
@@ -352,232 +528,1524 @@ impl PlataformaDonaciones {
         Ok(())
     }
     
-    /// Donar (operación crítica con todos los patrones)
-    pub fn donar(
-        env: Env,
-        donante: Address,
-        beneficiaria: Address,
-        monto: i128,
-    ) -> Result<(), Error> {
-        // 1. Validaciones
-        donante.require_auth();
-        
-        if monto <= 0 {
-            return Err(Error::MontoInvalido);
-        }
+    /// Admin: pausar depósitos y donaciones
+    /// 
+    /// Retiros, reclamos y reembolsos siguen funcionando para que
+    /// nadie quede con fondos bloqueados.
+    pub fn pausar(env: Env) -> Result<(), Error> {
+        let admin = Self::require_admin(&env)?;
         
-        // 2. Actualizar balance del donante (Persistent Storage)
-        let balance_donante = env.storage()
-            .persistent()
-            .get(&DataKeyDonaciones::BalanceDonante(donante.clone()))
-            .unwrap_or(0);
-        
-        if balance_donante < monto {
-            return Err(Error::BalanceInsuficiente);
-        }
+        env.storage().instance().set(&DataKeyDonaciones::Pausado, &true);
+        env.storage().instance().extend_ttl(100, 100);
         
-        let nuevo_balance_donante = balance_donante
-            .checked_sub(monto)
-            .ok_or(Error::BalanceInsuficiente)?;
+        env.events().publish((symbol_short!("pausado"), admin), true);
         
-        env.storage()
-            .persistent()
-            .set(&DataKeyDonaciones::BalanceDonante(donante.clone()), &nuevo_balance_donante);
+        Ok(())
+    }
+    
+    /// Admin: reanudar depósitos y donaciones
+    pub fn reanudar(env: Env) -> Result<(), Error> {
+        let admin = Self::require_admin(&env)?;
         
-        // 3. Actualizar donaciones recibidas por beneficiaria (Persistent Storage)
-        let total_recibido = env.storage()
-            .persistent()
-            .get(&DataKeyDonaciones::DonacionesRecibidas(beneficiaria.clone()))
-            .unwrap_or(0);
+        env.storage().instance().set(&DataKeyDonaciones::Pausado, &false);
+        env.storage().instance().extend_ttl(100, 100);
         
-        let nuevo_total_recibido = total_recibido
-            .checked_add(monto)
-            .ok_or(Error::NoInicializado)?;
+        env.events().publish((symbol_short!("pausado"), admin), false);
         
+        Ok(())
+    }
+    
+    /// Consulta: ¿Está pausada la plataforma?
+    pub fn esta_pausado(env: Env) -> bool {
         env.storage()
-            .persistent()
-            .set(&DataKeyDonaciones::DonacionesRecibidas(beneficiaria.clone()), &nuevo_total_recibido);
-        
-        // 4. Guardar detalle de donación (Persistent Storage con struct)
-        let id_donacion: u32 = env.storage()
             .instance()
-            .get(&DataKeyDonaciones::TotalDonaciones)
-            .unwrap_or(0);
+            .get(&DataKeyDonaciones::Pausado)
+            .unwrap_or(false)
+    }
+    
+    /// Admin: configurar la comisión (en puntos básicos) y la tesorería
+    /// 
+    /// La comisión se descuenta de cada donación (y de lo reclamado
+    /// por campañas) y se transfiere a `tesoreria`. Con `0` no se cobra.
+    pub fn configurar_comision(
+        env: Env,
+        comision_bps: u32,
+        tesoreria: Address,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env)?;
         
-        let donacion = DonacionInfo {
-            donante: donante.clone(),
-            beneficiaria: beneficiaria.clone(),
-            monto,
-            timestamp: env.ledger().timestamp(),
-        };
+        if comision_bps > MAX_COMISION_BPS {
+            return Err(Error::ComisionInvalida);
+        }
         
-        env.storage()
-            .persistent()
-            .set(&DataKeyDonaciones::Donacion(id_donacion), &donacion);
+        env.storage().instance().set(&DataKeyDonaciones::ComisionBps, &comision_bps);
+        env.storage().instance().set(&DataKeyDonaciones::Tesoreria, &tesoreria);
+        env.storage().instance().extend_ttl(100, 100);
         
-        // 5. Incrementar contador global (Instance Storage)
-        let nuevo_total = id_donacion
-            .checked_add(1)
-            .ok_or(Error::NoInicializado)?;
+        env.events().publish((symbol_short!("comision"), tesoreria), comision_bps);
         
+        Ok(())
+    }
+    
+    /// Consulta: Comisión actual en puntos básicos
+    pub fn get_comision_bps(env: Env) -> u32 {
         env.storage()
             .instance()
-            .set(&DataKeyDonaciones::TotalDonaciones, &nuevo_total);
-        
-        // 6. Extender TTL de datos críticos (después de operaciones exitosas)
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKeyDonaciones::BalanceDonante(donante.clone()), 100, 100);
-        
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKeyDonaciones::DonacionesRecibidas(beneficiaria.clone()), 100, 100);
+            .get(&DataKeyDonaciones::ComisionBps)
+            .unwrap_or(0)
+    }
+    
+    /// Consulta: Tesorería que recibe las comisiones
+    pub fn get_tesoreria(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKeyDonaciones::Tesoreria)
+    }
+    
+    /// Admin: configurar los fondos mínimos para crear un pool
+    /// 
+    /// Depende de los decimales del token custodiado. No afecta a los
+    /// pools ya creados.
+    pub fn configurar_fondos_minimos_pool(env: Env, minimo: i128) -> Result<(), Error> {
+        Self::require_admin(&env)?;
         
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKeyDonaciones::Donacion(id_donacion), 100, 100);
+        if minimo <= 0 {
+            return Err(Error::MontoInvalido);
+        }
         
-        // 7. Extender TTL de instance storage
+        env.storage().instance().set(&DataKeyDonaciones::FondosMinimosPool, &minimo);
         env.storage().instance().extend_ttl(100, 100);
         
+        env.events().publish((symbol_short!("pool_min"),), minimo);
+        
         Ok(())
     }
     
-    /// Consulta: Obtener balance del donante
-    pub fn get_balance_donante(env: Env, donante: Address) -> i128 {
-        env.storage()
-            .persistent()
-            .get(&DataKeyDonaciones::BalanceDonante(donante))
-            .unwrap_or(0)
-    }
-    
-    /// Consulta: Obtener total recibido por beneficiaria
-    pub fn get_total_recibido(env: Env, beneficiaria: Address) -> i128 {
+    /// Consulta: Fondos mínimos para crear un pool
+    pub fn get_fondos_minimos_pool(env: Env) -> i128 {
         env.storage()
-            .persistent()
-            .get(&DataKeyDonaciones::DonacionesRecibidas(beneficiaria))
-            .unwrap_or(0)
+            .instance()
+            .get(&DataKeyDonaciones::FondosMinimosPool)
+            .unwrap_or(FONDOS_MINIMOS_POOL)
     }
     
-    /// Consulta: Obtener información de donación por ID
-    pub fn get_donacion(env: Env, id: u32) -> Option<DonacionInfo> {
-        env.storage()
-            .persistent()
-            .get(&DataKeyDonaciones::Donacion(id))
+    /// Admin: cambiar el nombre de la plataforma
+    pub fn cambiar_nombre(env: Env, nombre: Symbol) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        
+        env.storage().instance().set(&DataKeyDonaciones::NombrePlataforma, &nombre);
+        env.storage().instance().extend_ttl(100, 100);
+        
+        env.events().publish((symbol_short!("nombre"),), nombre);
+        
+        Ok(())
     }
     
-    /// Consulta: Obtener total de donaciones (global)
-    pub fn get_total_donaciones(env: Env) -> u32 {
+    /// Consulta: Nombre de la plataforma
+    pub fn get_nombre(env: Env) -> Result<Symbol, Error> {
         env.storage()
             .instance()
-            .get(&DataKeyDonaciones::TotalDonaciones)
-            .unwrap_or(0)
+            .get(&DataKeyDonaciones::NombrePlataforma)
+            .ok_or(Error::NoInicializado)
     }
     
-    /// Helper: Establecer balance inicial (para testing)
-    pub fn establecer_balance(env: Env, donante: Address, balance: i128) {
-        env.storage()
-            .persistent()
-            .set(&DataKeyDonaciones::BalanceDonante(donante.clone()), &balance);
+    /// Admin: proponer un nuevo admin (paso 1 de 2)
+    /// 
+    /// El admin actual sigue siéndolo hasta que el propuesto acepte.
+    /// Proponer de nuevo reemplaza la propuesta anterior.
+    pub fn proponer_admin(env: Env, nuevo_admin: Address) -> Result<(), Error> {
+        let admin = Self::require_admin(&env)?;
         
-        // Extender TTL
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKeyDonaciones::BalanceDonante(donante), 100, 100);
+        env.storage().instance().set(&DataKeyDonaciones::AdminPendiente, &nuevo_admin);
+        env.storage().instance().extend_ttl(100, 100);
+        
+        env.events().publish((symbol_short!("adm_prop"), admin), nuevo_admin);
+        
+        Ok(())
     }
     
-    /// Verificar si donante existe (patrón de verificación)
-    pub fn donante_existe(env: Env, donante: Address) -> bool {
-        env.storage()
-            .persistent()
-            .has(&DataKeyDonaciones::BalanceDonante(donante))
-    }
-}
-
-// ============================================================
-// EJEMPLO 5: Patrón de Datos Relacionados
-// ============================================================
-
-/// Ejemplo del patrón de eliminar datos relacionados juntos
-#[contract]
-pub struct GestionUsuario;
-
-/// DataKey para gestión de usuario
-#[contracttype]
-#[derive(Clone)]
-pub enum DataKeyUsuario {
-    Balance(Address),
-    UltimaDonacion(Address),
-    TotalDonado(Address),
-    Perfil(Address),
-}
-
-#[contractimpl]
-impl GestionUsuario {
-    /// Crear usuario con múltiples datos relacionados
-    pub fn crear_usuario(
-        env: Env,
-        usuario: Address,
-        balance: i128,
-        total_donado: i128,
-    ) {
-        // Guardar múltiples datos relacionados
-        env.storage()
-            .persistent()
-            .set(&DataKeyUsuario::Balance(usuario.clone()), &balance);
+    /// El admin propuesto acepta el rol (paso 2 de 2)
+    pub fn aceptar_admin(env: Env) -> Result<(), Error> {
+        let nuevo_admin: Address = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::AdminPendiente)
+            .ok_or(Error::SinAdminPendiente)?;
         
-        env.storage()
-            .persistent()
-            .set(&DataKeyUsuario::TotalDonado(usuario.clone()), &total_donado);
+        nuevo_admin.require_auth();
         
-        // Extender TTL de todos los datos relacionados
-        env.storage()
-            .persistent()
-            .extend_ttl(&DataKeyUsuario::Balance(usuario.clone()), 100, 100);
+        let anterior: Address = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::Admin)
+            .ok_or(Error::NoInicializado)?;
         
+        env.storage().instance().set(&DataKeyDonaciones::Admin, &nuevo_admin);
+        env.storage().instance().remove(&DataKeyDonaciones::AdminPendiente);
+        env.storage().instance().extend_ttl(100, 100);
+        
+        env.events().publish((symbol_short!("adm_nuevo"), anterior), nuevo_admin);
+        
+        Ok(())
+    }
+    
+    /// Consulta: Admin actual
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
         env.storage()
-            .persistent()
-            .extend_ttl(&DataKeyUsuario::TotalDonado(usuario.clone()), 100, 100);
+            .instance()
+            .get(&DataKeyDonaciones::Admin)
+            .ok_or(Error::NoInicializado)
     }
     
-    /// Eliminar usuario (patrón de eliminar datos relacionados)
-    pub fn eliminar_usuario(env: Env, usuario: Address) -> Result<(), Error> {
-        // Verificar que el usuario existe
-        if !env.storage().persistent().has(&DataKeyUsuario::Balance(usuario.clone())) {
-            return Err(Error::UsuarioNoExiste);
+    /// Consulta: Admin propuesto que aún no aceptó
+    pub fn get_admin_pendiente(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKeyDonaciones::AdminPendiente)
+    }
+    
+    /// Depositar tokens en la plataforma para donarlos después
+    /// 
+    /// Transfiere `monto` del donante al contrato y lo acredita
+    /// en su `BalanceDonante`.
+    pub fn depositar(env: Env, donante: Address, monto: i128) -> Result<(), Error> {
+        // 1. Validaciones
+        donante.require_auth();
+        Self::verificar_no_pausado(&env)?;
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
         }
         
-        // Eliminar todos los datos relacionados juntos
-        env.storage()
+        // 2. Mover tokens reales: donante → contrato
+        Self::token_client(&env)?.transfer(
+            &donante,
+            &env.current_contract_address(),
+            &monto,
+        );
+        
+        // 3. Acreditar depósito (Persistent Storage)
+        let balance_donante: i128 = env.storage()
             .persistent()
-            .remove(&DataKeyUsuario::Balance(usuario.clone()));
+            .get(&DataKeyDonaciones::BalanceDonante(donante.clone()))
+            .unwrap_or(0);
+        
+        let nuevo_balance_donante = balance_donante
+            .checked_add(monto)
+            .ok_or(Error::Overflow)?;
         
         env.storage()
             .persistent()
-            .remove(&DataKeyUsuario::UltimaDonacion(usuario.clone()));
-        
+            .set(&DataKeyDonaciones::BalanceDonante(donante.clone()), &nuevo_balance_donante);
         env.storage()
             .persistent()
-            .remove(&DataKeyUsuario::TotalDonado(usuario.clone()));
+            .extend_ttl(&DataKeyDonaciones::BalanceDonante(donante.clone()), 100, 100);
         
-        // Si existe perfil, también eliminarlo
-        if env.storage().persistent().has(&DataKeyUsuario::Perfil(usuario.clone())) {
-            env.storage()
-                .persistent()
-                .remove(&DataKeyUsuario::Perfil(usuario));
-        }
+        Self::ajustar_custodia(&env, monto)?;
+        
+        env.events().publish(
+            (symbol_short!("deposito"), donante),
+            monto,
+        );
         
         Ok(())
     }
     
-    /// Verificar si usuario existe
-    pub fn usuario_existe(env: Env, usuario: Address) -> bool {
-        env.storage()
-            .persistent()
-            .has(&DataKeyUsuario::Balance(usuario))
-    }
-    
-    /// Obtener balance
+    /// Retirar tokens depositados que aún no se donaron
+    pub fn retirar_deposito(env: Env, donante: Address, monto: i128) -> Result<(), Error> {
+        donante.require_auth();
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        Self::descontar_balance_donante(&env, &donante, monto)?;
+        Self::ajustar_custodia(&env, -monto)?;
+        
+        // Mover tokens reales: contrato → donante
+        Self::token_client(&env)?.transfer(
+            &env.current_contract_address(),
+            &donante,
+            &monto,
+        );
+        
+        env.events().publish(
+            (symbol_short!("dep_ret"), donante),
+            monto,
+        );
+        
+        Ok(())
+    }
+    
+    /// Donar desde el balance depositado (operación crítica con todos los patrones)
+    pub fn donar(
+        env: Env,
+        donante: Address,
+        beneficiaria: Address,
+        monto: i128,
+    ) -> Result<(), Error> {
+        // 1. Validaciones
+        donante.require_auth();
+        Self::verificar_no_pausado(&env)?;
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        // 2. Actualizar balance del donante (Persistent Storage)
+        Self::descontar_balance_donante(&env, &donante, monto)?;
+        
+        // 3. Acreditar a la beneficiaria y guardar el detalle
+        Self::registrar_donacion(&env, &donante, &beneficiaria, monto)?;
+        
+        Ok(())
+    }
+    
+    /// Donar directamente con una transferencia del token
+    /// 
+    /// No requiere depósito previo: los tokens pasan del donante
+    /// al contrato y quedan disponibles para la beneficiaria.
+    pub fn donar_directo(
+        env: Env,
+        donante: Address,
+        beneficiaria: Address,
+        monto: i128,
+    ) -> Result<(), Error> {
+        donante.require_auth();
+        Self::verificar_no_pausado(&env)?;
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        Self::token_client(&env)?.transfer(
+            &donante,
+            &env.current_contract_address(),
+            &monto,
+        );
+        Self::ajustar_custodia(&env, monto)?;
+        
+        Self::registrar_donacion(&env, &donante, &beneficiaria, monto)?;
+        
+        Ok(())
+    }
+    
+    /// Retirar lo recibido en donaciones
+    /// 
+    /// Solo la beneficiaria puede retirar su propio saldo.
+    pub fn retirar(env: Env, beneficiaria: Address, monto: i128) -> Result<(), Error> {
+        beneficiaria.require_auth();
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        let key = DataKeyDonaciones::SaldoBeneficiaria(beneficiaria.clone());
+        let saldo: i128 = env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(0);
+        
+        if saldo < monto {
+            return Err(Error::BalanceInsuficiente);
+        }
+        
+        env.storage().persistent().set(&key, &(saldo - monto));
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+        Self::ajustar_custodia(&env, -monto)?;
+        
+        // Mover tokens reales: contrato → beneficiaria
+        Self::token_client(&env)?.transfer(
+            &env.current_contract_address(),
+            &beneficiaria,
+            &monto,
+        );
+        
+        env.events().publish(
+            (symbol_short!("retiro"), beneficiaria),
+            monto,
+        );
+        
+        Ok(())
+    }
+    
+    /// Crear una campaña con meta y fecha límite
+    /// 
+    /// Devuelve el ID de la campaña.
+    pub fn crear_campana(
+        env: Env,
+        beneficiaria: Address,
+        meta: i128,
+        fecha_limite: u64,
+    ) -> Result<u32, Error> {
+        beneficiaria.require_auth();
+        
+        if meta <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        if fecha_limite <= env.ledger().timestamp() {
+            return Err(Error::FechaInvalida);
+        }
+        
+        let id_campana: u32 = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::TotalCampanas)
+            .unwrap_or(0);
+        
+        let nuevo_total = id_campana
+            .checked_add(1)
+            .ok_or(Error::Overflow)?;
+        
+        let campana = CampanaInfo {
+            beneficiaria: beneficiaria.clone(),
+            meta,
+            fecha_limite,
+            recaudado: 0,
+            reclamada: false,
+        };
+        
+        Self::guardar_campana(&env, id_campana, &campana);
+        env.storage()
+            .instance()
+            .set(&DataKeyDonaciones::TotalCampanas, &nuevo_total);
+        env.storage().instance().extend_ttl(100, 100);
+        
+        env.events().publish(
+            (symbol_short!("campana"), beneficiaria, id_campana),
+            meta,
+        );
+        
+        Ok(id_campana)
+    }
+    
+    /// Aportar a una campaña (solo antes de la fecha límite)
+    /// 
+    /// Los tokens quedan en custodia del contrato hasta que la
+    /// beneficiaria los reclame o el donante pida el reembolso.
+    pub fn donar_campana(
+        env: Env,
+        donante: Address,
+        id_campana: u32,
+        monto: i128,
+    ) -> Result<(), Error> {
+        donante.require_auth();
+        Self::verificar_no_pausado(&env)?;
+        
+        if monto <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        let mut campana = Self::leer_campana(&env, id_campana)?;
+        
+        if env.ledger().timestamp() >= campana.fecha_limite {
+            return Err(Error::CampanaCerrada);
+        }
+        
+        // 1. Mover tokens reales: donante → contrato (escrow)
+        Self::token_client(&env)?.transfer(
+            &donante,
+            &env.current_contract_address(),
+            &monto,
+        );
+        Self::ajustar_custodia(&env, monto)?;
+        
+        // 2. Registrar el aporte del donante
+        let key_aporte = DataKeyDonaciones::AporteCampana(id_campana, donante.clone());
+        let aporte: i128 = env.storage()
+            .persistent()
+            .get(&key_aporte)
+            .unwrap_or(0);
+        
+        let nuevo_aporte = aporte
+            .checked_add(monto)
+            .ok_or(Error::Overflow)?;
+        
+        // El aporte tiene que sobrevivir hasta que se pueda reembolsar
+        let ttl = Self::ttl_campana(&env, &campana);
+        env.storage().persistent().set(&key_aporte, &nuevo_aporte);
+        env.storage().persistent().extend_ttl(&key_aporte, ttl, ttl);
+        
+        // 3. Actualizar lo recaudado por la campaña
+        campana.recaudado = campana.recaudado
+            .checked_add(monto)
+            .ok_or(Error::Overflow)?;
+        Self::guardar_campana(&env, id_campana, &campana);
+        
+        // 4. Guardar el detalle en el historial
+        Self::guardar_donacion(
+            &env,
+            ttl,
+            &DonacionInfo {
+                donante: donante.clone(),
+                beneficiaria: campana.beneficiaria.clone(),
+                monto,
+                timestamp: env.ledger().timestamp(),
+                campana: Some(id_campana),
+                monto_igualado: 0,
+                comision: 0,    // Se cobra al reclamar
+            },
+        )?;
+        
+        env.events().publish(
+            (symbol_short!("aporte"), donante, id_campana),
+            monto,
+        );
+        
+        Ok(())
+    }
+    
+    /// La beneficiaria reclama lo recaudado
+    /// 
+    /// Solo después de la fecha límite, si se alcanzó la meta y
+    /// una sola vez. Devuelve lo recibido, ya descontada la comisión.
+    pub fn reclamar_campana(env: Env, id_campana: u32) -> Result<i128, Error> {
+        let mut campana = Self::leer_campana(&env, id_campana)?;
+        campana.beneficiaria.require_auth();
+        
+        if env.ledger().timestamp() < campana.fecha_limite {
+            return Err(Error::CampanaEnCurso);
+        }
+        
+        if campana.recaudado < campana.meta {
+            return Err(Error::MetaNoAlcanzada);
+        }
+        
+        if campana.reclamada {
+            return Err(Error::YaReclamada);
+        }
+        
+        // 1. Marcar antes de mover tokens
+        campana.reclamada = true;
+        Self::guardar_campana(&env, id_campana, &campana);
+        
+        // 2. Comisión de la plataforma sobre lo recaudado
+        let comision = Self::cobrar_comision(&env, campana.recaudado)?;
+        let neto = campana.recaudado - comision;
+        Self::ajustar_custodia(&env, -neto)?;
+        
+        // 3. Sumar al total histórico recibido
+        let key_recibido = DataKeyDonaciones::DonacionesRecibidas(campana.beneficiaria.clone());
+        let total_recibido: i128 = env.storage()
+            .persistent()
+            .get(&key_recibido)
+            .unwrap_or(0);
+        
+        let nuevo_total_recibido = total_recibido
+            .checked_add(neto)
+            .ok_or(Error::Overflow)?;
+        
+        env.storage().persistent().set(&key_recibido, &nuevo_total_recibido);
+        env.storage().persistent().extend_ttl(&key_recibido, 100, 100);
+        
+        // 4. Mover tokens reales: contrato → beneficiaria
+        Self::token_client(&env)?.transfer(
+            &env.current_contract_address(),
+            &campana.beneficiaria,
+            &neto,
+        );
+        
+        env.events().publish(
+            (symbol_short!("reclamo"), campana.beneficiaria, id_campana),
+            neto,
+        );
+        
+        Ok(neto)
+    }
+    
+    /// Un donante recupera su aporte a una campaña fallida
+    /// 
+    /// Solo después de la fecha límite y si no se alcanzó la meta.
+    /// Devuelve exactamente lo que el donante aportó.
+    pub fn reembolsar_campana(
+        env: Env,
+        donante: Address,
+        id_campana: u32,
+    ) -> Result<i128, Error> {
+        donante.require_auth();
+        
+        let campana = Self::leer_campana(&env, id_campana)?;
+        
+        if env.ledger().timestamp() < campana.fecha_limite {
+            return Err(Error::CampanaEnCurso);
+        }
+        
+        if campana.recaudado >= campana.meta {
+            return Err(Error::MetaAlcanzada);
+        }
+        
+        let key_aporte = DataKeyDonaciones::AporteCampana(id_campana, donante.clone());
+        let aporte: i128 = env.storage()
+            .persistent()
+            .get(&key_aporte)
+            .unwrap_or(0);
+        
+        if aporte <= 0 {
+            return Err(Error::SinAporte);
+        }
+        
+        // 1. Borrar el aporte antes de mover tokens (no se puede reembolsar dos veces)
+        env.storage().persistent().remove(&key_aporte);
+        Self::ajustar_custodia(&env, -aporte)?;
+        
+        // 2. Mover tokens reales: contrato → donante
+        Self::token_client(&env)?.transfer(
+            &env.current_contract_address(),
+            &donante,
+            &aporte,
+        );
+        
+        env.events().publish(
+            (symbol_short!("reembolso"), donante, id_campana),
+            aporte,
+        );
+        
+        Ok(aporte)
+    }
+    
+    /// Crear un pool de igualación
+    /// 
+    /// El patrocinador transfiere `fondos` al contrato (al menos
+    /// `get_fondos_minimos_pool`). `beneficiarias` no puede estar
+    /// vacía, ni tener más de `MAX_BENEFICIARIAS_POOL` direcciones, ni
+    /// repetidas. Devuelve el ID del pool.
+    pub fn crear_pool(
+        env: Env,
+        patrocinador: Address,
+        ratio_bps: u32,
+        tope_por_donacion: i128,
+        fondos: i128,
+        beneficiarias: Vec<Address>,
+    ) -> Result<u32, Error> {
+        patrocinador.require_auth();
+        
+        if ratio_bps == 0 {
+            return Err(Error::RatioInvalido);
+        }
+        
+        if tope_por_donacion <= 0 || fondos <= 0 {
+            return Err(Error::MontoInvalido);
+        }
+        
+        if fondos < Self::get_fondos_minimos_pool(env.clone()) {
+            return Err(Error::FondosPoolInsuficientes);
+        }
+        
+        if beneficiarias.is_empty() {
+            return Err(Error::PoolSinBeneficiarias);
+        }
+        
+        if beneficiarias.len() > MAX_BENEFICIARIAS_POOL {
+            return Err(Error::DemasiadasBeneficiarias);
+        }
+        
+        let mut activos = Self::pools_activos(&env);
+        if activos.len() >= MAX_POOLS_ACTIVOS {
+            return Err(Error::DemasiadosPools);
+        }
+        
+        // 1. Asignar ID y registrar las beneficiarias (una key por
+        //    dirección, rechaza repetidas)
+        let id_pool: u32 = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::TotalPools)
+            .unwrap_or(0);
+        
+        let nuevo_total = id_pool
+            .checked_add(1)
+            .ok_or(Error::Overflow)?;
+        
+        let ttl = Self::ttl_pool(&env);
+        for beneficiaria in beneficiarias.iter() {
+            let key = DataKeyDonaciones::PoolBeneficiaria(id_pool, beneficiaria);
+            if env.storage().persistent().has(&key) {
+                return Err(Error::BeneficiariaDuplicada);
+            }
+            env.storage().persistent().set(&key, &true);
+            env.storage().persistent().extend_ttl(&key, ttl / 2, ttl);
+        }
+        
+        // 2. Mover tokens reales: patrocinador → contrato
+        Self::token_client(&env)?.transfer(
+            &patrocinador,
+            &env.current_contract_address(),
+            &fondos,
+        );
+        Self::ajustar_custodia(&env, fondos)?;
+        
+        // 3. Guardar el pool y activarlo
+        let pool = PoolIgualacion {
+            patrocinador: patrocinador.clone(),
+            ratio_bps,
+            tope_por_donacion,
+            fondos,
+            igualado: 0,
+            beneficiarias: beneficiarias.len(),
+            activo: true,
+        };
+        
+        Self::guardar_pool(&env, id_pool, &pool);
+        activos.push_back(id_pool);
+        env.storage()
+            .instance()
+            .set(&DataKeyDonaciones::PoolsActivos, &activos);
+        env.storage()
+            .instance()
+            .set(&DataKeyDonaciones::TotalPools, &nuevo_total);
+        env.storage().instance().extend_ttl(ttl / 2, ttl);
+        
+        env.events().publish(
+            (symbol_short!("pool"), patrocinador, id_pool),
+            fondos,
+        );
+        
+        Ok(id_pool)
+    }
+    
+    /// Cerrar un pool y devolver al patrocinador lo que no se usó
+    pub fn cerrar_pool(env: Env, id_pool: u32) -> Result<i128, Error> {
+        let mut pool = Self::leer_pool(&env, id_pool)?;
+        pool.patrocinador.require_auth();
+        
+        if !pool.activo {
+            return Err(Error::PoolCerrado);
+        }
+        
+        let restante = pool.fondos - pool.igualado;
+        
+        // 1. Desactivar antes de mover tokens
+        pool.activo = false;
+        Self::guardar_pool(&env, id_pool, &pool);
+        Self::quitar_pool_activo(&env, id_pool);
+        
+        // 2. Devolver el remanente (si queda)
+        if restante > 0 {
+            Self::ajustar_custodia(&env, -restante)?;
+            Self::token_client(&env)?.transfer(
+                &env.current_contract_address(),
+                &pool.patrocinador,
+                &restante,
+            );
+        }
+        
+        env.events().publish(
+            (symbol_short!("pool_fin"), pool.patrocinador, id_pool),
+            restante,
+        );
+        
+        Ok(restante)
+    }
+    
+    /// Consulta: Obtener un pool de igualación
+    pub fn get_pool(env: Env, id_pool: u32) -> Option<PoolIgualacion> {
+        Self::leer_pool(&env, id_pool).ok()
+    }
+    
+    /// Consulta: ¿`direccion` es beneficiaria elegible del pool?
+    pub fn es_beneficiaria_pool(env: Env, id_pool: u32, direccion: Address) -> bool {
+        Self::es_beneficiaria(&env, id_pool, &direccion)
+    }
+    
+    /// Consulta: IDs de los pools que todavía igualan donaciones
+    pub fn get_pools_activos(env: Env) -> Vec<u32> {
+        Self::pools_activos(&env)
+    }
+    
+    /// Consulta: Total igualado por pools a una beneficiaria
+    /// 
+    /// `get_total_recibido` solo cuenta lo aportado por donantes.
+    pub fn get_total_igualado(env: Env, beneficiaria: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::IgualadoRecibido(beneficiaria))
+            .unwrap_or(0)
+    }
+    
+    /// Consulta: Obtener una campaña
+    pub fn get_campana(env: Env, id_campana: u32) -> Option<CampanaInfo> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::Campana(id_campana))
+    }
+    
+    /// Consulta: Aporte de un donante a una campaña (0 si ya fue reembolsado)
+    pub fn get_aporte_campana(env: Env, id_campana: u32, donante: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::AporteCampana(id_campana, donante))
+            .unwrap_or(0)
+    }
+    
+    /// Consulta: Obtener balance del donante
+    pub fn get_balance_donante(env: Env, donante: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::BalanceDonante(donante))
+            .unwrap_or(0)
+    }
+    
+    /// Consulta: Obtener total recibido por beneficiaria
+    pub fn get_total_recibido(env: Env, beneficiaria: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::DonacionesRecibidas(beneficiaria))
+            .unwrap_or(0)
+    }
+    
+    /// Consulta: Saldo que la beneficiaria todavía puede retirar
+    pub fn get_saldo_disponible(env: Env, beneficiaria: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::SaldoBeneficiaria(beneficiaria))
+            .unwrap_or(0)
+    }
+    
+    /// Consulta: Tokens en custodia según el registro interno
+    /// 
+    /// Debe coincidir con el balance del contrato en el token.
+    pub fn get_total_custodia(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::TotalCustodia)
+            .unwrap_or(0)
+    }
+    
+    /// Consulta: Token custodiado
+    pub fn get_token(env: Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::Token)
+            .ok_or(Error::NoInicializado)
+    }
+    
+    /// Consulta: Obtener información de donación por ID
+    pub fn get_donacion(env: Env, id: u32) -> Option<DonacionInfo> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::Donacion(id))
+    }
+    
+    /// Consulta: Obtener total de donaciones (global)
+    pub fn get_total_donaciones(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::TotalDonaciones)
+            .unwrap_or(0)
+    }
+    
+    /// Consulta paginada: donaciones hechas por un donante
+    /// 
+    /// Empezar con `cursor = 0` y seguir con `pagina.siguiente`
+    /// hasta que sea `None`. `limite` va de 1 a `MAX_PAGINA`.
+    pub fn donaciones_de_donante(
+        env: Env,
+        donante: Address,
+        cursor: u32,
+        limite: u32,
+    ) -> Result<PaginaDonaciones, Error> {
+        Self::paginar(&env, &Indice::Donante(donante), 0, u64::MAX, cursor, limite)
+    }
+    
+    /// Consulta paginada: donaciones recibidas por una beneficiaria
+    pub fn donaciones_a_beneficiaria(
+        env: Env,
+        beneficiaria: Address,
+        cursor: u32,
+        limite: u32,
+    ) -> Result<PaginaDonaciones, Error> {
+        Self::paginar(&env, &Indice::Beneficiaria(beneficiaria), 0, u64::MAX, cursor, limite)
+    }
+    
+    /// Consulta paginada: todas las donaciones con `desde <= timestamp <= hasta`
+    pub fn donaciones_por_fecha(
+        env: Env,
+        desde: u64,
+        hasta: u64,
+        cursor: u32,
+        limite: u32,
+    ) -> Result<PaginaDonaciones, Error> {
+        Self::paginar(&env, &Indice::Global, desde, hasta, cursor, limite)
+    }
+    
+    /// Consulta paginada: donaciones de un donante dentro de un rango de fechas
+    pub fn donaciones_de_donante_por_fecha(
+        env: Env,
+        donante: Address,
+        desde: u64,
+        hasta: u64,
+        cursor: u32,
+        limite: u32,
+    ) -> Result<PaginaDonaciones, Error> {
+        Self::paginar(&env, &Indice::Donante(donante), desde, hasta, cursor, limite)
+    }
+    
+    /// Consulta paginada: donaciones a una beneficiaria dentro de un rango de fechas
+    pub fn donaciones_recibidas_por_fecha(
+        env: Env,
+        beneficiaria: Address,
+        desde: u64,
+        hasta: u64,
+        cursor: u32,
+        limite: u32,
+    ) -> Result<PaginaDonaciones, Error> {
+        Self::paginar(&env, &Indice::Beneficiaria(beneficiaria), desde, hasta, cursor, limite)
+    }
+    
+    /// Verificar si donante existe (patrón de verificación)
+    pub fn donante_existe(env: Env, donante: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKeyDonaciones::BalanceDonante(donante))
+    }
+}
+
+// Helpers privados de PlataformaDonaciones
+impl PlataformaDonaciones {
+    /// Cliente del token custodiado
+    fn token_client(env: &Env) -> Result<token::Client<'_>, Error> {
+        let token: Address = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::Token)
+            .ok_or(Error::NoInicializado)?;
+        Ok(token::Client::new(env, &token))
+    }
+    
+    /// Verifica la firma del admin guardado y lo devuelve
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::Admin)
+            .ok_or(Error::NoInicializado)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+    
+    /// Falla si el admin pausó la plataforma
+    fn verificar_no_pausado(env: &Env) -> Result<(), Error> {
+        let pausado: bool = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::Pausado)
+            .unwrap_or(false);
+        
+        if pausado {
+            return Err(Error::Pausado);
+        }
+        
+        Ok(())
+    }
+    
+    /// Transfiere la comisión sobre `monto` a la tesorería
+    /// 
+    /// Los tokens ya deben estar en custodia. Devuelve la comisión
+    /// cobrada (0 si no hay comisión configurada).
+    fn cobrar_comision(env: &Env, monto: i128) -> Result<i128, Error> {
+        let comision_bps: u32 = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::ComisionBps)
+            .unwrap_or(0);
+        
+        if comision_bps == 0 {
+            return Ok(0);
+        }
+        
+        let tesoreria: Address = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::Tesoreria)
+            .ok_or(Error::NoInicializado)?;
+        
+        let comision = monto
+            .checked_mul(comision_bps as i128)
+            .ok_or(Error::Overflow)?
+            / BPS_BASE;
+        
+        if comision > 0 {
+            Self::ajustar_custodia(env, -comision)?;
+            Self::token_client(env)?.transfer(
+                &env.current_contract_address(),
+                &tesoreria,
+                &comision,
+            );
+            
+            env.events().publish((symbol_short!("cobro"), tesoreria), comision);
+        }
+        
+        Ok(comision)
+    }
+    
+    /// Suma (o resta, si `delta` < 0) al total en custodia
+    fn ajustar_custodia(env: &Env, delta: i128) -> Result<(), Error> {
+        let total: i128 = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::TotalCustodia)
+            .unwrap_or(0);
+        
+        let nuevo_total = total
+            .checked_add(delta)
+            .ok_or(Error::Overflow)?;
+        
+        if nuevo_total < 0 {
+            return Err(Error::BalanceInsuficiente);
+        }
+        
+        env.storage()
+            .instance()
+            .set(&DataKeyDonaciones::TotalCustodia, &nuevo_total);
+        env.storage().instance().extend_ttl(100, 100);
+        
+        Ok(())
+    }
+    
+    /// Resta del depósito de un donante
+    fn descontar_balance_donante(env: &Env, donante: &Address, monto: i128) -> Result<(), Error> {
+        let balance_donante: i128 = env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::BalanceDonante(donante.clone()))
+            .unwrap_or(0);
+        
+        if balance_donante < monto {
+            return Err(Error::BalanceInsuficiente);
+        }
+        
+        let nuevo_balance_donante = balance_donante
+            .checked_sub(monto)
+            .ok_or(Error::BalanceInsuficiente)?;
+        
+        env.storage()
+            .persistent()
+            .set(&DataKeyDonaciones::BalanceDonante(donante.clone()), &nuevo_balance_donante);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKeyDonaciones::BalanceDonante(donante.clone()), 100, 100);
+        
+        Ok(())
+    }
+    
+    /// Acredita una donación a la beneficiaria y guarda su detalle
+    /// 
+    /// Los tokens ya deben estar en custodia del contrato.
+    fn registrar_donacion(
+        env: &Env,
+        donante: &Address,
+        beneficiaria: &Address,
+        monto: i128,
+    ) -> Result<(), Error> {
+        // 1. Comisión de la plataforma (sale de la custodia hacia la tesorería)
+        let comision = Self::cobrar_comision(env, monto)?;
+        let neto = monto - comision;
+        
+        // 2. Actualizar donaciones recibidas por beneficiaria (Persistent Storage)
+        let total_recibido: i128 = env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::DonacionesRecibidas(beneficiaria.clone()))
+            .unwrap_or(0);
+        
+        let nuevo_total_recibido = total_recibido
+            .checked_add(neto)
+            .ok_or(Error::Overflow)?;
+        
+        env.storage()
+            .persistent()
+            .set(&DataKeyDonaciones::DonacionesRecibidas(beneficiaria.clone()), &nuevo_total_recibido);
+        
+        // 3. Igualación del primer pool elegible (ya está en custodia)
+        let monto_igualado = Self::aplicar_igualacion(env, donante, beneficiaria, monto)?;
+        
+        // 4. Saldo retirable de la beneficiaria (Persistent Storage)
+        let saldo: i128 = env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::SaldoBeneficiaria(beneficiaria.clone()))
+            .unwrap_or(0);
+        
+        let nuevo_saldo = saldo
+            .checked_add(neto)
+            .and_then(|s| s.checked_add(monto_igualado))
+            .ok_or(Error::Overflow)?;
+        
+        env.storage()
+            .persistent()
+            .set(&DataKeyDonaciones::SaldoBeneficiaria(beneficiaria.clone()), &nuevo_saldo);
+        
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKeyDonaciones::DonacionesRecibidas(beneficiaria.clone()), 100, 100);
+        
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKeyDonaciones::SaldoBeneficiaria(beneficiaria.clone()), 100, 100);
+        
+        // 5. Guardar detalle de donación e índices
+        Self::guardar_donacion(
+            env,
+            TTL_MINIMO,
+            &DonacionInfo {
+                donante: donante.clone(),
+                beneficiaria: beneficiaria.clone(),
+                monto,
+                timestamp: env.ledger().timestamp(),
+                campana: None,
+                monto_igualado,
+                comision,
+            },
+        )?;
+        
+        env.events().publish(
+            (symbol_short!("donacion"), donante.clone(), beneficiaria.clone()),
+            monto,
+        );
+        
+        Ok(())
+    }
+    
+    /// Iguala una donación con el primer pool activo que la acepte
+    /// 
+    /// Devuelve el monto igualado (0 si ningún pool aplica). Los pools
+    /// que se agotan dejan de estar activos.
+    fn aplicar_igualacion(
+        env: &Env,
+        donante: &Address,
+        beneficiaria: &Address,
+        monto: i128,
+    ) -> Result<i128, Error> {
+        // Donarse a sí misma nunca se iguala
+        if donante == beneficiaria {
+            return Ok(0);
+        }
+        
+        for id_pool in Self::pools_activos(env).iter() {
+            let mut pool = Self::leer_pool(env, id_pool)?;
+            
+            if !Self::es_beneficiaria(env, id_pool, beneficiaria) {
+                continue;
+            }
+            
+            // Lo que dona una beneficiaria del pool puede venir del pool
+            if Self::es_beneficiaria(env, id_pool, donante) {
+                continue;
+            }
+            
+            let segun_ratio = monto
+                .checked_mul(pool.ratio_bps as i128)
+                .ok_or(Error::Overflow)?
+                / BPS_BASE;
+            let restante = pool.fondos - pool.igualado;
+            let igualado = segun_ratio
+                .min(pool.tope_por_donacion)
+                .min(restante);
+            
+            if igualado <= 0 {
+                continue;
+            }
+            
+            pool.igualado += igualado;
+            if pool.igualado == pool.fondos {
+                pool.activo = false;
+                Self::quitar_pool_activo(env, id_pool);
+            }
+            Self::guardar_pool(env, id_pool, &pool);
+            
+            // Total igualado a la beneficiaria (reporte separado)
+            let key = DataKeyDonaciones::IgualadoRecibido(beneficiaria.clone());
+            let total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            let nuevo_total = total
+                .checked_add(igualado)
+                .ok_or(Error::Overflow)?;
+            env.storage().persistent().set(&key, &nuevo_total);
+            env.storage().persistent().extend_ttl(&key, 100, 100);
+            
+            env.events().publish(
+                (symbol_short!("igualado"), beneficiaria.clone(), id_pool),
+                igualado,
+            );
+            
+            return Ok(igualado);
+        }
+        
+        Ok(0)
+    }
+    
+    /// IDs de pools activos (Instance Storage)
+    /// 
+    /// Se lee en cada donación: la instancia se extiende con
+    /// `ttl_pool` para que la lista no expire mientras haya pools.
+    fn pools_activos(env: &Env) -> Vec<u32> {
+        let activos: Vec<u32> = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::PoolsActivos)
+            .unwrap_or(Vec::new(env));
+        if !activos.is_empty() {
+            let ttl = Self::ttl_pool(env);
+            env.storage().instance().extend_ttl(ttl / 2, ttl);
+        }
+        activos
+    }
+    
+    /// Saca un pool de la lista de activos
+    fn quitar_pool_activo(env: &Env, id_pool: u32) {
+        let mut activos = Self::pools_activos(env);
+        if let Some(posicion) = activos.first_index_of(id_pool) {
+            activos.remove(posicion);
+            env.storage()
+                .instance()
+                .set(&DataKeyDonaciones::PoolsActivos, &activos);
+        }
+    }
+    
+    /// Lee un pool existente y extiende su TTL
+    fn leer_pool(env: &Env, id_pool: u32) -> Result<PoolIgualacion, Error> {
+        let key = DataKeyDonaciones::Pool(id_pool);
+        let pool: PoolIgualacion = env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::PoolNoExiste)?;
+        let ttl = Self::ttl_pool(env);
+        env.storage().persistent().extend_ttl(&key, ttl / 2, ttl);
+        Ok(pool)
+    }
+    
+    /// Beneficiaria elegible de un pool (extiende su TTL si lo es)
+    fn es_beneficiaria(env: &Env, id_pool: u32, direccion: &Address) -> bool {
+        let key = DataKeyDonaciones::PoolBeneficiaria(id_pool, direccion.clone());
+        let elegible = env.storage().persistent().has(&key);
+        if elegible {
+            let ttl = Self::ttl_pool(env);
+            env.storage().persistent().extend_ttl(&key, ttl / 2, ttl);
+        }
+        elegible
+    }
+    
+    /// Guarda un pool y extiende su TTL
+    fn guardar_pool(env: &Env, id_pool: u32, pool: &PoolIgualacion) {
+        let key = DataKeyDonaciones::Pool(id_pool);
+        let ttl = Self::ttl_pool(env);
+        env.storage().persistent().set(&key, pool);
+        env.storage().persistent().extend_ttl(&key, ttl / 2, ttl);
+    }
+    
+    /// TTL (en ledgers) de los pools y de la lista de activos
+    /// 
+    /// Un pool no tiene fecha de cierre: vive mientras tenga fondos, así
+    /// que usa el TTL largo de los checkpoints y se extiende en cada
+    /// lectura (cada donación lo lee).
+    fn ttl_pool(env: &Env) -> u32 {
+        checkpoints::TTL_CHECKPOINT.min(env.storage().max_ttl())
+    }
+    
+    /// Lee una campaña existente
+    fn leer_campana(env: &Env, id_campana: u32) -> Result<CampanaInfo, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::Campana(id_campana))
+            .ok_or(Error::CampanaNoExiste)
+    }
+    
+    /// Guarda una campaña y extiende su TTL
+    fn guardar_campana(env: &Env, id_campana: u32, campana: &CampanaInfo) {
+        let key = DataKeyDonaciones::Campana(id_campana);
+        let ttl = Self::ttl_campana(env, campana);
+        env.storage().persistent().set(&key, campana);
+        env.storage().persistent().extend_ttl(&key, ttl, ttl);
+    }
+    
+    /// TTL (en ledgers) que cubre la campaña hasta su fecha límite
+    /// más el margen para reclamar o reembolsar
+    /// 
+    /// Nunca menos que `TTL_MINIMO` ni más que el máximo de la red.
+    fn ttl_campana(env: &Env, campana: &CampanaInfo) -> u32 {
+        let segundos = campana.fecha_limite.saturating_sub(env.ledger().timestamp());
+        let hasta_fecha = (segundos / SEGUNDOS_POR_LEDGER).min(u32::MAX as u64) as u32;
+        
+        hasta_fecha
+            .saturating_add(MARGEN_CAMPANA_LEDGERS)
+            .max(TTL_MINIMO)
+            .min(env.storage().max_ttl())
+    }
+    
+    /// Guarda el detalle de una donación y la agrega a los índices
+    /// 
+    /// Los IDs son consecutivos y se asignan en orden de llegada, así
+    /// que cada índice queda ordenado por `timestamp`. `ttl` es el TTL
+    /// mínimo del detalle y de los índices (los aportes a campañas
+    /// duran lo que la campaña).
+    fn guardar_donacion(env: &Env, ttl: u32, donacion: &DonacionInfo) -> Result<u32, Error> {
+        // 1. Asignar ID global (Instance Storage)
+        let id_donacion: u32 = env.storage()
+            .instance()
+            .get(&DataKeyDonaciones::TotalDonaciones)
+            .unwrap_or(0);
+        
+        let nuevo_total = id_donacion
+            .checked_add(1)
+            .ok_or(Error::Overflow)?;
+        
+        env.storage()
+            .persistent()
+            .set(&DataKeyDonaciones::Donacion(id_donacion), donacion);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKeyDonaciones::Donacion(id_donacion), ttl, ttl);
+        
+        env.storage()
+            .instance()
+            .set(&DataKeyDonaciones::TotalDonaciones, &nuevo_total);
+        
+        // 2. Agregar a los índices por donante y por beneficiaria
+        let indice_donante = Indice::Donante(donacion.donante.clone());
+        let indice_beneficiaria = Indice::Beneficiaria(donacion.beneficiaria.clone());
+        Self::agregar_a_indice(env, &indice_donante, id_donacion, ttl)?;
+        Self::agregar_a_indice(env, &indice_beneficiaria, id_donacion, ttl)?;
+        
+        // 3. Extender TTL de instance storage
+        env.storage().instance().extend_ttl(100, 100);
+        
+        Ok(id_donacion)
+    }
+    
+    /// Agrega un ID al final de un índice (slot por posición + contador)
+    fn agregar_a_indice(
+        env: &Env,
+        indice: &Indice,
+        id_donacion: u32,
+        ttl: u32,
+    ) -> Result<(), Error> {
+        let posicion = Self::total_indice(env, indice);
+        let nuevo_total = posicion
+            .checked_add(1)
+            .ok_or(Error::Overflow)?;
+        
+        let (key_slot, key_total) = match indice {
+            Indice::Donante(donante) => (
+                DataKeyDonaciones::IndiceDonante(donante.clone(), posicion),
+                DataKeyDonaciones::TotalPorDonante(donante.clone()),
+            ),
+            Indice::Beneficiaria(beneficiaria) => (
+                DataKeyDonaciones::IndiceBeneficiaria(beneficiaria.clone(), posicion),
+                DataKeyDonaciones::TotalPorBeneficiaria(beneficiaria.clone()),
+            ),
+            // El índice global son los propios IDs
+            Indice::Global => return Ok(()),
+        };
+        
+        env.storage().persistent().set(&key_slot, &id_donacion);
+        env.storage().persistent().set(&key_total, &nuevo_total);
+        env.storage().persistent().extend_ttl(&key_slot, ttl, ttl);
+        env.storage().persistent().extend_ttl(&key_total, ttl, ttl);
+        
+        Ok(())
+    }
+    
+    /// Cantidad de donaciones en un índice
+    fn total_indice(env: &Env, indice: &Indice) -> u32 {
+        let key = match indice {
+            Indice::Global => DataKeyDonaciones::TotalDonaciones,
+            Indice::Donante(donante) => DataKeyDonaciones::TotalPorDonante(donante.clone()),
+            Indice::Beneficiaria(beneficiaria) => {
+                DataKeyDonaciones::TotalPorBeneficiaria(beneficiaria.clone())
+            }
+        };
+        
+        match indice {
+            Indice::Global => env.storage().instance().get(&key).unwrap_or(0),
+            _ => env.storage().persistent().get(&key).unwrap_or(0),
+        }
+    }
+    
+    /// Lee la donación que ocupa `posicion` dentro de un índice
+    fn donacion_en(env: &Env, indice: &Indice, posicion: u32) -> Result<DonacionInfo, Error> {
+        let id_donacion: u32 = match indice {
+            Indice::Global => posicion,
+            Indice::Donante(donante) => env.storage()
+                .persistent()
+                .get(&DataKeyDonaciones::IndiceDonante(donante.clone(), posicion))
+                .ok_or(Error::DonacionNoExiste)?,
+            Indice::Beneficiaria(beneficiaria) => env.storage()
+                .persistent()
+                .get(&DataKeyDonaciones::IndiceBeneficiaria(beneficiaria.clone(), posicion))
+                .ok_or(Error::DonacionNoExiste)?,
+        };
+        
+        env.storage()
+            .persistent()
+            .get(&DataKeyDonaciones::Donacion(id_donacion))
+            .ok_or(Error::DonacionNoExiste)
+    }
+    
+    /// Primera posición del índice con `timestamp >= desde` (búsqueda binaria)
+    fn primera_posicion_desde(
+        env: &Env,
+        indice: &Indice,
+        desde: u64,
+        total: u32,
+    ) -> Result<u32, Error> {
+        let mut bajo = 0u32;
+        let mut alto = total;
+        
+        while bajo < alto {
+            let medio = bajo + (alto - bajo) / 2;
+            if Self::donacion_en(env, indice, medio)?.timestamp < desde {
+                bajo = medio + 1;
+            } else {
+                alto = medio;
+            }
+        }
+        
+        Ok(bajo)
+    }
+    
+    /// Arma una página de un índice, filtrando por rango de `timestamp`
+    /// 
+    /// `cursor` es la posición dentro del índice donde empieza la página
+    /// (0 para la primera). La página trae como máximo `limite` donaciones.
+    fn paginar(
+        env: &Env,
+        indice: &Indice,
+        desde: u64,
+        hasta: u64,
+        cursor: u32,
+        limite: u32,
+    ) -> Result<PaginaDonaciones, Error> {
+        if limite == 0 || limite > MAX_PAGINA || desde > hasta {
+            return Err(Error::PaginaInvalida);
+        }
+        
+        let total = Self::total_indice(env, indice);
+        
+        // Saltar directo al inicio del rango en lugar de recorrer desde 0
+        let inicio_rango = if desde == 0 {
+            0
+        } else {
+            Self::primera_posicion_desde(env, indice, desde, total)?
+        };
+        
+        let mut posicion = cursor.max(inicio_rango);
+        let mut donaciones = Vec::new(env);
+        
+        while posicion < total && donaciones.len() < limite {
+            let donacion = Self::donacion_en(env, indice, posicion)?;
+            if donacion.timestamp > hasta {
+                // Índice ordenado por tiempo: ya no hay más en el rango
+                return Ok(PaginaDonaciones { donaciones, siguiente: None });
+            }
+            donaciones.push_back(donacion);
+            posicion += 1;
+        }
+        
+        // Solo hay página siguiente si la próxima donación sigue en el rango
+        let siguiente = if posicion < total
+            && Self::donacion_en(env, indice, posicion)?.timestamp <= hasta
+        {
+            Some(posicion)
+        } else {
+            None
+        };
+        
+        Ok(PaginaDonaciones { donaciones, siguiente })
+    }
+}
+
+// ============================================================
+// EJEMPLO 5: Patrón de Datos Relacionados
+// ============================================================
+
+/// Ejemplo del patrón de eliminar datos relacionados juntos
+/// 
+/// Solo el admin da de alta y de baja usuarios: el registro sirve
+/// como padrón de votantes (ver `registro_votantes.rs`), así que
+/// registrarse a uno mismo no puede ser gratis.
+#[contract]
+pub struct GestionUsuario;
+
+/// DataKey para gestión de usuario
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKeyUsuario {
+    Admin,              // Instance
+    Balance(Address),
+    UltimaDonacion(Address),
+    TotalDonado(Address),
+    Perfil(Address),
+}
+
+#[contractimpl]
+impl GestionUsuario {
+    /// Inicializa con el admin que gestiona los usuarios
+    /// 
+    /// Lo firma el admin; llamarla en la misma transacción del deploy.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKeyUsuario::Admin) {
+            return Err(Error::YaInicializado);
+        }
+        admin.require_auth();
+        
+        env.storage().instance().set(&DataKeyUsuario::Admin, &admin);
+        env.storage().instance().extend_ttl(100, 100);
+        
+        Ok(())
+    }
+    
+    /// Admin: crear usuario con múltiples datos relacionados
+    pub fn crear_usuario(
+        env: Env,
+        usuario: Address,
+        balance: i128,
+        total_donado: i128,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        
+        // Guardar múltiples datos relacionados
+        env.storage()
+            .persistent()
+            .set(&DataKeyUsuario::Balance(usuario.clone()), &balance);
+        
+        env.storage()
+            .persistent()
+            .set(&DataKeyUsuario::TotalDonado(usuario.clone()), &total_donado);
+        
+        // Extender TTL de todos los datos relacionados
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKeyUsuario::Balance(usuario.clone()), 100, 100);
+        
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKeyUsuario::TotalDonado(usuario.clone()), 100, 100);
+        
+        Ok(())
+    }
+    
+    /// Admin: eliminar usuario (patrón de eliminar datos relacionados)
+    pub fn eliminar_usuario(env: Env, usuario: Address) -> Result<(), Error> {
+        Self::require_admin(&env)?;
+        
+        // Verificar que el usuario existe
+        if !env.storage().persistent().has(&DataKeyUsuario::Balance(usuario.clone())) {
+            return Err(Error::UsuarioNoExiste);
+        }
+        
+        // Eliminar todos los datos relacionados juntos
+        env.storage()
+            .persistent()
+            .remove(&DataKeyUsuario::Balance(usuario.clone()));
+        
+        env.storage()
+            .persistent()
+            .remove(&DataKeyUsuario::UltimaDonacion(usuario.clone()));
+        
+        env.storage()
+            .persistent()
+            .remove(&DataKeyUsuario::TotalDonado(usuario.clone()));
+        
+        // Si existe perfil, también eliminarlo
+        if env.storage().persistent().has(&DataKeyUsuario::Perfil(usuario.clone())) {
+            env.storage()
+                .persistent()
+                .remove(&DataKeyUsuario::Perfil(usuario));
+        }
+        
+        Ok(())
+    }
+    
+    /// Verificar si usuario existe
+    pub fn usuario_existe(env: Env, usuario: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKeyUsuario::Balance(usuario))
+    }
+    
+    /// Obtener balance
     pub fn get_balance(env: Env, usuario: Address) -> i128 {
         env.storage()
             .persistent()
@@ -586,6 +2054,19 @@ impl GestionUsuario {
     }
 }
 
+// Helpers privados de GestionUsuario
+impl GestionUsuario {
+    /// Verifica la firma del admin guardado
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKeyUsuario::Admin)
+            .ok_or(Error::NoInicializado)?;
+        admin.require_auth();
+        Ok(admin)
+    }
+}
+
 // ============================================================
 // EJEMPLO 6: Estrategia de Extensión de TTL
 // ============================================================
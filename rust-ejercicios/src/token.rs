@@ -0,0 +1,513 @@
+#![no_std]
+use soroban_sdk::{
+    contract,
+    contracterror,
+    contractimpl,
+    contracttype,
+    symbol_short,
+    Address,
+    Env,
+    String as SorobanString,
+};
+
+use crate::traits_ejemplos::Token;
+
+// ============================================================
+// PARTE 5: TOKEN FUNGIBLE (SEP-41)
+// ============================================================
+// Implementación real del trait `Token` de traits_ejemplos,
+// compatible con la interfaz estándar de tokens de Soroban (SEP-41)
+
+// ============================================================
+// CONSTANTES DE TTL
+// ============================================================
+
+/// Ledgers aproximados en un día (5 segundos por ledger)
+pub(crate) const DIA_EN_LEDGERS: u32 = 17280;
+
+/// Extensión de TTL para la configuración del token (instance)
+pub(crate) const INSTANCE_BUMP: u32 = 7 * DIA_EN_LEDGERS;
+pub(crate) const INSTANCE_UMBRAL: u32 = INSTANCE_BUMP - DIA_EN_LEDGERS;
+
+/// Extensión de TTL para balances (persistent)
+pub(crate) const BALANCE_BUMP: u32 = 30 * DIA_EN_LEDGERS;
+pub(crate) const BALANCE_UMBRAL: u32 = BALANCE_BUMP - DIA_EN_LEDGERS;
+
+// ============================================================
+// DEFINICIÓN DE ERRORES PERSONALIZADOS
+// ============================================================
+
+/// Errores personalizados del token
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    /// El token ya fue inicializado
+    YaInicializado = 1,
+    /// El token no ha sido inicializado
+    NoInicializado = 2,
+    /// El monto es negativo (o cero donde no se permite)
+    MontoInvalido = 3,
+    /// La cuenta no tiene balance suficiente
+    BalanceInsuficiente = 4,
+    /// El spender no tiene allowance suficiente
+    AllowanceInsuficiente = 5,
+    /// El ledger de expiración del allowance ya pasó
+    ExpiracionInvalida = 6,
+    /// Más de 18 decimales
+    DecimalesInvalidos = 7,
+    /// Overflow en balances o supply
+    Overflow = 8,
+}
+
+// ============================================================
+// DATAKEY PARA ORGANIZAR STORAGE
+// ============================================================
+
+/// Allowance guardado en Temporary Storage
+///
+/// El allowance expira solo: después de `expiration_ledger`
+/// se considera 0 aunque la entrada siga existiendo.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Metadata del token (se guarda junta porque se lee junta)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenMetadata {
+    pub decimal: u32,
+    pub name: SorobanString,
+    pub symbol: SorobanString,
+}
+
+/// DataKey enum para organizar el storage
+///
+/// Patrón: Instance = configuración, Persistent = balances,
+/// Temporary = allowances (tienen expiración propia)
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    // Instance storage
+    /// Administradora que puede acuñar (mint)
+    Admin,
+    /// Nombre, símbolo y decimales
+    Metadata,
+    /// Total de tokens en circulación
+    TotalSupply,
+
+    // Persistent storage
+    /// Balance de cada holder
+    Balance(Address),
+
+    // Temporary storage
+    /// Allowance (from, spender)
+    Allowance(Address, Address),
+}
+
+// ============================================================
+// CONTRATO TOKEN
+// ============================================================
+
+/// Token fungible completo
+///
+/// - Inicialización con nombre, símbolo y decimales
+/// - `mint` solo para la administradora, `burn` para cada holder
+/// - `approve` / `allowance` / `transfer_from` para gasto delegado
+/// - `require_auth` en toda operación que gasta fondos
+/// - Eventos estándar: transfer, mint, burn, approve
+#[contract]
+pub struct TokenContract;
+
+#[contractimpl]
+impl TokenContract {
+    // ============================================================
+    // SECCIÓN 1: INICIALIZACIÓN Y ADMINISTRACIÓN
+    // ============================================================
+
+    /// Inicializa el token
+    ///
+    /// Solo puede llamarse una vez.
+    ///
+    /// # Errores
+    /// - `YaInicializado` - Si ya existe una administradora
+    /// - `DecimalesInvalidos` - Si `decimal` > 18
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        decimal: u32,
+        name: SorobanString,
+        symbol: SorobanString,
+    ) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::YaInicializado);
+        }
+
+        if decimal > 18 {
+            return Err(Error::DecimalesInvalidos);
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(
+            &DataKey::Metadata,
+            &TokenMetadata { decimal, name, symbol },
+        );
+        env.storage().instance().set(&DataKey::TotalSupply, &0i128);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_UMBRAL, INSTANCE_BUMP);
+
+        Ok(())
+    }
+
+    /// Acuña tokens nuevos para `to`
+    ///
+    /// Solo la administradora puede acuñar.
+    pub fn mint(env: Env, to: Address, amount: i128) -> Result<(), Error> {
+        Self::validar_monto(amount)?;
+
+        let admin = Self::admin(env.clone())?;
+        admin.require_auth();
+
+        let supply = Self::leer_supply(&env)
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+
+        Self::sumar_balance(&env, &to, amount)?;
+        env.storage().instance().set(&DataKey::TotalSupply, &supply);
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_UMBRAL, INSTANCE_BUMP);
+
+        env.events().publish(
+            (symbol_short!("mint"), admin, to),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Obtiene la administradora del token
+    pub fn admin(env: Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NoInicializado)
+    }
+
+    // ============================================================
+    // SECCIÓN 2: INTERFAZ SEP-41
+    // ============================================================
+
+    /// Cantidad que `spender` puede gastar en nombre de `from`
+    ///
+    /// Retorna 0 si no existe o si ya expiró.
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        Self::leer_allowance(&env, &from, &spender).amount
+    }
+
+    /// Autoriza a `spender` a gastar hasta `amount` de `from`
+    ///
+    /// Sobrescribe el allowance anterior. `amount = 0` lo revoca.
+    ///
+    /// # Errores
+    /// - `MontoInvalido` - Si `amount` es negativo
+    /// - `ExpiracionInvalida` - Si `amount > 0` y la expiración ya pasó
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), Error> {
+        from.require_auth();
+
+        if amount < 0 {
+            return Err(Error::MontoInvalido);
+        }
+
+        Self::escribir_allowance(&env, &from, &spender, amount, expiration_ledger)?;
+
+        env.events().publish(
+            (symbol_short!("approve"), from, spender),
+            (amount, expiration_ledger),
+        );
+
+        Ok(())
+    }
+
+    /// Balance de una cuenta (0 si nunca recibió tokens)
+    pub fn balance(env: Env, id: Address) -> i128 {
+        let key = DataKey::Balance(id);
+        match env.storage().persistent().get::<DataKey, i128>(&key) {
+            Some(balance) => {
+                env.storage()
+                    .persistent()
+                    .extend_ttl(&key, BALANCE_UMBRAL, BALANCE_BUMP);
+                balance
+            }
+            None => 0,
+        }
+    }
+
+    /// Transfiere `amount` de `from` a `to`
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) -> Result<(), Error> {
+        from.require_auth();
+
+        Self::validar_monto(amount)?;
+        Self::restar_balance(&env, &from, amount)?;
+        Self::sumar_balance(&env, &to, amount)?;
+
+        env.events().publish(
+            (symbol_short!("transfer"), from, to),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Transfiere `amount` de `from` a `to` usando el allowance de `spender`
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        spender.require_auth();
+
+        Self::validar_monto(amount)?;
+        Self::gastar_allowance(&env, &from, &spender, amount)?;
+        Self::restar_balance(&env, &from, amount)?;
+        Self::sumar_balance(&env, &to, amount)?;
+
+        env.events().publish(
+            (symbol_short!("transfer"), from, to),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Quema `amount` del balance de `from`
+    pub fn burn(env: Env, from: Address, amount: i128) -> Result<(), Error> {
+        from.require_auth();
+
+        Self::validar_monto(amount)?;
+        Self::quemar(&env, &from, amount)?;
+
+        env.events().publish(
+            (symbol_short!("burn"), from),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Quema `amount` de `from` usando el allowance de `spender`
+    pub fn burn_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        spender.require_auth();
+
+        Self::validar_monto(amount)?;
+        Self::gastar_allowance(&env, &from, &spender, amount)?;
+        Self::quemar(&env, &from, amount)?;
+
+        env.events().publish(
+            (symbol_short!("burn"), from),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Cantidad de decimales del token
+    pub fn decimals(env: Env) -> Result<u32, Error> {
+        Ok(Self::leer_metadata(&env)?.decimal)
+    }
+
+    /// Nombre del token
+    pub fn name(env: Env) -> Result<SorobanString, Error> {
+        Ok(Self::leer_metadata(&env)?.name)
+    }
+
+    /// Símbolo del token
+    pub fn symbol(env: Env) -> Result<SorobanString, Error> {
+        Ok(Self::leer_metadata(&env)?.symbol)
+    }
+
+    /// Total de tokens en circulación
+    pub fn total_supply(env: Env) -> i128 {
+        Self::leer_supply(&env)
+    }
+}
+
+// ============================================================
+// FUNCIONES HELPER (privadas)
+// ============================================================
+
+impl TokenContract {
+    /// Los montos nunca pueden ser negativos
+    fn validar_monto(amount: i128) -> Result<(), Error> {
+        if amount < 0 {
+            return Err(Error::MontoInvalido);
+        }
+        Ok(())
+    }
+
+    fn leer_metadata(env: &Env) -> Result<TokenMetadata, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Metadata)
+            .ok_or(Error::NoInicializado)
+    }
+
+    fn leer_supply(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalSupply)
+            .unwrap_or(0)
+    }
+
+    fn escribir_balance(env: &Env, id: &Address, balance: i128) {
+        let key = DataKey::Balance(id.clone());
+        env.storage().persistent().set(&key, &balance);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_UMBRAL, BALANCE_BUMP);
+    }
+
+    fn sumar_balance(env: &Env, id: &Address, amount: i128) -> Result<(), Error> {
+        let nuevo = Self::balance(env.clone(), id.clone())
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+        Self::escribir_balance(env, id, nuevo);
+        Ok(())
+    }
+
+    fn restar_balance(env: &Env, id: &Address, amount: i128) -> Result<(), Error> {
+        let balance = Self::balance(env.clone(), id.clone());
+        if balance < amount {
+            return Err(Error::BalanceInsuficiente);
+        }
+        Self::escribir_balance(env, id, balance - amount);
+        Ok(())
+    }
+
+    /// Resta del balance y del supply total
+    fn quemar(env: &Env, from: &Address, amount: i128) -> Result<(), Error> {
+        Self::restar_balance(env, from, amount)?;
+
+        let supply = Self::leer_supply(env)
+            .checked_sub(amount)
+            .ok_or(Error::Overflow)?;
+        env.storage().instance().set(&DataKey::TotalSupply, &supply);
+
+        Ok(())
+    }
+
+    /// Lee el allowance tratando los expirados como 0
+    fn leer_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        match env.storage().temporary().get::<DataKey, AllowanceValue>(&key) {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => {
+                allowance
+            }
+            Some(allowance) => AllowanceValue {
+                amount: 0,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+            None => AllowanceValue {
+                amount: 0,
+                expiration_ledger: 0,
+            },
+        }
+    }
+
+    fn escribir_allowance(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), Error> {
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            return Err(Error::ExpiracionInvalida);
+        }
+
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().temporary().set(
+            &key,
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+
+        // La entrada vive exactamente hasta su expiración
+        if amount > 0 {
+            let ttl = expiration_ledger - env.ledger().sequence();
+            env.storage().temporary().extend_ttl(&key, ttl, ttl);
+        }
+
+        Ok(())
+    }
+
+    fn gastar_allowance(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let allowance = Self::leer_allowance(env, from, spender);
+        if allowance.amount < amount {
+            return Err(Error::AllowanceInsuficiente);
+        }
+
+        if amount > 0 {
+            Self::escribir_allowance(
+                env,
+                from,
+                spender,
+                allowance.amount - amount,
+                allowance.expiration_ledger,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================
+// IMPLEMENTACIÓN DEL TRAIT TOKEN
+// ============================================================
+
+// Implementación del trait Token para TokenContract
+impl Token for TokenContract {
+    fn balance_of(&self, env: &Env, owner: Address) -> i128 {
+        Self::balance(env.clone(), owner)
+    }
+
+    fn transfer(
+        &self,
+        env: &Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), soroban_sdk::Error> {
+        // Delegar en la función del contrato (incluye require_auth)
+        Self::transfer(env.clone(), from, to, amount).map_err(soroban_sdk::Error::from)
+    }
+
+    fn total_supply(&self, env: &Env) -> i128 {
+        Self::leer_supply(env)
+    }
+}
@@ -0,0 +1,216 @@
+#![cfg(test)]
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+    Env,
+    String as SorobanString,
+};
+use crate::token::{
+    Error,
+    TokenContract,
+    TokenContractClient,
+};
+use crate::traits_ejemplos::Token;
+
+fn crear_token<'a>(env: &Env, admin: &Address) -> TokenContractClient<'a> {
+    let contract_id = env.register_contract(None, TokenContract);
+    let client = TokenContractClient::new(env, &contract_id);
+    client.initialize(
+        admin,
+        &7,
+        &SorobanString::from_str(env, "Tiburona"),
+        &SorobanString::from_str(env, "TIB"),
+    );
+    client
+}
+
+#[test]
+fn test_initialize_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token = crear_token(&env, &admin);
+
+    assert_eq!(token.decimals(), 7);
+    assert_eq!(token.name(), SorobanString::from_str(&env, "Tiburona"));
+    assert_eq!(token.symbol(), SorobanString::from_str(&env, "TIB"));
+    assert_eq!(token.admin(), admin);
+    assert_eq!(token.total_supply(), 0);
+}
+
+#[test]
+fn test_initialize_dos_veces() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token = crear_token(&env, &admin);
+
+    let resultado = token.try_initialize(
+        &admin,
+        &7,
+        &SorobanString::from_str(&env, "Otro"),
+        &SorobanString::from_str(&env, "OTR"),
+    );
+    assert_eq!(resultado, Err(Ok(Error::YaInicializado)));
+}
+
+#[test]
+fn test_mint_y_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+    let token = crear_token(&env, &admin);
+
+    token.mint(&ana, &1000);
+    assert_eq!(token.balance(&ana), 1000);
+    assert_eq!(token.total_supply(), 1000);
+
+    token.transfer(&ana, &bea, &300);
+    assert_eq!(token.balance(&ana), 700);
+    assert_eq!(token.balance(&bea), 300);
+    assert_eq!(token.total_supply(), 1000);
+}
+
+#[test]
+fn test_transfer_balance_insuficiente() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+    let token = crear_token(&env, &admin);
+
+    token.mint(&ana, &100);
+
+    let resultado = token.try_transfer(&ana, &bea, &101);
+    assert_eq!(resultado, Err(Ok(Error::BalanceInsuficiente)));
+    assert_eq!(token.balance(&ana), 100);
+}
+
+#[test]
+fn test_transfer_requiere_auth() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+    let token = crear_token(&env, &admin);
+
+    token.mint(&ana, &100);
+    token.transfer(&ana, &bea, &10);
+
+    // La única autorización de la última llamada es la de `from`
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, ana);
+}
+
+#[test]
+fn test_approve_y_transfer_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let ana = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let bea = Address::generate(&env);
+    let token = crear_token(&env, &admin);
+
+    token.mint(&ana, &1000);
+    token.approve(&ana, &spender, &400, &200);
+    assert_eq!(token.allowance(&ana, &spender), 400);
+
+    token.transfer_from(&spender, &ana, &bea, &150);
+    assert_eq!(token.allowance(&ana, &spender), 250);
+    assert_eq!(token.balance(&ana), 850);
+    assert_eq!(token.balance(&bea), 150);
+
+    let resultado = token.try_transfer_from(&spender, &ana, &bea, &251);
+    assert_eq!(resultado, Err(Ok(Error::AllowanceInsuficiente)));
+}
+
+#[test]
+fn test_allowance_expira() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let ana = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let token = crear_token(&env, &admin);
+
+    token.mint(&ana, &1000);
+    token.approve(&ana, &spender, &400, &10);
+
+    env.ledger().with_mut(|li| li.sequence_number = 11);
+    assert_eq!(token.allowance(&ana, &spender), 0);
+
+    // No se puede aprobar con una expiración pasada
+    let resultado = token.try_approve(&ana, &spender, &1, &5);
+    assert_eq!(resultado, Err(Ok(Error::ExpiracionInvalida)));
+}
+
+#[test]
+fn test_burn_y_burn_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let ana = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let token = crear_token(&env, &admin);
+
+    token.mint(&ana, &1000);
+    token.burn(&ana, &100);
+    assert_eq!(token.balance(&ana), 900);
+    assert_eq!(token.total_supply(), 900);
+
+    token.approve(&ana, &spender, &50, &100);
+    token.burn_from(&spender, &ana, &50);
+    assert_eq!(token.balance(&ana), 850);
+    assert_eq!(token.total_supply(), 850);
+    assert_eq!(token.allowance(&ana, &spender), 0);
+}
+
+#[test]
+fn test_monto_negativo() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+    let token = crear_token(&env, &admin);
+
+    assert_eq!(token.try_mint(&ana, &-1), Err(Ok(Error::MontoInvalido)));
+    assert_eq!(token.try_transfer(&ana, &bea, &-1), Err(Ok(Error::MontoInvalido)));
+}
+
+#[test]
+fn test_impl_trait_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+    let token = crear_token(&env, &admin);
+    token.mint(&ana, &500);
+
+    // Usar el contrato a través del trait Token
+    env.as_contract(&token.address, || {
+        let contrato = TokenContract;
+        assert_eq!(contrato.balance_of(&env, ana.clone()), 500);
+        assert_eq!(contrato.total_supply(&env), 500);
+
+        assert!(Token::transfer(&contrato, &env, ana.clone(), bea.clone(), 200).is_ok());
+        assert_eq!(contrato.balance_of(&env, bea.clone()), 200);
+    });
+}
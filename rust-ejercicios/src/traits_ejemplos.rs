@@ -5,12 +5,20 @@ use soroban_sdk::{
     Env,
     Address,
     Symbol,
+    Val,
+    Vec,
     i128,
     Error,
     Vec as SorobanVec,
     String as SorobanString,
     symbol_short,
     contracttype,
+    contracterror,
+    token,
+};
+use crate::ownable::{
+    self,
+    Error as OwnableError,
 };
 
 // ============================================================
@@ -223,16 +231,51 @@ pub trait Token {
 /// 
 /// Define las funciones que TODA propuesta debe tener,
 /// sin importar el tipo (ley, presupuesto, evento, etc.)
+/// 
+/// Los votos son pesos (i128): una persona = 1, o el peso que
+/// le corresponda (tokens, asignación del admin, etc.).
+/// 
+/// Reglas por defecto (sobreescribibles):
+/// - Sin quórum
+/// - Umbral de 50%: pasa si a favor > 50% de (a favor + en contra)
+/// - Las abstenciones cuentan para el quórum, no para el umbral
 pub trait Votable {
     /// Obtiene votos a favor
-    fn votos_a_favor(&self, env: &Env) -> u32;
+    fn votos_a_favor(&self, env: &Env) -> i128;
     
     /// Obtiene votos en contra
-    fn votos_en_contra(&self, env: &Env) -> u32;
+    fn votos_en_contra(&self, env: &Env) -> i128;
+    
+    /// Obtiene abstenciones
+    fn abstenciones(&self, _env: &Env) -> i128 {
+        0
+    }
+    
+    /// Participación mínima (a favor + en contra + abstenciones)
+    fn quorum(&self, _env: &Env) -> i128 {
+        0
+    }
+    
+    /// Umbral de aprobación en puntos básicos (6_667 ≈ 2/3)
+    fn umbral_bps(&self, _env: &Env) -> u32 {
+        5_000
+    }
+    
+    /// Determina si se alcanzó el quórum
+    fn quorum_alcanzado(&self, env: &Env) -> bool {
+        let participacion = self.votos_a_favor(env)
+            + self.votos_en_contra(env)
+            + self.abstenciones(env);
+        participacion >= self.quorum(env)
+    }
     
-    /// Determina si la propuesta pasó (más votos a favor que en contra)
+    /// Determina si la propuesta pasó (quórum + a favor por encima del umbral)
     fn paso(&self, env: &Env) -> bool {
-        self.votos_a_favor(env) > self.votos_en_contra(env)
+        let a_favor = self.votos_a_favor(env);
+        let emitidos = a_favor + self.votos_en_contra(env);
+        
+        self.quorum_alcanzado(env)
+            && a_favor * 10_000 > emitidos * self.umbral_bps(env) as i128
     }
 }
 
@@ -243,23 +286,43 @@ pub trait Votable {
 /// Trait Ownable - Patrón estándar de control de acceso
 /// 
 /// Define las funciones que todo contrato "Ownable" debe tener
-/// para controlar quién puede ejecutar funciones administrativas
+/// para controlar quién puede ejecutar funciones administrativas.
+/// 
+/// Todas tienen implementación por defecto sobre `crate::ownable`
+/// (owner en Instance Storage, firma con `require_auth`), así que
+/// alcanza con `impl Ownable for MiContrato {}`.
 pub trait Ownable {
     /// Obtiene el owner actual del contrato
-    fn get_owner(&self, env: &Env) -> Address;
+    fn get_owner(&self, env: &Env) -> Result<Address, OwnableError> {
+        ownable::owner(env)
+    }
     
-    /// Transfiere el ownership a otra persona
-    fn transfer_ownership(&self, env: &Env, new_owner: Address) -> Result<(), Error>;
+    /// Obtiene el owner propuesto que aún no aceptó
+    fn pending_owner(&self, env: &Env) -> Option<Address> {
+        ownable::owner_pendiente(env)
+    }
     
-    /// Verifica que quien llama es el owner (guardián)
-    fn require_owner(&self, env: &Env, caller: Address) -> Result<(), Error>;
-}
-
-/// Storage keys para Ownable
-#[contracttype]
-#[derive(Clone)]
-pub enum DataKeyOwnable {
-    Owner,
+    /// Propone un nuevo owner (paso 1 de 2, requiere firma del owner)
+    /// 
+    /// El owner actual lo sigue siendo hasta que el nuevo acepte.
+    fn transfer_ownership(&self, env: &Env, new_owner: Address) -> Result<(), OwnableError> {
+        ownable::proponer(env, &new_owner)
+    }
+    
+    /// El owner propuesto acepta (paso 2 de 2, requiere su firma)
+    fn accept_ownership(&self, env: &Env) -> Result<(), OwnableError> {
+        ownable::aceptar(env).map(|_| ())
+    }
+    
+    /// El owner renuncia y el contrato queda sin owner
+    fn renounce_ownership(&self, env: &Env) -> Result<(), OwnableError> {
+        ownable::renunciar(env)
+    }
+    
+    /// Verifica la firma del owner (guardián) y lo devuelve
+    fn require_owner(&self, env: &Env) -> Result<Address, OwnableError> {
+        ownable::require_owner(env)
+    }
 }
 
 // ============================================================
@@ -271,10 +334,11 @@ pub enum DataKeyOwnable {
 pub struct MicroCredito;
 
 /// Storage keys adicionales para MicroCredito
+/// 
+/// El owner se guarda con `DataKeyOwnable` (módulo `ownable`).
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKeyMicroCredito {
-    Owner,
     TasaInteres,
     TotalPrestado,
 }
@@ -282,10 +346,12 @@ pub enum DataKeyMicroCredito {
 #[contractimpl]
 impl MicroCredito {
     /// Inicializa el contrato con un owner
-    pub fn initialize(env: Env, owner: Address) {
-        env.storage().instance().set(&DataKeyMicroCredito::Owner, &owner);
+    pub fn initialize(env: Env, owner: Address) -> Result<(), OwnableError> {
+        ownable::inicializar(&env, &owner)?;
         env.storage().instance().set(&DataKeyMicroCredito::TasaInteres, &10u32); // 10% por defecto
         env.storage().instance().set(&DataKeyMicroCredito::TotalPrestado, &0i128);
+        
+        Ok(())
     }
     
     /// Función pública - cualquiera puede llamarla
@@ -323,14 +389,14 @@ impl MicroCredito {
     /// 
     /// # Argumentos
     /// - `env`: Entorno de Soroban
-    /// - `caller`: Address de quien llama la función (debe ser el owner)
     /// - `nueva_tasa`: Nueva tasa de interés (en porcentaje)
     /// 
     /// # Retorna
-    /// `Result<(), Error>` - Ok(()) si exitoso, Error si no es el owner
-    pub fn cambiar_tasa_interes(env: Env, caller: Address, nueva_tasa: u32) -> Result<(), Error> {
-        // PRIMERO: Verificar que el caller es el owner
-        Self::require_owner(&env, caller)?;
+    /// `Result<(), OwnableError>` - Ok(()) si exitoso, Error si no hay owner
+    /// (sin la firma del owner la transacción se rechaza)
+    pub fn cambiar_tasa_interes(env: Env, nueva_tasa: u32) -> Result<(), OwnableError> {
+        // PRIMERO: Verificar la firma del owner guardado
+        ownable::require_owner(&env)?;
         
         // SEGUNDO: Si llegamos aquí, es seguro cambiar la tasa
         env.storage()
@@ -361,88 +427,37 @@ impl MicroCredito {
             .get(&DataKeyMicroCredito::TotalPrestado)
             .unwrap_or(0i128)
     }
-}
-
-// Implementación del trait Ownable para MicroCredito
-impl Ownable for MicroCredito {
-    /// Obtiene el owner actual del contrato
-    fn get_owner(&self, env: &Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKeyMicroCredito::Owner)
-            .expect("Owner no inicializado")
+    
+    /// Consulta el owner actual
+    pub fn owner(env: Env) -> Result<Address, OwnableError> {
+        ownable::owner(&env)
     }
     
-    /// Transfiere el ownership a otra persona
-    /// 
-    /// Solo el owner actual puede transferir el ownership
-    fn transfer_ownership(&self, env: &Env, new_owner: Address) -> Result<(), Error> {
-        // PASO 1: Obtener quien está llamando esta función
-        // Nota: En un contrato real, usarías env.invoker() o env.current_contract_address()
-        // Para este ejemplo, asumimos que el caller se pasa como parámetro
-        
-        // PASO 2: VALIDAR PRIMERO - Verificar que el caller ES el owner actual
-        // Nota: En implementación real, obtener caller de env
-        // Por ahora, requerimos que se pase explícitamente
-        
-        // En una implementación completa:
-        // let caller = env.invoker();
-        // self.require_owner(env, caller)?;
-        
-        // PASO 3: Si llegamos aquí, es seguro cambiar el owner en storage
-        env.storage()
-            .instance()
-            .set(&DataKeyMicroCredito::Owner, &new_owner);
-        
-        // Emitir evento
-        env.events().publish(
-            (symbol_short!("owner_trans"),),
-            new_owner
-        );
-        
-        Ok(())
+    /// Consulta el owner propuesto que aún no aceptó
+    pub fn owner_pendiente(env: Env) -> Option<Address> {
+        ownable::owner_pendiente(&env)
     }
     
-    /// Verifica que quien llama es el owner (guardián)
-    /// 
-    /// # Argumentos
-    /// - `env`: Entorno de Soroban
-    /// - `caller`: Address de quien llama la función
-    /// 
-    /// # Retorna
-    /// `Result<(), Error>` - Ok(()) si es el owner, Error si no lo es
-    fn require_owner(&self, env: &Env, caller: Address) -> Result<(), Error> {
-        // PASO 1: Obtener el owner guardado en storage
-        let owner = self.get_owner(env);
-        
-        // PASO 2: Comparar el caller con el owner guardado
-        if caller != owner {
-            // ❌ No coinciden → retornar error
-            return Err(symbol_short!("NOT_OWNER"));
-        }
-        
-        // ✅ Coinciden → todo bien
-        Ok(())
+    /// Propone un nuevo owner (requiere firma del owner actual)
+    pub fn proponer_owner(env: Env, nuevo_owner: Address) -> Result<(), OwnableError> {
+        ownable::proponer(&env, &nuevo_owner)
     }
-}
-
-// Función auxiliar para Ownable (implementación estática)
-impl MicroCredito {
-    /// Versión estática de require_owner para usar en funciones públicas
-    pub fn require_owner(env: &Env, caller: Address) -> Result<(), Error> {
-        let owner: Address = env.storage()
-            .instance()
-            .get(&DataKeyMicroCredito::Owner)
-            .expect("Owner no inicializado");
-        
-        if caller != owner {
-            return Err(symbol_short!("NOT_OWNER"));
-        }
-        
-        Ok(())
+    
+    /// El owner propuesto acepta (requiere su firma)
+    pub fn aceptar_owner(env: Env) -> Result<(), OwnableError> {
+        ownable::aceptar(&env).map(|_| ())
+    }
+    
+    /// El owner renuncia (requiere su firma)
+    pub fn renunciar_owner(env: Env) -> Result<(), OwnableError> {
+        ownable::renunciar(&env)
     }
 }
 
+// Implementación del trait Ownable para MicroCredito
+// (todas las funciones usan la implementación por defecto)
+impl Ownable for MicroCredito {}
+
 // ============================================================
 // FUNCIÓN GENÉRICA QUE USA TRAITS
 // ============================================================
@@ -487,7 +502,113 @@ pub fn registrar_donacion<T: Donacion>(
 // IMPLEMENTACIÓN DE VOTABLE PARA PROPUESTAS
 // ============================================================
 
+/// Errores de PropuestaLey
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ErrorPropuesta {
+    YaInicializado = 1,
+    NoInicializado = 2,
+    YaVoto = 3,
+    SinPeso = 4,
+    ReglasInvalidas = 5,
+    ModoIncorrecto = 6,
+    AccionYaDefinida = 7,
+    SinAccion = 8,
+    VotacionCerrada = 9,
+    VotacionAbierta = 10,
+    NoAprobada = 11,
+    RetrasoPendiente = 12,
+    YaEjecutada = 13,
+    FechasInvalidas = 14,
+    VotacionIniciada = 15,
+    AutoDelegacion = 16,
+    CicloDelegacion = 17,
+    ProfundidadExcedida = 18,
+    DemasiadosDelegadores = 19,
+    SinDelegacion = 20,
+    SinBloqueo = 21,
+    ArbolExcedido = 22,
+    DelegacionNoAceptada = 23,
+}
+
+/// Largo máximo de una cadena de delegación (en saltos)
+pub const MAX_PROFUNDIDAD_DELEGACION: u32 = 4;
+
+/// Máximo de delegadores directos por dirección
+pub const MAX_DELEGADORES: u32 = 10;
+
+/// Máximo de direcciones en un árbol de delegación (contando la raíz)
+/// 
+/// Cada voto recorre el árbol que cuelga del votante, así que el
+/// tamaño total (no solo el de cada nivel) tiene que estar acotado.
+pub const MAX_ARBOL_DELEGACION: u32 = 20;
+
+/// De dónde sale el peso de cada votante
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ModoPeso {
+    /// Tokens de este contrato bloqueados en la propuesta (`bloquear`)
+    Token(Address),
+    /// Peso asignado por el admin con `asignar_peso`
+    Asignado,
+}
+
+/// Opciones de voto
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum OpcionVoto {
+    AFavor = 0,
+    EnContra = 1,
+    Abstencion = 2,
+}
+
+/// Reglas de aprobación de la propuesta
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReglasVotacion {
+    pub quorum: i128,       // Participación mínima (en peso)
+    pub umbral_bps: u32,    // 5_000 = mayoría simple, 6_667 ≈ 2/3
+}
+
+/// Acción on-chain que ejecuta una propuesta aprobada
+/// 
+/// Se invoca `contrato.funcion(args...)` con la propuesta como
+/// invocador, así un contrato cuyo owner es la propuesta (por ejemplo
+/// `MicroCredito::cambiar_tasa_interes`) acepta la llamada.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccionPropuesta {
+    pub contrato: Address,
+    pub funcion: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// Calendario de ejecución de la acción
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ejecucion {
+    pub accion: AccionPropuesta,
+    pub cierre: u64,        // Desde este timestamp ya no se vota
+    pub retraso: u64,       // Espera después del cierre antes de ejecutar
+    pub ejecutada: bool,
+}
+
 /// Propuesta de Ley
+/// 
+/// Voto ponderado: cada dirección vota una vez (con su firma) y
+/// cuenta con su peso. En modo `Token` el peso son los tokens que
+/// la dirección bloqueó en la propuesta: quedan en el contrato hasta
+/// el cierre, así los mismos tokens no votan dos veces desde otra
+/// cuenta.
+/// 
+/// Delegación (democracia líquida): una dirección puede delegar su
+/// peso en otra, que a su vez puede delegar. Cuando alguien vota,
+/// cuenta su peso más el de todas las delegadoras (directas o en
+/// cadena) que todavía no votaron. Si una delegadora vota después,
+/// revoca o re-delega, recupera su peso del voto que lo contaba.
+/// Solo se delega con peso y en quien aceptó recibir delegaciones.
 #[contract]
 pub struct PropuestaLey;
 
@@ -497,70 +618,710 @@ pub enum DataKeyPropuestaLey {
     VotosSi,
     VotosNo,
     Titulo,
+    Abstenciones,
+    Admin,
+    Modo,
+    Reglas,
+    Peso(Address),          // Solo en modo Asignado
+    Voto(Address),          // Registro de quién votó y qué
+    Ejecucion,              // Acción opcional a ejecutar si pasa
+    Delegado(Address),      // delegadora → delegado
+    Delegadores(Address),   // delegado → Vec<Address> de delegadoras directas
+    Absorbido(Address),     // (votante, peso): quién contó el peso de esta dirección
+    PesoVoto(Address),      // Peso total con el que cuenta el voto emitido
+    Bloqueado(Address),     // Solo en modo Token: tokens depositados
+    AceptaDelegaciones(Address), // El delegado aceptó recibir delegaciones
 }
 
 #[contractimpl]
 impl PropuestaLey {
-    /// Inicializa una nueva propuesta de ley
-    pub fn initialize(env: Env, titulo: Symbol) {
-        env.storage().instance().set(&DataKeyPropuestaLey::VotosSi, &0u32);
-        env.storage().instance().set(&DataKeyPropuestaLey::VotosNo, &0u32);
+    /// Inicializa una nueva propuesta de ley (firma el admin)
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        titulo: Symbol,
+        modo: ModoPeso,
+        reglas: ReglasVotacion,
+    ) -> Result<(), ErrorPropuesta> {
+        admin.require_auth();
+        
+        if env.storage().instance().has(&DataKeyPropuestaLey::Admin) {
+            return Err(ErrorPropuesta::YaInicializado);
+        }
+        
+        if reglas.quorum < 0 || reglas.umbral_bps > 10_000 {
+            return Err(ErrorPropuesta::ReglasInvalidas);
+        }
+        
+        env.storage().instance().set(&DataKeyPropuestaLey::Admin, &admin);
         env.storage().instance().set(&DataKeyPropuestaLey::Titulo, &titulo);
+        env.storage().instance().set(&DataKeyPropuestaLey::Modo, &modo);
+        env.storage().instance().set(&DataKeyPropuestaLey::Reglas, &reglas);
+        env.storage().instance().set(&DataKeyPropuestaLey::VotosSi, &0i128);
+        env.storage().instance().set(&DataKeyPropuestaLey::VotosNo, &0i128);
+        env.storage().instance().set(&DataKeyPropuestaLey::Abstenciones, &0i128);
+        
+        Ok(())
     }
     
-    /// Vota a favor
-    pub fn votar_a_favor(env: Env) {
-        let mut votos = env.storage()
+    /// Admin: asigna el peso de un votante (solo en modo `Asignado`)
+    pub fn asignar_peso(env: Env, votante: Address, peso: i128) -> Result<(), ErrorPropuesta> {
+        let admin: Address = env.storage()
             .instance()
-            .get(&DataKeyPropuestaLey::VotosSi)
-            .unwrap_or(0u32);
-        votos += 1;
-        env.storage().instance().set(&DataKeyPropuestaLey::VotosSi, &votos);
+            .get(&DataKeyPropuestaLey::Admin)
+            .ok_or(ErrorPropuesta::NoInicializado)?;
+        admin.require_auth();
+        
+        if Self::modo(&env)? != ModoPeso::Asignado {
+            return Err(ErrorPropuesta::ModoIncorrecto);
+        }
+        
+        if peso < 0 {
+            return Err(ErrorPropuesta::SinPeso);
+        }
+        
+        env.storage()
+            .persistent()
+            .set(&DataKeyPropuestaLey::Peso(votante), &peso);
+        
+        Ok(())
+    }
+    
+    /// Bloquea `monto` tokens de `votante` en la propuesta (solo en modo `Token`)
+    /// 
+    /// Suman al peso de la dirección mientras no haya votado; si ese
+    /// peso ya cuenta en el voto de un delegado, el voto se actualiza.
+    /// Requiere la acción definida: sin cierre los tokens no se
+    /// podrían retirar.
+    pub fn bloquear(env: Env, votante: Address, monto: i128) -> Result<i128, ErrorPropuesta> {
+        votante.require_auth();
+        
+        let ModoPeso::Token(token) = Self::modo(&env)? else {
+            return Err(ErrorPropuesta::ModoIncorrecto);
+        };
+        Self::get_ejecucion(env.clone())?;
+        Self::verificar_abierta(&env)?;
+        
+        if monto <= 0 {
+            return Err(ErrorPropuesta::SinPeso);
+        }
+        if Self::ha_votado(&env, &votante) {
+            return Err(ErrorPropuesta::YaVoto);
+        }
+        
+        token::Client::new(&env, &token).transfer(
+            &votante,
+            &env.current_contract_address(),
+            &monto,
+        );
+        
+        // Sacar el peso anterior del voto que lo contaba y volver a sumarlo
+        Self::liberar(&env, &votante);
+        let key = DataKeyPropuestaLey::Bloqueado(votante.clone());
+        let bloqueado = Self::get_peso(env.clone(), votante.clone())? + monto;
+        env.storage().persistent().set(&key, &bloqueado);
+        if let Some(delegado) = Self::votante_final(&env, &votante) {
+            Self::absorber(&env, &delegado, &votante)?;
+        }
+        
+        env.events().publish(
+            (symbol_short!("bloquear"), votante),
+            monto,
+        );
+        
+        Ok(bloqueado)
+    }
+    
+    /// Devuelve los tokens bloqueados de `votante` (solo en modo `Token`)
+    /// 
+    /// Antes del cierre solo si su peso no cuenta en ningún voto
+    /// (propio o de un delegado); después del cierre, siempre.
+    pub fn retirar(env: Env, votante: Address) -> Result<i128, ErrorPropuesta> {
+        votante.require_auth();
+        
+        let ModoPeso::Token(token) = Self::modo(&env)? else {
+            return Err(ErrorPropuesta::ModoIncorrecto);
+        };
+        
+        let key = DataKeyPropuestaLey::Bloqueado(votante.clone());
+        let monto: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if monto == 0 {
+            return Err(ErrorPropuesta::SinBloqueo);
+        }
+        
+        let contado = env.storage()
+            .persistent()
+            .has(&DataKeyPropuestaLey::Absorbido(votante.clone()));
+        if contado && Self::verificar_abierta(&env).is_ok() {
+            return Err(ErrorPropuesta::VotacionAbierta);
+        }
+        
+        env.storage().persistent().remove(&key);
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &votante,
+            &monto,
+        );
+        
+        env.events().publish(
+            (symbol_short!("retirar"), votante),
+            monto,
+        );
+        
+        Ok(monto)
+    }
+    
+    /// Vota a favor
+    pub fn votar_a_favor(env: Env, votante: Address) -> Result<i128, ErrorPropuesta> {
+        Self::votar(env, votante, OpcionVoto::AFavor)
     }
     
     /// Vota en contra
-    pub fn votar_en_contra(env: Env) {
-        let mut votos = env.storage()
-            .instance()
-            .get(&DataKeyPropuestaLey::VotosNo)
-            .unwrap_or(0u32);
-        votos += 1;
-        env.storage().instance().set(&DataKeyPropuestaLey::VotosNo, &votos);
+    pub fn votar_en_contra(env: Env, votante: Address) -> Result<i128, ErrorPropuesta> {
+        Self::votar(env, votante, OpcionVoto::EnContra)
+    }
+    
+    /// Se abstiene (cuenta para el quórum)
+    pub fn abstenerse(env: Env, votante: Address) -> Result<i128, ErrorPropuesta> {
+        Self::votar(env, votante, OpcionVoto::Abstencion)
+    }
+    
+    /// Peso con el que votaría `votante` ahora
+    pub fn get_peso(env: Env, votante: Address) -> Result<i128, ErrorPropuesta> {
+        match Self::modo(&env)? {
+            ModoPeso::Token(_) => Ok(env.storage()
+                .persistent()
+                .get(&DataKeyPropuestaLey::Bloqueado(votante))
+                .unwrap_or(0)),
+            ModoPeso::Asignado => Ok(env.storage()
+                .persistent()
+                .get(&DataKeyPropuestaLey::Peso(votante))
+                .unwrap_or(0)),
+        }
+    }
+    
+    /// Voto emitido por `votante` (None si no votó)
+    pub fn get_voto(env: Env, votante: Address) -> Option<OpcionVoto> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyPropuestaLey::Voto(votante))
     }
     
     /// Obtiene votos a favor
-    pub fn get_votos_si(env: Env) -> u32 {
+    pub fn get_votos_si(env: Env) -> i128 {
         env.storage()
             .instance()
             .get(&DataKeyPropuestaLey::VotosSi)
-            .unwrap_or(0u32)
+            .unwrap_or(0i128)
     }
     
     /// Obtiene votos en contra
-    pub fn get_votos_no(env: Env) -> u32 {
+    pub fn get_votos_no(env: Env) -> i128 {
         env.storage()
             .instance()
             .get(&DataKeyPropuestaLey::VotosNo)
-            .unwrap_or(0u32)
+            .unwrap_or(0i128)
+    }
+    
+    /// Obtiene abstenciones
+    pub fn get_abstenciones(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKeyPropuestaLey::Abstenciones)
+            .unwrap_or(0i128)
+    }
+    
+    /// Obtiene las reglas de aprobación
+    pub fn get_reglas(env: Env) -> Result<ReglasVotacion, ErrorPropuesta> {
+        env.storage()
+            .instance()
+            .get(&DataKeyPropuestaLey::Reglas)
+            .ok_or(ErrorPropuesta::NoInicializado)
+    }
+    
+    /// ¿Pasó la propuesta con las reglas configuradas?
+    pub fn aprobada(env: Env) -> bool {
+        PropuestaLey.paso(&env)
+    }
+    
+    /// Admin: define la acción a ejecutar si la propuesta pasa
+    /// 
+    /// Solo una vez y antes del primer voto, para que nadie vote
+    /// una acción distinta de la que se ejecuta. La votación cierra
+    /// en `cierre` y la acción se puede ejecutar desde
+    /// `cierre + retraso`.
+    pub fn definir_accion(
+        env: Env,
+        accion: AccionPropuesta,
+        cierre: u64,
+        retraso: u64,
+    ) -> Result<(), ErrorPropuesta> {
+        let admin: Address = env.storage()
+            .instance()
+            .get(&DataKeyPropuestaLey::Admin)
+            .ok_or(ErrorPropuesta::NoInicializado)?;
+        admin.require_auth();
+        
+        if env.storage().instance().has(&DataKeyPropuestaLey::Ejecucion) {
+            return Err(ErrorPropuesta::AccionYaDefinida);
+        }
+        
+        let participacion = Self::get_votos_si(env.clone())
+            + Self::get_votos_no(env.clone())
+            + Self::get_abstenciones(env.clone());
+        if participacion > 0 {
+            return Err(ErrorPropuesta::VotacionIniciada);
+        }
+        
+        if cierre <= env.ledger().timestamp() {
+            return Err(ErrorPropuesta::FechasInvalidas);
+        }
+        
+        let ejecucion = Ejecucion {
+            accion: accion.clone(),
+            cierre,
+            retraso,
+            ejecutada: false,
+        };
+        env.storage().instance().set(&DataKeyPropuestaLey::Ejecucion, &ejecucion);
+        
+        env.events().publish(
+            (symbol_short!("accion"), accion.contrato),
+            (accion.funcion, cierre, retraso),
+        );
+        
+        Ok(())
+    }
+    
+    /// Ejecuta la acción de una propuesta aprobada (cualquiera puede llamarla)
+    /// 
+    /// Requiere que la votación haya cerrado, que haya pasado el
+    /// retraso y que la propuesta esté aprobada. Se ejecuta una sola
+    /// vez; si la llamada al contrato destino falla, la transacción
+    /// completa se revierte y se puede reintentar.
+    pub fn execute(env: Env) -> Result<(), ErrorPropuesta> {
+        let mut ejecucion = Self::get_ejecucion(env.clone())?;
+        
+        if ejecucion.ejecutada {
+            return Err(ErrorPropuesta::YaEjecutada);
+        }
+        
+        let ahora = env.ledger().timestamp();
+        if ahora < ejecucion.cierre {
+            return Err(ErrorPropuesta::VotacionAbierta);
+        }
+        if ahora < ejecucion.cierre.saturating_add(ejecucion.retraso) {
+            return Err(ErrorPropuesta::RetrasoPendiente);
+        }
+        
+        if !PropuestaLey.paso(&env) {
+            return Err(ErrorPropuesta::NoAprobada);
+        }
+        
+        // Marcar antes de invocar: una re-entrada ve YaEjecutada
+        ejecucion.ejecutada = true;
+        env.storage().instance().set(&DataKeyPropuestaLey::Ejecucion, &ejecucion);
+        
+        let accion = ejecucion.accion;
+        env.invoke_contract::<Val>(
+            &accion.contrato,
+            &accion.funcion,
+            accion.args,
+        );
+        
+        env.events().publish(
+            (symbol_short!("ejecutada"), accion.contrato),
+            accion.funcion,
+        );
+        
+        Ok(())
+    }
+    
+    /// Obtiene la acción y su calendario
+    pub fn get_ejecucion(env: Env) -> Result<Ejecucion, ErrorPropuesta> {
+        env.storage()
+            .instance()
+            .get(&DataKeyPropuestaLey::Ejecucion)
+            .ok_or(ErrorPropuesta::SinAccion)
+    }
+    
+    /// Acepta (o deja de aceptar) delegaciones hacia `delegado`
+    /// 
+    /// Dejar de aceptar no deshace las delegaciones ya recibidas.
+    pub fn aceptar_delegaciones(env: Env, delegado: Address, acepta: bool) {
+        delegado.require_auth();
+        
+        let key = DataKeyPropuestaLey::AceptaDelegaciones(delegado.clone());
+        if acepta {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        
+        env.events().publish(
+            (symbol_short!("acepta"), delegado),
+            acepta,
+        );
+    }
+    
+    /// Delega el peso de `delegadora` en `delegado` (o re-delega)
+    /// 
+    /// El delegado tiene que haber aceptado delegaciones y la
+    /// delegadora tener peso (propio o delegado). Rechaza ciclos,
+    /// cadenas de más de `MAX_PROFUNDIDAD_DELEGACION` saltos y
+    /// árboles de más de `MAX_ARBOL_DELEGACION` direcciones. Si el
+    /// nuevo delegado (o alguien más arriba en la cadena) ya votó,
+    /// el peso se suma a ese voto de inmediato.
+    pub fn delegar(env: Env, delegadora: Address, delegado: Address) -> Result<(), ErrorPropuesta> {
+        delegadora.require_auth();
+        
+        Self::verificar_abierta(&env)?;
+        
+        if Self::ha_votado(&env, &delegadora) {
+            return Err(ErrorPropuesta::YaVoto);
+        }
+        if delegadora == delegado {
+            return Err(ErrorPropuesta::AutoDelegacion);
+        }
+        if !env.storage()
+            .persistent()
+            .has(&DataKeyPropuestaLey::AceptaDelegaciones(delegado.clone()))
+        {
+            return Err(ErrorPropuesta::DelegacionNoAceptada);
+        }
+        if Self::get_poder(env.clone(), delegadora.clone())? <= 0 {
+            return Err(ErrorPropuesta::SinPeso);
+        }
+        
+        // Subir desde el delegado: si aparece la delegadora hay ciclo
+        let mut saltos = Self::altura(&env, &delegadora) + 1;
+        let mut actual = delegado.clone();
+        while let Some(siguiente) = Self::get_delegado(env.clone(), actual.clone()) {
+            if siguiente == delegadora {
+                return Err(ErrorPropuesta::CicloDelegacion);
+            }
+            saltos += 1;
+            actual = siguiente;
+        }
+        if saltos > MAX_PROFUNDIDAD_DELEGACION {
+            return Err(ErrorPropuesta::ProfundidadExcedida);
+        }
+        
+        // `actual` es la raíz del árbol al que se suma (re-delegar dentro
+        // del mismo árbol no cambia su tamaño)
+        if Self::raiz(&env, &delegadora) != actual
+            && Self::tamano(&env, &actual) + Self::tamano(&env, &delegadora) > MAX_ARBOL_DELEGACION
+        {
+            return Err(ErrorPropuesta::ArbolExcedido);
+        }
+        
+        let mut lista = Self::delegadores(&env, &delegado);
+        if lista.len() >= MAX_DELEGADORES {
+            return Err(ErrorPropuesta::DemasiadosDelegadores);
+        }
+        
+        // Sacar el peso del voto anterior y reconectar
+        Self::liberar(&env, &delegadora);
+        Self::desconectar(&env, &delegadora);
+        
+        lista.push_back(delegadora.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKeyPropuestaLey::Delegadores(delegado.clone()), &lista);
+        env.storage()
+            .persistent()
+            .set(&DataKeyPropuestaLey::Delegado(delegadora.clone()), &delegado);
+        
+        if let Some(votante) = Self::votante_final(&env, &delegadora) {
+            Self::absorber(&env, &votante, &delegadora)?;
+        }
+        
+        env.events().publish(
+            (symbol_short!("delegar"), delegadora),
+            delegado,
+        );
+        
+        Ok(())
+    }
+    
+    /// Revoca la delegación: el peso vuelve a la delegadora
+    pub fn revocar_delegacion(env: Env, delegadora: Address) -> Result<(), ErrorPropuesta> {
+        delegadora.require_auth();
+        
+        Self::verificar_abierta(&env)?;
+        
+        if Self::get_delegado(env.clone(), delegadora.clone()).is_none() {
+            return Err(ErrorPropuesta::SinDelegacion);
+        }
+        
+        Self::liberar(&env, &delegadora);
+        let anterior = Self::desconectar(&env, &delegadora);
+        
+        env.events().publish(
+            (symbol_short!("revocar"), delegadora),
+            anterior,
+        );
+        
+        Ok(())
+    }
+    
+    /// Delegado directo de `delegadora` (None si no delegó)
+    pub fn get_delegado(env: Env, delegadora: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyPropuestaLey::Delegado(delegadora))
+    }
+    
+    /// Delegadoras directas de `delegado`
+    pub fn get_delegadores(env: Env, delegado: Address) -> Vec<Address> {
+        Self::delegadores(&env, &delegado)
+    }
+    
+    /// Peso con el que contaría el voto de `votante` ahora: el propio
+    /// más el de sus delegadoras (directas o en cadena) que no votaron
+    pub fn get_poder(env: Env, votante: Address) -> Result<i128, ErrorPropuesta> {
+        let mut nodos = SorobanVec::new(&env);
+        Self::subarbol(&env, &votante, &mut nodos);
+        
+        let mut total = 0i128;
+        for nodo in nodos.iter() {
+            total += Self::get_peso(env.clone(), nodo)?;
+        }
+        Ok(total)
+    }
+    
+    /// Peso con el que cuenta el voto emitido por `votante`
+    pub fn get_peso_voto(env: Env, votante: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKeyPropuestaLey::PesoVoto(votante))
+            .unwrap_or(0)
+    }
+}
+
+// Helpers privados de PropuestaLey
+impl PropuestaLey {
+    fn modo(env: &Env) -> Result<ModoPeso, ErrorPropuesta> {
+        env.storage()
+            .instance()
+            .get(&DataKeyPropuestaLey::Modo)
+            .ok_or(ErrorPropuesta::NoInicializado)
+    }
+    
+    /// Registra el voto de `votante` con su peso y el de sus
+    /// delegadoras que no votaron (una vez por dirección)
+    fn votar(env: Env, votante: Address, opcion: OpcionVoto) -> Result<i128, ErrorPropuesta> {
+        votante.require_auth();
+        
+        Self::verificar_abierta(&env)?;
+        
+        if Self::ha_votado(&env, &votante) {
+            return Err(ErrorPropuesta::YaVoto);
+        }
+        
+        if Self::get_poder(env.clone(), votante.clone())? <= 0 {
+            return Err(ErrorPropuesta::SinPeso);
+        }
+        
+        // Si su delegado ya votó con este peso, lo recupera
+        Self::liberar(&env, &votante);
+        
+        env.storage()
+            .persistent()
+            .set(&DataKeyPropuestaLey::Voto(votante.clone()), &opcion);
+        let peso = Self::absorber(&env, &votante, &votante)?;
+        
+        env.events().publish(
+            (symbol_short!("voto_ley"), votante),
+            (opcion, peso),
+        );
+        
+        Ok(peso)
+    }
+    
+    /// Con acción definida, solo se vota (o delega) hasta el cierre
+    fn verificar_abierta(env: &Env) -> Result<(), ErrorPropuesta> {
+        if let Ok(ejecucion) = Self::get_ejecucion(env.clone()) {
+            if env.ledger().timestamp() >= ejecucion.cierre {
+                return Err(ErrorPropuesta::VotacionCerrada);
+            }
+        }
+        Ok(())
+    }
+    
+    fn ha_votado(env: &Env, direccion: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKeyPropuestaLey::Voto(direccion.clone()))
+    }
+    
+    /// Suma `delta` al total de `opcion`
+    fn sumar_votos(env: &Env, opcion: OpcionVoto, delta: i128) {
+        let key_total = match opcion {
+            OpcionVoto::AFavor => DataKeyPropuestaLey::VotosSi,
+            OpcionVoto::EnContra => DataKeyPropuestaLey::VotosNo,
+            OpcionVoto::Abstencion => DataKeyPropuestaLey::Abstenciones,
+        };
+        let total: i128 = env.storage().instance().get(&key_total).unwrap_or(0);
+        env.storage().instance().set(&key_total, &(total + delta));
+    }
+    
+    /// Suma `delta` al voto de `votante` (su opción y su peso total)
+    fn ajustar_voto(env: &Env, votante: &Address, delta: i128) {
+        let opcion: OpcionVoto = env.storage()
+            .persistent()
+            .get(&DataKeyPropuestaLey::Voto(votante.clone()))
+            .unwrap();
+        Self::sumar_votos(env, opcion, delta);
+        
+        let key_peso = DataKeyPropuestaLey::PesoVoto(votante.clone());
+        let peso: i128 = env.storage().persistent().get(&key_peso).unwrap_or(0);
+        env.storage().persistent().set(&key_peso, &(peso + delta));
+    }
+    
+    /// Delegadoras directas de `delegado`
+    fn delegadores(env: &Env, delegado: &Address) -> SorobanVec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKeyPropuestaLey::Delegadores(delegado.clone()))
+            .unwrap_or(SorobanVec::new(env))
+    }
+    
+    /// `raiz` y las delegadoras (directas o en cadena) cuyo peso
+    /// fluye hacia ella: se corta en quienes ya votaron
+    fn subarbol(env: &Env, raiz: &Address, nodos: &mut SorobanVec<Address>) {
+        nodos.push_back(raiz.clone());
+        for delegadora in Self::delegadores(env, raiz).iter() {
+            if !Self::ha_votado(env, &delegadora) {
+                Self::subarbol(env, &delegadora, nodos);
+            }
+        }
+    }
+    
+    /// Cuenta el peso del subárbol de `raiz` en el voto de `votante`
+    fn absorber(env: &Env, votante: &Address, raiz: &Address) -> Result<i128, ErrorPropuesta> {
+        let mut nodos = SorobanVec::new(env);
+        Self::subarbol(env, raiz, &mut nodos);
+        
+        let mut total = 0i128;
+        for nodo in nodos.iter() {
+            let peso = Self::get_peso(env.clone(), nodo.clone())?;
+            env.storage().persistent().set(
+                &DataKeyPropuestaLey::Absorbido(nodo),
+                &(votante.clone(), peso),
+            );
+            total += peso;
+        }
+        
+        Self::ajustar_voto(env, votante, total);
+        Ok(total)
+    }
+    
+    /// Descuenta el peso del subárbol de `raiz` del voto que lo contaba
+    fn liberar(env: &Env, raiz: &Address) {
+        let mut nodos = SorobanVec::new(env);
+        Self::subarbol(env, raiz, &mut nodos);
+        
+        for nodo in nodos.iter() {
+            let key = DataKeyPropuestaLey::Absorbido(nodo);
+            let absorbido: Option<(Address, i128)> = env.storage().persistent().get(&key);
+            if let Some((votante, peso)) = absorbido {
+                Self::ajustar_voto(env, &votante, -peso);
+                env.storage().persistent().remove(&key);
+            }
+        }
+    }
+    
+    /// Primer delegado que votó siguiendo la cadena desde `direccion`
+    fn votante_final(env: &Env, direccion: &Address) -> Option<Address> {
+        let mut actual = direccion.clone();
+        for _ in 0..MAX_PROFUNDIDAD_DELEGACION {
+            let delegado: Address = env.storage()
+                .persistent()
+                .get(&DataKeyPropuestaLey::Delegado(actual))?;
+            if Self::ha_votado(env, &delegado) {
+                return Some(delegado);
+            }
+            actual = delegado;
+        }
+        None
+    }
+    
+    /// Última dirección de la cadena de delegación desde `direccion`
+    fn raiz(env: &Env, direccion: &Address) -> Address {
+        let mut actual = direccion.clone();
+        while let Some(delegado) = Self::get_delegado(env.clone(), actual.clone()) {
+            actual = delegado;
+        }
+        actual
+    }
+    
+    /// Direcciones del árbol de delegación de `raiz` (incluida), hayan
+    /// votado o no
+    fn tamano(env: &Env, raiz: &Address) -> u32 {
+        let mut total = 1;
+        for delegadora in Self::delegadores(env, raiz).iter() {
+            total += Self::tamano(env, &delegadora);
+        }
+        total
+    }
+    
+    /// Saltos desde las delegadoras más lejanas hasta `direccion`
+    fn altura(env: &Env, direccion: &Address) -> u32 {
+        let mut maxima = 0;
+        for delegadora in Self::delegadores(env, direccion).iter() {
+            maxima = maxima.max(Self::altura(env, &delegadora) + 1);
+        }
+        maxima
+    }
+    
+    /// Quita la delegación de `delegadora` (sin tocar los votos)
+    fn desconectar(env: &Env, delegadora: &Address) -> Option<Address> {
+        let key = DataKeyPropuestaLey::Delegado(delegadora.clone());
+        let anterior: Address = env.storage().persistent().get(&key)?;
+        env.storage().persistent().remove(&key);
+        
+        let mut lista = Self::delegadores(env, &anterior);
+        if let Some(i) = lista.first_index_of(delegadora) {
+            lista.remove(i);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKeyPropuestaLey::Delegadores(anterior.clone()), &lista);
+        
+        Some(anterior)
     }
 }
 
 // Implementación del trait Votable para PropuestaLey
 impl Votable for PropuestaLey {
-    fn votos_a_favor(&self, env: &Env) -> u32 {
+    fn votos_a_favor(&self, env: &Env) -> i128 {
         Self::get_votos_si(env.clone())
     }
     
-    fn votos_en_contra(&self, env: &Env) -> u32 {
+    fn votos_en_contra(&self, env: &Env) -> i128 {
         Self::get_votos_no(env.clone())
     }
     
-    // paso() ya está implementado en el trait con valor por defecto
+    fn abstenciones(&self, env: &Env) -> i128 {
+        Self::get_abstenciones(env.clone())
+    }
+    
+    fn quorum(&self, env: &Env) -> i128 {
+        Self::get_reglas(env.clone()).map(|r| r.quorum).unwrap_or(0)
+    }
+    
+    fn umbral_bps(&self, env: &Env) -> u32 {
+        Self::get_reglas(env.clone()).map(|r| r.umbral_bps).unwrap_or(5_000)
+    }
+    
+    // quorum_alcanzado() y paso() usan la implementación por defecto
 }
 
 /// Función genérica que cuenta propuestas aprobadas
 /// 
-/// Funciona con CUALQUIER tipo que implemente Votable
+/// Funciona con CUALQUIER tipo que implemente Votable, respetando
+/// las reglas (quórum, umbral) de cada propuesta
 pub fn contar_aprobadas<T: Votable>(propuestas: &[T], env: &Env) -> u32 {
     let mut contador = 0u32;
     for propuesta in propuestas.iter() {
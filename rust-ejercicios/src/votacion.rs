@@ -0,0 +1,870 @@
+#![no_std]
+use soroban_sdk::{
+    contract,
+    contractimpl,
+    contracterror,
+    contracttype,
+    Env,
+    Address,
+    Bytes,
+    BytesN,
+    Map,
+    Symbol,
+    Vec,
+    symbol_short,
+};
+use crate::ownable::{
+    self,
+    Error as OwnableError,
+};
+use crate::rate_limit::{
+    self,
+    Error as RateLimitError,
+    LimiteTasa,
+};
+use crate::registro_votantes;
+
+// ============================================================
+// SISTEMA DE VOTACIÓN CON PROPUESTAS
+// ============================================================
+//
+// Evolución del Ejercicio 3.2 (antes en ejercicios_practica.rs):
+// - Propuestas con N opciones, guardadas por ID
+// - Ventana de votación [inicio, fin) en timestamp del ledger
+// - Un voto por dirección y propuesta, con require_auth
+// - Modo secreto (commit-reveal): se publica sha256(opción || salt)
+//   y se revela después, así nadie ve resultados parciales
+// - Modo cuadrático: votantes registrados con un presupuesto de
+//   créditos; n votos a una opción cuestan n² créditos
+// - Modo preferencial: cada papeleta ordena candidatos y el ganador
+//   se calcula por segunda vuelta instantánea después del cierre
+// - Opcional: solo votan direcciones de un registro de usuarios
+//   (ver `registro_votantes.rs`), configurado por el admin
+// - Opcional: límite de votos por dirección y ventana (ver `rate_limit.rs`)
+
+// ============================================================
+// DEFINICIÓN DE ERRORES PERSONALIZADOS
+// ============================================================
+
+/// Errores del sistema de votación
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    PropuestaNoExiste = 1,
+    OpcionesInvalidas = 2,
+    FechasInvalidas = 3,
+    VotacionNoAbierta = 4,
+    VotacionCerrada = 5,
+    YaVoto = 6,
+    OpcionInvalida = 7,
+    ModoIncorrecto = 8,
+    SinCompromiso = 9,
+    RevelacionInvalida = 10,
+    RevelacionNoAbierta = 11,
+    RevelacionCerrada = 12,
+    ResultadosNoDisponibles = 13,
+    NoRegistrado = 14,
+    YaRegistrado = 15,
+    CreditosInsuficientes = 16,
+    VotosInvalidos = 17,
+    PresupuestoInvalido = 18,
+    RankingInvalido = 19,
+    VotanteNoRegistrado = 20,
+    RateLimited = 21,
+}
+
+/// Máximo de opciones por propuesta (acota el costo de leer resultados)
+pub const MAX_OPCIONES: u32 = 10;
+
+/// Máximo de candidatos en modo preferencial
+///
+/// El recuento recorre cada ranking distinto en cada ronda; con 5
+/// candidatos hay como mucho 325 rankings distintos y 5 rondas.
+pub const MAX_CANDIDATOS_PREFERENCIAL: u32 = 5;
+
+// ============================================================
+// TIPOS Y STORAGE
+// ============================================================
+
+/// Cómo se emiten los votos de una propuesta
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ModoVotacion {
+    /// `vote` cuenta al instante
+    Publica,
+    /// `commit_vote` en [inicio, fin), `reveal_vote` en [fin, fin_revelacion)
+    Secreta(u64),
+    /// `vote_quadratic` con este presupuesto de créditos por votante
+    Cuadratica(u32),
+    /// `vote_ranked` con un orden de candidatos; gana por segunda vuelta instantánea
+    Preferencial,
+}
+
+/// Propuesta guardada (Persistent Storage)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Propuesta {
+    pub creador: Address,
+    pub opciones: Vec<Symbol>,
+    pub inicio: u64,            // Primer timestamp en que se puede votar
+    pub fin: u64,               // Desde este timestamp ya no se vota
+    pub votos: Vec<u32>,        // votos[i] = votos de opciones[i]
+    pub creditos: Vec<u32>,     // creditos[i] = créditos gastados en opciones[i]
+    pub total_votos: u32,
+    pub modo: ModoVotacion,
+}
+
+/// Resultado de `get_results` (mismo orden que `opciones`)
+///
+/// Fuera del modo cuadrático cada voto cuesta un crédito.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Resultados {
+    pub votos: Vec<u32>,
+    pub creditos: Vec<u32>,
+}
+
+/// Una ronda de la segunda vuelta instantánea
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ronda {
+    pub conteo: Vec<u32>,       // Papeletas por candidato (0 si ya eliminado)
+    pub eliminados: Vec<u32>,   // Candidatos eliminados al final de la ronda
+}
+
+/// Resultado de `get_winner`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Ganador {
+    SinVotos,
+    Opcion(u32),            // Índice de la opción ganadora
+    Empate(Vec<u32>),       // Índices empatados en el primer lugar
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    // Instance
+    TotalPropuestas,
+
+    // Persistent
+    Propuesta(u32),
+    Voto(u32, Address),             // (propuesta, votante) → índice de opción
+    Compromiso(u32, Address),       // Modo secreto: hash aún no revelado
+    Creditos(u32, Address),         // Modo cuadrático: créditos restantes
+    VotosCuadraticos(u32, Address), // Modo cuadrático: votos por opción
+    Papeletas(u32),                 // Modo preferencial: Map<ranking, cantidad>
+}
+
+// ============================================================
+// CONTRATO
+// ============================================================
+
+/// Sistema de votación con propuestas de N opciones
+#[contract]
+pub struct SistemaVotacion;
+
+#[contractimpl]
+impl SistemaVotacion {
+    /// Define el admin (owner) que configura el registro de votantes
+    ///
+    /// Opcional: sin inicializar, el sistema funciona abierto. Lo
+    /// firma el admin; llamarla en la misma transacción del deploy.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), OwnableError> {
+        admin.require_auth();
+        ownable::inicializar(&env, &admin)
+    }
+
+    /// Admin: solo votan direcciones existentes en `registro`
+    /// (un contrato `GestionUsuario`); None vuelve a abrir el voto
+    pub fn set_voter_registry(env: Env, registro: Option<Address>) -> Result<(), OwnableError> {
+        ownable::require_owner(&env)?;
+        registro_votantes::configurar(&env, registro);
+        Ok(())
+    }
+
+    /// Registro de votantes configurado (None = vota cualquiera)
+    pub fn get_voter_registry(env: Env) -> Option<Address> {
+        registro_votantes::registro(&env)
+    }
+
+    /// Admin: limita las llamadas por dirección (None = sin límite)
+    pub fn set_rate_limit(env: Env, limite: Option<LimiteTasa>) -> Result<(), RateLimitError> {
+        ownable::require_owner(&env).map_err(|_| RateLimitError::SinAdmin)?;
+        rate_limit::configurar(&env, limite)
+    }
+
+    /// Límite de llamadas configurado
+    pub fn get_rate_limit(env: Env) -> Option<LimiteTasa> {
+        rate_limit::limite(&env)
+    }
+
+    /// Crea una propuesta y devuelve su ID
+    ///
+    /// Requiere entre 2 y `MAX_OPCIONES` opciones sin repetir e
+    /// `inicio < fin`.
+    pub fn create_proposal(
+        env: Env,
+        creador: Address,
+        opciones: Vec<Symbol>,
+        inicio: u64,
+        fin: u64,
+    ) -> Result<u32, Error> {
+        Self::crear(env, creador, opciones, inicio, fin, ModoVotacion::Publica)
+    }
+
+    /// Crea una propuesta de voto secreto y devuelve su ID
+    ///
+    /// Compromisos en [inicio, fin), revelaciones en
+    /// [fin, fin_revelacion). Los resultados solo se pueden consultar
+    /// desde `fin_revelacion`.
+    pub fn create_secret_proposal(
+        env: Env,
+        creador: Address,
+        opciones: Vec<Symbol>,
+        inicio: u64,
+        fin: u64,
+        fin_revelacion: u64,
+    ) -> Result<u32, Error> {
+        if fin >= fin_revelacion {
+            return Err(Error::FechasInvalidas);
+        }
+
+        Self::crear(env, creador, opciones, inicio, fin, ModoVotacion::Secreta(fin_revelacion))
+    }
+
+    /// Crea una propuesta de voto cuadrático y devuelve su ID
+    ///
+    /// Cada votante registrado con `register_voter` recibe
+    /// `presupuesto` créditos para esta propuesta.
+    pub fn create_quadratic_proposal(
+        env: Env,
+        creador: Address,
+        opciones: Vec<Symbol>,
+        inicio: u64,
+        fin: u64,
+        presupuesto: u32,
+    ) -> Result<u32, Error> {
+        if presupuesto == 0 {
+            return Err(Error::PresupuestoInvalido);
+        }
+
+        Self::crear(env, creador, opciones, inicio, fin, ModoVotacion::Cuadratica(presupuesto))
+    }
+
+    /// Vota por la opción con índice `opcion` (una vez por propuesta)
+    pub fn vote(env: Env, votante: Address, id: u32, opcion: u32) -> Result<(), Error> {
+        votante.require_auth();
+        Self::verificar_votante(&env, &votante)?;
+
+        let propuesta = Self::leer_propuesta(&env, id)?;
+
+        if propuesta.modo != ModoVotacion::Publica {
+            return Err(Error::ModoIncorrecto);
+        }
+
+        Self::verificar_periodo_voto(&env, &propuesta)?;
+
+        if Self::ha_votado(&env, id, &votante) {
+            return Err(Error::YaVoto);
+        }
+
+        Self::contar_voto(&env, id, propuesta, &votante, opcion)?;
+
+        env.events().publish(
+            (symbol_short!("voto"), id),
+            (votante, opcion),
+        );
+
+        Ok(())
+    }
+
+    /// Modo secreto: guarda `sha256(opcion || salt)` (ver `hash_vote`)
+    ///
+    /// Una vez por propuesta. El evento no incluye la opción.
+    pub fn commit_vote(env: Env, votante: Address, id: u32, compromiso: BytesN<32>) -> Result<(), Error> {
+        votante.require_auth();
+        Self::verificar_votante(&env, &votante)?;
+
+        let propuesta = Self::leer_propuesta(&env, id)?;
+
+        if !matches!(propuesta.modo, ModoVotacion::Secreta(_)) {
+            return Err(Error::ModoIncorrecto);
+        }
+
+        Self::verificar_periodo_voto(&env, &propuesta)?;
+
+        if Self::ha_votado(&env, id, &votante) {
+            return Err(Error::YaVoto);
+        }
+
+        let key = DataKey::Compromiso(id, votante.clone());
+        env.storage().persistent().set(&key, &compromiso);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+
+        env.events().publish(
+            (symbol_short!("compromis"), id),
+            votante,
+        );
+
+        Ok(())
+    }
+
+    /// Modo secreto: revela la opción y la sal del compromiso
+    ///
+    /// Solo en [fin, fin_revelacion). Los compromisos que no se
+    /// revelan a tiempo no cuentan.
+    pub fn reveal_vote(
+        env: Env,
+        votante: Address,
+        id: u32,
+        opcion: u32,
+        salt: BytesN<32>,
+    ) -> Result<(), Error> {
+        votante.require_auth();
+
+        let propuesta = Self::leer_propuesta(&env, id)?;
+
+        let fin_revelacion = match propuesta.modo {
+            ModoVotacion::Secreta(fin_revelacion) => fin_revelacion,
+            _ => return Err(Error::ModoIncorrecto),
+        };
+
+        let ahora = env.ledger().timestamp();
+        if ahora < propuesta.fin {
+            return Err(Error::RevelacionNoAbierta);
+        }
+        if ahora >= fin_revelacion {
+            return Err(Error::RevelacionCerrada);
+        }
+
+        let key = DataKey::Compromiso(id, votante.clone());
+        let compromiso: BytesN<32> = env.storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::SinCompromiso)?;
+
+        if Self::hash_vote(env.clone(), opcion, salt) != compromiso {
+            return Err(Error::RevelacionInvalida);
+        }
+
+        Self::contar_voto(&env, id, propuesta, &votante, opcion)?;
+        env.storage().persistent().remove(&key);
+
+        env.events().publish(
+            (symbol_short!("revelado"), id),
+            (votante, opcion),
+        );
+
+        Ok(())
+    }
+
+    /// Crea una elección preferencial y devuelve su ID
+    ///
+    /// Hasta `MAX_CANDIDATOS_PREFERENCIAL` candidatos. El ganador y
+    /// las rondas se consultan después de `fin`.
+    pub fn create_ranked_proposal(
+        env: Env,
+        creador: Address,
+        opciones: Vec<Symbol>,
+        inicio: u64,
+        fin: u64,
+    ) -> Result<u32, Error> {
+        if opciones.len() > MAX_CANDIDATOS_PREFERENCIAL {
+            return Err(Error::OpcionesInvalidas);
+        }
+
+        Self::crear(env, creador, opciones, inicio, fin, ModoVotacion::Preferencial)
+    }
+
+    /// Modo preferencial: vota con los candidatos en orden de preferencia
+    ///
+    /// No hace falta ordenarlos a todos; si se eliminan todos los de
+    /// la papeleta, deja de contar. `get_vote` devuelve la primera
+    /// preferencia.
+    pub fn vote_ranked(env: Env, votante: Address, id: u32, ranking: Vec<u32>) -> Result<(), Error> {
+        votante.require_auth();
+        Self::verificar_votante(&env, &votante)?;
+
+        let propuesta = Self::leer_propuesta(&env, id)?;
+
+        if propuesta.modo != ModoVotacion::Preferencial {
+            return Err(Error::ModoIncorrecto);
+        }
+
+        Self::verificar_periodo_voto(&env, &propuesta)?;
+
+        if Self::ha_votado(&env, id, &votante) {
+            return Err(Error::YaVoto);
+        }
+
+        if ranking.is_empty() {
+            return Err(Error::RankingInvalido);
+        }
+        for (i, candidato) in ranking.iter().enumerate() {
+            if candidato >= propuesta.opciones.len() {
+                return Err(Error::OpcionInvalida);
+            }
+            if ranking.first_index_of(candidato) != Some(i as u32) {
+                return Err(Error::RankingInvalido);
+            }
+        }
+
+        // La primera preferencia cuenta en `votos` (ronda 1)
+        Self::contar_voto(&env, id, propuesta, &votante, ranking.get_unchecked(0))?;
+
+        // Papeletas iguales se agrupan: el tamaño depende de los
+        // rankings distintos, no de la cantidad de votantes
+        let key = DataKey::Papeletas(id);
+        let mut papeletas: Map<Vec<u32>, u32> = env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Map::new(&env));
+        let cantidad = papeletas.get(ranking.clone()).unwrap_or(0);
+        papeletas.set(ranking.clone(), cantidad + 1);
+        env.storage().persistent().set(&key, &papeletas);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+
+        env.events().publish(
+            (symbol_short!("voto_pref"), id),
+            (votante, ranking),
+        );
+
+        Ok(())
+    }
+
+    /// Modo preferencial: rondas de la segunda vuelta instantánea
+    ///
+    /// Solo después del cierre.
+    pub fn get_rounds(env: Env, id: u32) -> Result<Vec<Ronda>, Error> {
+        let propuesta = Self::leer_resultados(&env, id)?;
+
+        if propuesta.modo != ModoVotacion::Preferencial {
+            return Err(Error::ModoIncorrecto);
+        }
+
+        Ok(Self::segunda_vuelta(&env, id, &propuesta).0)
+    }
+
+    /// Modo cuadrático: el creador registra a un votante
+    ///
+    /// El votante recibe el presupuesto de créditos de la propuesta.
+    /// Se puede registrar hasta el cierre de la votación.
+    pub fn register_voter(env: Env, id: u32, votante: Address) -> Result<(), Error> {
+        let propuesta = Self::leer_propuesta(&env, id)?;
+        propuesta.creador.require_auth();
+
+        let presupuesto = match propuesta.modo {
+            ModoVotacion::Cuadratica(presupuesto) => presupuesto,
+            _ => return Err(Error::ModoIncorrecto),
+        };
+
+        if env.ledger().timestamp() >= propuesta.fin {
+            return Err(Error::VotacionCerrada);
+        }
+
+        let key = DataKey::Creditos(id, votante.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::YaRegistrado);
+        }
+
+        env.storage().persistent().set(&key, &presupuesto);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+
+        env.events().publish(
+            (symbol_short!("registro"), id),
+            votante,
+        );
+
+        Ok(())
+    }
+
+    /// Modo cuadrático: suma `votos` a una opción
+    ///
+    /// Tener n votos en una opción cuesta n² créditos, así que pasar
+    /// de a a a+votos cuesta (a+votos)² - a². Se puede repartir el
+    /// presupuesto entre varias opciones con varias llamadas.
+    /// Devuelve los créditos restantes.
+    pub fn vote_quadratic(
+        env: Env,
+        votante: Address,
+        id: u32,
+        opcion: u32,
+        votos: u32,
+    ) -> Result<u32, Error> {
+        votante.require_auth();
+        Self::verificar_votante(&env, &votante)?;
+
+        let mut propuesta = Self::leer_propuesta(&env, id)?;
+
+        if !matches!(propuesta.modo, ModoVotacion::Cuadratica(_)) {
+            return Err(Error::ModoIncorrecto);
+        }
+
+        Self::verificar_periodo_voto(&env, &propuesta)?;
+
+        if votos == 0 {
+            return Err(Error::VotosInvalidos);
+        }
+
+        let key_creditos = DataKey::Creditos(id, votante.clone());
+        let restantes: u32 = env.storage()
+            .persistent()
+            .get(&key_creditos)
+            .ok_or(Error::NoRegistrado)?;
+
+        let key_votos = DataKey::VotosCuadraticos(id, votante.clone());
+        let mut mis_votos = Self::get_quadratic_votes(env.clone(), id, votante.clone())?;
+        let previos = mis_votos.get(opcion).ok_or(Error::OpcionInvalida)?;
+
+        // En u64 para que n² no desborde
+        let nuevos = previos as u64 + votos as u64;
+        let costo = nuevos * nuevos - (previos as u64) * (previos as u64);
+        if costo > restantes as u64 {
+            return Err(Error::CreditosInsuficientes);
+        }
+        let costo = costo as u32;
+
+        mis_votos.set(opcion, nuevos as u32);
+        env.storage().persistent().set(&key_votos, &mis_votos);
+        env.storage().persistent().extend_ttl(&key_votos, 100, 100);
+
+        let restantes = restantes - costo;
+        env.storage().persistent().set(&key_creditos, &restantes);
+
+        let total = propuesta.votos.get_unchecked(opcion);
+        propuesta.votos.set(opcion, total + votos);
+        let gastado = propuesta.creditos.get_unchecked(opcion);
+        propuesta.creditos.set(opcion, gastado + costo);
+        propuesta.total_votos += votos;
+        Self::guardar_propuesta(&env, id, &propuesta);
+
+        env.events().publish(
+            (symbol_short!("voto_cuad"), id),
+            (votante, opcion, votos, costo),
+        );
+
+        Ok(restantes)
+    }
+
+    /// Modo cuadrático: créditos que le quedan a `votante`
+    pub fn get_credits(env: Env, id: u32, votante: Address) -> Result<u32, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Creditos(id, votante))
+            .ok_or(Error::NoRegistrado)
+    }
+
+    /// Modo cuadrático: votos de `votante` por opción
+    pub fn get_quadratic_votes(env: Env, id: u32, votante: Address) -> Result<Vec<u32>, Error> {
+        let propuesta = Self::leer_propuesta(&env, id)?;
+
+        Ok(env.storage()
+            .persistent()
+            .get(&DataKey::VotosCuadraticos(id, votante))
+            .unwrap_or(Self::ceros(&env, propuesta.opciones.len())))
+    }
+
+    /// Compromiso para `commit_vote`: sha256(opcion en big endian || salt)
+    ///
+    /// La sal debe ser aleatoria y secreta hasta revelar; si no,
+    /// basta probar cada opción para descubrir el voto.
+    pub fn hash_vote(env: Env, opcion: u32, salt: BytesN<32>) -> BytesN<32> {
+        let mut datos = Bytes::from_array(&env, &opcion.to_be_bytes());
+        datos.append(&salt.into());
+        env.crypto().sha256(&datos)
+    }
+
+    /// Obtiene una propuesta
+    ///
+    /// Mientras los resultados no se publican (ver `get_results`),
+    /// `votos`, `creditos` y `total_votos` vienen en cero.
+    pub fn get_proposal(env: Env, id: u32) -> Result<Propuesta, Error> {
+        let mut propuesta = Self::leer_propuesta(&env, id)?;
+
+        if !Self::resultados_publicos(&env, &propuesta) {
+            let largo = propuesta.opciones.len();
+            propuesta.votos = Self::ceros(&env, largo);
+            propuesta.creditos = Self::ceros(&env, largo);
+            propuesta.total_votos = 0;
+        }
+
+        Ok(propuesta)
+    }
+
+    /// Cantidad de propuestas creadas
+    pub fn get_total_proposals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalPropuestas)
+            .unwrap_or(0)
+    }
+
+    /// Obtiene votos y créditos gastados por opción (mismo orden que `opciones`)
+    ///
+    /// En modo secreto, solo después de `fin_revelacion`.
+    pub fn get_results(env: Env, id: u32) -> Result<Resultados, Error> {
+        let propuesta = Self::leer_resultados(&env, id)?;
+
+        Ok(Resultados {
+            votos: propuesta.votos,
+            creditos: propuesta.creditos,
+        })
+    }
+
+    /// Obtiene el ganador: una opción, un empate o sin votos
+    ///
+    /// En modo preferencial, por segunda vuelta instantánea.
+    pub fn get_winner(env: Env, id: u32) -> Result<Ganador, Error> {
+        let propuesta = Self::leer_resultados(&env, id)?;
+
+        if propuesta.modo == ModoVotacion::Preferencial {
+            return Ok(Self::segunda_vuelta(&env, id, &propuesta).1);
+        }
+
+        if propuesta.total_votos == 0 {
+            return Ok(Ganador::SinVotos);
+        }
+
+        let mut maximo = 0u32;
+        for votos in propuesta.votos.iter() {
+            maximo = maximo.max(votos);
+        }
+
+        let mut primeros = Vec::new(&env);
+        for (i, votos) in propuesta.votos.iter().enumerate() {
+            if votos == maximo {
+                primeros.push_back(i as u32);
+            }
+        }
+
+        if primeros.len() == 1 {
+            Ok(Ganador::Opcion(primeros.get_unchecked(0)))
+        } else {
+            Ok(Ganador::Empate(primeros))
+        }
+    }
+
+    /// Opción votada por `votante` (None si no votó)
+    pub fn get_vote(env: Env, id: u32, votante: Address) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Voto(id, votante))
+    }
+}
+
+// Helpers privados
+impl SistemaVotacion {
+    fn crear(
+        env: Env,
+        creador: Address,
+        opciones: Vec<Symbol>,
+        inicio: u64,
+        fin: u64,
+        modo: ModoVotacion,
+    ) -> Result<u32, Error> {
+        creador.require_auth();
+
+        if opciones.len() < 2 || opciones.len() > MAX_OPCIONES {
+            return Err(Error::OpcionesInvalidas);
+        }
+
+        for (i, opcion) in opciones.iter().enumerate() {
+            if opciones.first_index_of(&opcion) != Some(i as u32) {
+                return Err(Error::OpcionesInvalidas);
+            }
+        }
+
+        if inicio >= fin {
+            return Err(Error::FechasInvalidas);
+        }
+
+        let id: u32 = env.storage()
+            .instance()
+            .get(&DataKey::TotalPropuestas)
+            .unwrap_or(0);
+
+        let propuesta = Propuesta {
+            creador: creador.clone(),
+            votos: Self::ceros(&env, opciones.len()),
+            creditos: Self::ceros(&env, opciones.len()),
+            opciones,
+            inicio,
+            fin,
+            total_votos: 0,
+            modo,
+        };
+
+        Self::guardar_propuesta(&env, id, &propuesta);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalPropuestas, &(id + 1));
+        env.storage().instance().extend_ttl(100, 100);
+
+        env.events().publish(
+            (symbol_short!("propuesta"), creador),
+            id,
+        );
+
+        Ok(id)
+    }
+
+    /// Con registro configurado, el votante tiene que estar dado de
+    /// alta; con límite configurado, le tienen que quedar llamadas
+    fn verificar_votante(env: &Env, votante: &Address) -> Result<(), Error> {
+        if !registro_votantes::esta_habilitado(env, votante) {
+            return Err(Error::VotanteNoRegistrado);
+        }
+        rate_limit::consumir(env, votante).map_err(|_| Error::RateLimited)?;
+        Ok(())
+    }
+
+    /// Verifica que estemos en [inicio, fin)
+    fn verificar_periodo_voto(env: &Env, propuesta: &Propuesta) -> Result<(), Error> {
+        let ahora = env.ledger().timestamp();
+        if ahora < propuesta.inicio {
+            return Err(Error::VotacionNoAbierta);
+        }
+        if ahora >= propuesta.fin {
+            return Err(Error::VotacionCerrada);
+        }
+        Ok(())
+    }
+
+    /// ¿Ya votó (o se comprometió a votar)?
+    fn ha_votado(env: &Env, id: u32, votante: &Address) -> bool {
+        env.storage().persistent().has(&DataKey::Voto(id, votante.clone()))
+            || env.storage().persistent().has(&DataKey::Compromiso(id, votante.clone()))
+    }
+
+    /// Suma el voto y registra la opción de `votante`
+    fn contar_voto(
+        env: &Env,
+        id: u32,
+        mut propuesta: Propuesta,
+        votante: &Address,
+        opcion: u32,
+    ) -> Result<(), Error> {
+        let votos = propuesta.votos
+            .get(opcion)
+            .ok_or(Error::OpcionInvalida)?;
+
+        propuesta.votos.set(opcion, votos + 1);
+        let gastado = propuesta.creditos.get_unchecked(opcion);
+        propuesta.creditos.set(opcion, gastado + 1);
+        propuesta.total_votos += 1;
+        Self::guardar_propuesta(env, id, &propuesta);
+
+        let key_voto = DataKey::Voto(id, votante.clone());
+        env.storage().persistent().set(&key_voto, &opcion);
+        env.storage().persistent().extend_ttl(&key_voto, 100, 100);
+
+        Ok(())
+    }
+
+    /// Lee la propuesta si sus resultados ya se pueden publicar
+    fn leer_resultados(env: &Env, id: u32) -> Result<Propuesta, Error> {
+        let propuesta = Self::leer_propuesta(env, id)?;
+
+        if !Self::resultados_publicos(env, &propuesta) {
+            return Err(Error::ResultadosNoDisponibles);
+        }
+
+        Ok(propuesta)
+    }
+
+    /// ¿Ya se pueden publicar los votos de `propuesta`?
+    fn resultados_publicos(env: &Env, propuesta: &Propuesta) -> bool {
+        let publicar_desde = match propuesta.modo {
+            ModoVotacion::Secreta(fin_revelacion) => fin_revelacion,
+            ModoVotacion::Preferencial => propuesta.fin,
+            _ => 0,
+        };
+        env.ledger().timestamp() >= publicar_desde
+    }
+
+    /// Segunda vuelta instantánea sobre las papeletas de `id`
+    ///
+    /// En cada ronda cada papeleta cuenta para su primer candidato
+    /// no eliminado. Gana quien supere la mitad de las papeletas que
+    /// siguen contando; si no, se eliminan todos los candidatos
+    /// empatados en el último lugar. Si todos empatan, es empate.
+    fn segunda_vuelta(env: &Env, id: u32, propuesta: &Propuesta) -> (Vec<Ronda>, Ganador) {
+        let papeletas: Map<Vec<u32>, u32> = env.storage()
+            .persistent()
+            .get(&DataKey::Papeletas(id))
+            .unwrap_or(Map::new(env));
+
+        let candidatos = propuesta.opciones.len();
+        let mut activos: u32 = (1 << candidatos) - 1;   // Bit i = candidato i sigue
+        let mut rondas = Vec::new(env);
+
+        loop {
+            let mut conteo = Self::ceros(env, candidatos);
+            let mut total = 0u32;
+            for (ranking, cantidad) in papeletas.iter() {
+                if let Some(candidato) = ranking.iter().find(|c| activos & (1 << c) != 0) {
+                    conteo.set(candidato, conteo.get_unchecked(candidato) + cantidad);
+                    total += cantidad;
+                }
+            }
+
+            if total == 0 {
+                rondas.push_back(Ronda { conteo, eliminados: Vec::new(env) });
+                return (rondas, Ganador::SinVotos);
+            }
+
+            let mut minimo = u32::MAX;
+            for candidato in 0..candidatos {
+                let votos = conteo.get_unchecked(candidato);
+                if activos & (1 << candidato) == 0 {
+                    continue;
+                }
+                if votos * 2 > total {
+                    rondas.push_back(Ronda { conteo, eliminados: Vec::new(env) });
+                    return (rondas, Ganador::Opcion(candidato));
+                }
+                minimo = minimo.min(votos);
+            }
+
+            let mut eliminados = Vec::new(env);
+            for candidato in 0..candidatos {
+                if activos & (1 << candidato) != 0 && conteo.get_unchecked(candidato) == minimo {
+                    eliminados.push_back(candidato);
+                }
+            }
+
+            if eliminados.len() == activos.count_ones() {
+                rondas.push_back(Ronda { conteo, eliminados: Vec::new(env) });
+                return (rondas, Ganador::Empate(eliminados));
+            }
+
+            for candidato in eliminados.iter() {
+                activos &= !(1 << candidato);
+            }
+            rondas.push_back(Ronda { conteo, eliminados });
+        }
+    }
+
+    fn leer_propuesta(env: &Env, id: u32) -> Result<Propuesta, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Propuesta(id))
+            .ok_or(Error::PropuestaNoExiste)
+    }
+
+    fn ceros(env: &Env, largo: u32) -> Vec<u32> {
+        let mut ceros = Vec::new(env);
+        for _ in 0..largo {
+            ceros.push_back(0u32);
+        }
+        ceros
+    }
+
+    fn guardar_propuesta(env: &Env, id: u32, propuesta: &Propuesta) {
+        let key = DataKey::Propuesta(id);
+        env.storage().persistent().set(&key, propuesta);
+        env.storage().persistent().extend_ttl(&key, 100, 100);
+    }
+}
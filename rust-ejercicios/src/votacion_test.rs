@@ -0,0 +1,556 @@
+#![cfg(test)]
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address,
+    BytesN,
+    Env,
+    Symbol,
+    Vec,
+    symbol_short,
+    vec,
+};
+use crate::votacion::{
+    Error,
+    Ganador,
+    ModoVotacion,
+    Ronda,
+    SistemaVotacion,
+    SistemaVotacionClient,
+    MAX_CANDIDATOS_PREFERENCIAL,
+    MAX_OPCIONES,
+};
+use crate::rate_limit::LimiteTasa;
+use crate::storage_patterns::{GestionUsuario, GestionUsuarioClient};
+
+fn setup<'a>(env: &Env) -> SistemaVotacionClient<'a> {
+    let contract_id = env.register_contract(None, SistemaVotacion);
+    SistemaVotacionClient::new(env, &contract_id)
+}
+
+fn opciones_abc(env: &Env) -> Vec<Symbol> {
+    vec![env, symbol_short!("A"), symbol_short!("B"), symbol_short!("C")]
+}
+
+#[test]
+fn test_sistema_votacion() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+
+    let id = client.create_proposal(&creador, &opciones_abc(&env), &0, &100);
+
+    // Cada dirección vota una vez
+    for opcion in [0u32, 0, 0, 1, 2] {
+        client.vote(&Address::generate(&env), &id, &opcion);
+    }
+
+    let resultados = client.get_results(&id);
+    assert_eq!(resultados.votos, vec![&env, 3u32, 1, 1]);
+    // Un voto, un crédito
+    assert_eq!(resultados.creditos, resultados.votos);
+    assert_eq!(client.get_winner(&id), Ganador::Opcion(0));
+    assert_eq!(client.get_proposal(&id).total_votos, 5);
+}
+
+#[test]
+fn test_sistema_votacion_empate() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+
+    let id = client.create_proposal(&creador, &opciones_abc(&env), &0, &100);
+    assert_eq!(client.get_winner(&id), Ganador::SinVotos);
+
+    client.vote(&Address::generate(&env), &id, &0);
+    client.vote(&Address::generate(&env), &id, &2);
+
+    assert_eq!(client.get_winner(&id), Ganador::Empate(vec![&env, 0u32, 2]));
+}
+
+#[test]
+fn test_un_voto_por_direccion() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+    let votante = Address::generate(&env);
+
+    let id = client.create_proposal(&creador, &opciones_abc(&env), &0, &100);
+    client.vote(&votante, &id, &1);
+
+    // La firma exigida es la del votante
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, votante);
+
+    assert_eq!(client.try_vote(&votante, &id, &2), Err(Ok(Error::YaVoto)));
+    assert_eq!(client.get_vote(&id, &votante), Some(1));
+
+    // Puede votar en otra propuesta
+    let otra = client.create_proposal(&creador, &opciones_abc(&env), &0, &100);
+    client.vote(&votante, &otra, &0);
+    assert_eq!(client.get_results(&id).votos, vec![&env, 0u32, 1, 0]);
+    assert_eq!(client.get_total_proposals(), 2);
+}
+
+#[test]
+fn test_ventana_de_votacion() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+    let votante = Address::generate(&env);
+
+    let id = client.create_proposal(&creador, &opciones_abc(&env), &10, &20);
+
+    env.ledger().with_mut(|li| li.timestamp = 9);
+    assert_eq!(client.try_vote(&votante, &id, &0), Err(Ok(Error::VotacionNoAbierta)));
+
+    env.ledger().with_mut(|li| li.timestamp = 20);
+    assert_eq!(client.try_vote(&votante, &id, &0), Err(Ok(Error::VotacionCerrada)));
+
+    env.ledger().with_mut(|li| li.timestamp = 19);
+    client.vote(&votante, &id, &0);
+}
+
+#[test]
+fn test_validaciones() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+
+    // Menos de dos opciones
+    let una = vec![&env, symbol_short!("A")];
+    assert_eq!(
+        client.try_create_proposal(&creador, &una, &0, &100),
+        Err(Ok(Error::OpcionesInvalidas))
+    );
+
+    // Opciones repetidas
+    let repetidas = vec![&env, symbol_short!("A"), symbol_short!("A")];
+    assert_eq!(
+        client.try_create_proposal(&creador, &repetidas, &0, &100),
+        Err(Ok(Error::OpcionesInvalidas))
+    );
+
+    // Demasiadas opciones
+    let nombres = ["o0", "o1", "o2", "o3", "o4", "o5", "o6", "o7", "o8", "o9", "o10"];
+    let mut muchas = Vec::new(&env);
+    for nombre in nombres.iter().take(MAX_OPCIONES as usize + 1) {
+        muchas.push_back(Symbol::new(&env, nombre));
+    }
+    assert_eq!(
+        client.try_create_proposal(&creador, &muchas, &0, &100),
+        Err(Ok(Error::OpcionesInvalidas))
+    );
+
+    assert_eq!(
+        client.try_create_proposal(&creador, &opciones_abc(&env), &100, &100),
+        Err(Ok(Error::FechasInvalidas))
+    );
+
+    let id = client.create_proposal(&creador, &opciones_abc(&env), &0, &100);
+    assert_eq!(
+        client.try_vote(&Address::generate(&env), &id, &3),
+        Err(Ok(Error::OpcionInvalida))
+    );
+    assert_eq!(client.try_get_results(&7), Err(Ok(Error::PropuestaNoExiste)));
+}
+
+/// Propuesta secreta: compromisos en [0, 100), revelación en [100, 200)
+fn propuesta_secreta(env: &Env, client: &SistemaVotacionClient) -> u32 {
+    let creador = Address::generate(env);
+    client.create_secret_proposal(&creador, &opciones_abc(env), &0, &100, &200)
+}
+
+#[test]
+fn test_voto_secreto() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let id = propuesta_secreta(&env, &client);
+    assert_eq!(client.get_proposal(&id).modo, ModoVotacion::Secreta(200));
+
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+    let caro = Address::generate(&env);
+    let sal_ana = BytesN::from_array(&env, &[1; 32]);
+    let sal_bea = BytesN::from_array(&env, &[2; 32]);
+    let sal_caro = BytesN::from_array(&env, &[3; 32]);
+
+    client.commit_vote(&ana, &id, &client.hash_vote(&2, &sal_ana));
+    client.commit_vote(&bea, &id, &client.hash_vote(&2, &sal_bea));
+    client.commit_vote(&caro, &id, &client.hash_vote(&0, &sal_caro));
+
+    // Nada visible durante la votación
+    assert_eq!(client.try_get_results(&id), Err(Ok(Error::ResultadosNoDisponibles)));
+    assert_eq!(client.get_vote(&id, &ana), None);
+    assert_eq!(
+        client.try_reveal_vote(&ana, &id, &2, &sal_ana),
+        Err(Ok(Error::RevelacionNoAbierta))
+    );
+
+    // Revelar: ana y bea; caro nunca revela
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    client.reveal_vote(&ana, &id, &2, &sal_ana);
+    client.reveal_vote(&bea, &id, &2, &sal_bea);
+    assert_eq!(client.get_vote(&id, &ana), Some(2));
+    assert_eq!(client.try_get_winner(&id), Err(Ok(Error::ResultadosNoDisponibles)));
+
+    // Los recuentos de la propuesta tampoco se ven antes de tiempo
+    let propuesta = client.get_proposal(&id);
+    assert_eq!(propuesta.votos, vec![&env, 0u32, 0, 0]);
+    assert_eq!(propuesta.total_votos, 0);
+
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(
+        client.try_reveal_vote(&caro, &id, &0, &sal_caro),
+        Err(Ok(Error::RevelacionCerrada))
+    );
+
+    // El compromiso sin revelar no cuenta
+    assert_eq!(client.get_results(&id).votos, vec![&env, 0u32, 0, 2]);
+    assert_eq!(client.get_winner(&id), Ganador::Opcion(2));
+    assert_eq!(client.get_proposal(&id).total_votos, 2);
+}
+
+#[test]
+fn test_voto_secreto_validaciones() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let id = propuesta_secreta(&env, &client);
+    let publica = client.create_proposal(&Address::generate(&env), &opciones_abc(&env), &0, &100);
+
+    let votante = Address::generate(&env);
+    let sal = BytesN::from_array(&env, &[7; 32]);
+    let compromiso = client.hash_vote(&1, &sal);
+
+    // Cada modo con su función
+    assert_eq!(client.try_vote(&votante, &id, &1), Err(Ok(Error::ModoIncorrecto)));
+    assert_eq!(
+        client.try_commit_vote(&votante, &publica, &compromiso),
+        Err(Ok(Error::ModoIncorrecto))
+    );
+
+    client.commit_vote(&votante, &id, &compromiso);
+    assert_eq!(
+        client.try_commit_vote(&votante, &id, &compromiso),
+        Err(Ok(Error::YaVoto))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 150);
+
+    // Otra opción u otra sal no coinciden con el compromiso
+    assert_eq!(
+        client.try_reveal_vote(&votante, &id, &2, &sal),
+        Err(Ok(Error::RevelacionInvalida))
+    );
+    let otra_sal = BytesN::from_array(&env, &[8; 32]);
+    assert_eq!(
+        client.try_reveal_vote(&votante, &id, &1, &otra_sal),
+        Err(Ok(Error::RevelacionInvalida))
+    );
+
+    let sin_compromiso = Address::generate(&env);
+    assert_eq!(
+        client.try_reveal_vote(&sin_compromiso, &id, &1, &sal),
+        Err(Ok(Error::SinCompromiso))
+    );
+
+    // Se revela una sola vez
+    client.reveal_vote(&votante, &id, &1, &sal);
+    assert_eq!(
+        client.try_reveal_vote(&votante, &id, &1, &sal),
+        Err(Ok(Error::SinCompromiso))
+    );
+
+    // La fase de revelación debe terminar después de la de votación
+    assert_eq!(
+        client.try_create_secret_proposal(&votante, &opciones_abc(&env), &0, &100, &100),
+        Err(Ok(Error::FechasInvalidas))
+    );
+}
+
+/// Propuesta cuadrática con 100 créditos por votante registrado
+fn propuesta_cuadratica(env: &Env, client: &SistemaVotacionClient, votantes: &[&Address]) -> u32 {
+    let creador = Address::generate(env);
+    let id = client.create_quadratic_proposal(&creador, &opciones_abc(env), &0, &100, &100);
+    for votante in votantes {
+        client.register_voter(&id, votante);
+    }
+    id
+}
+
+#[test]
+fn test_voto_cuadratico() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let ana = Address::generate(&env);
+    let bea = Address::generate(&env);
+    let id = propuesta_cuadratica(&env, &client, &[&ana, &bea]);
+
+    // ana concentra: 10 votos a A cuestan 100 créditos
+    assert_eq!(client.vote_quadratic(&ana, &id, &0, &10), 0);
+
+    // bea reparte: 6 a B (36) y luego 2 más (64 - 36 = 28), 6 a C (36)
+    assert_eq!(client.vote_quadratic(&bea, &id, &1, &6), 64);
+    assert_eq!(client.vote_quadratic(&bea, &id, &1, &2), 36);
+    assert_eq!(client.vote_quadratic(&bea, &id, &2, &6), 0);
+    assert_eq!(client.get_quadratic_votes(&id, &bea), vec![&env, 0u32, 8, 6]);
+
+    let resultados = client.get_results(&id);
+    assert_eq!(resultados.votos, vec![&env, 10u32, 8, 6]);
+    assert_eq!(resultados.creditos, vec![&env, 100u32, 64, 36]);
+    assert_eq!(client.get_winner(&id), Ganador::Opcion(0));
+    assert_eq!(client.get_proposal(&id).total_votos, 24);
+}
+
+#[test]
+fn test_voto_cuadratico_validaciones() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let ana = Address::generate(&env);
+    let id = propuesta_cuadratica(&env, &client, &[&ana]);
+
+    // Registro: lo firma el creador, una vez por votante
+    let auths = env.auths();
+    let creador = client.get_proposal(&id).creador;
+    assert_eq!(auths[0].0, creador);
+    assert_eq!(client.try_register_voter(&id, &ana), Err(Ok(Error::YaRegistrado)));
+
+    let intrusa = Address::generate(&env);
+    assert_eq!(
+        client.try_vote_quadratic(&intrusa, &id, &0, &1),
+        Err(Ok(Error::NoRegistrado))
+    );
+
+    // 11² = 121 > 100
+    assert_eq!(
+        client.try_vote_quadratic(&ana, &id, &0, &11),
+        Err(Ok(Error::CreditosInsuficientes))
+    );
+    client.vote_quadratic(&ana, &id, &0, &7);
+    // Quedan 51: pasar de 7 a 11 votos cuesta 121 - 49 = 72
+    assert_eq!(
+        client.try_vote_quadratic(&ana, &id, &0, &4),
+        Err(Ok(Error::CreditosInsuficientes))
+    );
+    assert_eq!(client.get_credits(&id, &ana), 51);
+
+    assert_eq!(client.try_vote_quadratic(&ana, &id, &0, &0), Err(Ok(Error::VotosInvalidos)));
+    assert_eq!(client.try_vote_quadratic(&ana, &id, &3, &1), Err(Ok(Error::OpcionInvalida)));
+    assert_eq!(client.try_vote(&ana, &id, &0), Err(Ok(Error::ModoIncorrecto)));
+
+    // Sin presupuesto no hay propuesta
+    assert_eq!(
+        client.try_create_quadratic_proposal(&creador, &opciones_abc(&env), &0, &100, &0),
+        Err(Ok(Error::PresupuestoInvalido))
+    );
+
+    // Ni registro ni votos después del cierre
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(
+        client.try_register_voter(&id, &intrusa),
+        Err(Ok(Error::VotacionCerrada))
+    );
+    assert_eq!(
+        client.try_vote_quadratic(&ana, &id, &1, &1),
+        Err(Ok(Error::VotacionCerrada))
+    );
+}
+
+/// Emite papeletas preferenciales: `cantidad` votantes por ranking
+fn votar_rankings(env: &Env, client: &SistemaVotacionClient, id: u32, rankings: &[(u32, Vec<u32>)]) {
+    for (cantidad, ranking) in rankings {
+        for _ in 0..*cantidad {
+            client.vote_ranked(&Address::generate(env), &id, ranking);
+        }
+    }
+}
+
+#[test]
+fn test_segunda_vuelta_instantanea() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+    let opciones = vec![&env, symbol_short!("A"), symbol_short!("B"), symbol_short!("C"), symbol_short!("D")];
+    let id = client.create_ranked_proposal(&creador, &opciones, &0, &100);
+
+    // A gana en primeras preferencias pero no tiene mayoría
+    votar_rankings(&env, &client, id, &[
+        (4, vec![&env, 0u32, 3]),
+        (3, vec![&env, 1u32, 2]),
+        (2, vec![&env, 2u32, 1]),
+        (1, vec![&env, 3u32]),
+    ]);
+
+    // Nada antes del cierre
+    assert_eq!(client.try_get_winner(&id), Err(Ok(Error::ResultadosNoDisponibles)));
+    assert_eq!(client.get_proposal(&id).votos, vec![&env, 0u32, 0, 0, 0]);
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.get_proposal(&id).total_votos, 10);
+
+    // Primeras preferencias
+    assert_eq!(client.get_results(&id).votos, vec![&env, 4u32, 3, 2, 1]);
+
+    // D sale primero (su papeleta se agota), luego C pasa a B
+    assert_eq!(
+        client.get_rounds(&id),
+        vec![
+            &env,
+            Ronda { conteo: vec![&env, 4u32, 3, 2, 1], eliminados: vec![&env, 3u32] },
+            Ronda { conteo: vec![&env, 4u32, 3, 2, 0], eliminados: vec![&env, 2u32] },
+            Ronda { conteo: vec![&env, 4u32, 5, 0, 0], eliminados: vec![&env] },
+        ]
+    );
+    assert_eq!(client.get_winner(&id), Ganador::Opcion(1));
+}
+
+#[test]
+fn test_segunda_vuelta_empate() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+    let id = client.create_ranked_proposal(&creador, &opciones_abc(&env), &0, &100);
+
+    votar_rankings(&env, &client, id, &[
+        (3, vec![&env, 0u32]),
+        (3, vec![&env, 1u32]),
+        (1, vec![&env, 2u32, 0]),
+        (1, vec![&env, 2u32, 1]),
+    ]);
+
+    // C sale y reparte 1 y 1: A y B quedan 4 a 4
+    env.ledger().with_mut(|li| li.timestamp = 100);
+    assert_eq!(client.get_winner(&id), Ganador::Empate(vec![&env, 0u32, 1]));
+    assert_eq!(client.get_rounds(&id).len(), 2);
+
+    // Sin papeletas
+    let vacia = client.create_ranked_proposal(&creador, &opciones_abc(&env), &100, &200);
+    env.ledger().with_mut(|li| li.timestamp = 200);
+    assert_eq!(client.get_winner(&vacia), Ganador::SinVotos);
+}
+
+#[test]
+fn test_voto_preferencial_validaciones() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let creador = Address::generate(&env);
+    let votante = Address::generate(&env);
+    let id = client.create_ranked_proposal(&creador, &opciones_abc(&env), &0, &100);
+
+    assert_eq!(
+        client.try_vote_ranked(&votante, &id, &Vec::new(&env)),
+        Err(Ok(Error::RankingInvalido))
+    );
+    assert_eq!(
+        client.try_vote_ranked(&votante, &id, &vec![&env, 1u32, 0, 1]),
+        Err(Ok(Error::RankingInvalido))
+    );
+    assert_eq!(
+        client.try_vote_ranked(&votante, &id, &vec![&env, 1u32, 3]),
+        Err(Ok(Error::OpcionInvalida))
+    );
+
+    client.vote_ranked(&votante, &id, &vec![&env, 2u32, 0]);
+    assert_eq!(client.get_vote(&id, &votante), Some(2));
+    assert_eq!(
+        client.try_vote_ranked(&votante, &id, &vec![&env, 0u32]),
+        Err(Ok(Error::YaVoto))
+    );
+    assert_eq!(client.try_vote(&votante, &id, &0), Err(Ok(Error::ModoIncorrecto)));
+
+    // Las rondas son solo para elecciones preferenciales
+    let publica = client.create_proposal(&creador, &opciones_abc(&env), &0, &100);
+    assert_eq!(client.try_get_rounds(&publica), Err(Ok(Error::ModoIncorrecto)));
+
+    // Candidatos acotados
+    let nombres = ["c0", "c1", "c2", "c3", "c4", "c5"];
+    let mut muchas = Vec::new(&env);
+    for nombre in nombres.iter().take(MAX_CANDIDATOS_PREFERENCIAL as usize + 1) {
+        muchas.push_back(Symbol::new(&env, nombre));
+    }
+    assert_eq!(
+        client.try_create_ranked_proposal(&creador, &muchas, &0, &100),
+        Err(Ok(Error::OpcionesInvalidas))
+    );
+}
+
+#[test]
+fn test_registro_de_votantes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let admin = Address::generate(&env);
+    let creador = Address::generate(&env);
+
+    // Sin admin no se puede configurar
+    assert!(client.try_set_voter_registry(&None).is_err());
+    client.initialize(&admin);
+    assert_eq!(env.auths()[0].0, admin);
+
+    let registro_id = env.register_contract(None, GestionUsuario);
+    let registro = GestionUsuarioClient::new(&env, &registro_id);
+    let registrado = Address::generate(&env);
+    registro.initialize(&admin);
+    registro.crear_usuario(&registrado, &0, &0);
+
+    client.set_voter_registry(&Some(registro_id.clone()));
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(client.get_voter_registry(), Some(registro_id));
+
+    let id = client.create_proposal(&creador, &opciones_abc(&env), &0, &100);
+    client.vote(&registrado, &id, &0);
+
+    let anonimo = Address::generate(&env);
+    assert_eq!(client.try_vote(&anonimo, &id, &1), Err(Ok(Error::VotanteNoRegistrado)));
+
+    // También en voto secreto y preferencial
+    let secreta = client.create_secret_proposal(&creador, &opciones_abc(&env), &0, &100, &200);
+    let hash = client.hash_vote(&1, &BytesN::from_array(&env, &[7; 32]));
+    assert_eq!(
+        client.try_commit_vote(&anonimo, &secreta, &hash),
+        Err(Ok(Error::VotanteNoRegistrado))
+    );
+    let preferencial = client.create_ranked_proposal(&creador, &opciones_abc(&env), &0, &100);
+    assert_eq!(
+        client.try_vote_ranked(&anonimo, &preferencial, &vec![&env, 0, 1]),
+        Err(Ok(Error::VotanteNoRegistrado))
+    );
+
+    // Sin registro vuelve a votar cualquiera
+    client.set_voter_registry(&None);
+    client.vote(&anonimo, &id, &1);
+    assert_eq!(client.get_results(&id).votos, vec![&env, 1, 1, 0]);
+}
+
+#[test]
+fn test_limite_de_votos_por_ventana() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = setup(&env);
+    let admin = Address::generate(&env);
+    let creador = Address::generate(&env);
+    let votante = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.set_rate_limit(&Some(LimiteTasa { max_llamadas: 2, ventana: 50 }));
+    assert_eq!(env.auths()[0].0, admin);
+
+    let ids = [0u32; 3].map(|_| client.create_proposal(&creador, &opciones_abc(&env), &0, &100));
+    client.vote(&votante, &ids[0], &0);
+    client.vote(&votante, &ids[1], &0);
+    assert_eq!(client.try_vote(&votante, &ids[2], &0), Err(Ok(Error::RateLimited)));
+
+    env.ledger().with_mut(|li| li.sequence_number += 50);
+    client.vote(&votante, &ids[2], &0);
+}